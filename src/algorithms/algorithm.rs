@@ -1,5 +1,11 @@
 use crate::graph::graph::GraphSnapshot;
+use std::cell::{Ref, RefCell};
+use std::fmt;
+use std::rc::Rc;
 
+/// Steps kept for the inspector. A run goes on unrecorded past that, so
+/// large graphs stay cheap, and only its final state is added.
+pub const MAX_RECORDED_STEPS: usize = 1000;
 
 pub struct Snapshot<T> {
 	pub data: T,
@@ -8,4 +14,103 @@ pub struct Snapshot<T> {
 pub trait Algorithm<T> {
 	fn run(&mut self);
 	fn get_snapshot(&self) -> Snapshot<T>;
+	/// Runtime states recorded at every step of the last `run`, oldest first,
+	/// at most `MAX_RECORDED_STEPS` of them plus the final one.
+	fn get_history(&self) -> &[T];
+}
+
+/// Appends `runtime` to `history` unless `MAX_RECORDED_STEPS` is reached.
+pub fn record<T: Clone>(history: &mut Vec<T>, runtime: &T) {
+	if history.len() < MAX_RECORDED_STEPS {
+		history.push(runtime.clone());
+	}
+}
+
+/// Appends the final `runtime` of a run, kept even past the cap, unless it
+/// is the step recorded last.
+pub fn record_last<T: Clone + PartialEq>(history: &mut Vec<T>, runtime: &T) {
+	if history.last() != Some(runtime) {
+		history.push(runtime.clone());
+	}
+}
+
+/// Drops all but the last step of a full `history`, so steps appended after
+/// a run (e.g. another query against a built table) are still recorded.
+pub fn make_room<T>(history: &mut Vec<T>) {
+	if history.len() >= MAX_RECORDED_STEPS {
+		history.drain(..history.len() - 1);
+	}
+}
+
+/// Append-only list for runtime logs. Every clone shares one buffer and
+/// sees the items pushed before it was taken, so recording a step costs O(1)
+/// however long the log grows.
+pub struct SharedLog<T> {
+	items: Rc<RefCell<Vec<T>>>,
+	len: usize,
+}
+
+impl<T: Clone> SharedLog<T> {
+	pub fn new() -> Self {
+		SharedLog { items: Rc::new(RefCell::new(Vec::new())), len: 0 }
+	}
+	pub fn push(&mut self, item: T) {
+		// A clone that fell behind the buffer copies its own part first.
+		if self.items.borrow().len() != self.len {
+			let own = self.items.borrow()[..self.len].to_vec();
+			self.items = Rc::new(RefCell::new(own));
+		}
+		self.items.borrow_mut().push(item);
+		self.len += 1;
+	}
+	pub fn items(&self) -> Ref<'_, [T]> {
+		Ref::map(self.items.borrow(), |items| &items[..self.len])
+	}
+	pub fn len(&self) -> usize {
+		self.len
+	}
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+}
+impl<T: Clone> Default for SharedLog<T> {
+	fn default() -> Self {
+		SharedLog::new()
+	}
+}
+impl<T> Clone for SharedLog<T> {
+	fn clone(&self) -> Self {
+		SharedLog { items: Rc::clone(&self.items), len: self.len }
+	}
+}
+impl<T: Clone + PartialEq> PartialEq for SharedLog<T> {
+	fn eq(&self, other: &Self) -> bool {
+		*self.items() == *other.items()
+	}
+}
+impl<T: Clone + fmt::Debug> fmt::Debug for SharedLog<T> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_list().entries(self.items().iter()).finish()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_shared_log_clones_keep_their_length() -> Result<(), String> {
+		let mut log = SharedLog::new();
+		log.push(1);
+		let step = log.clone();
+		log.push(2);
+		assert_eq!(*step.items(), [1]);
+		assert_eq!(*log.items(), [1, 2]);
+
+		let mut fork = step.clone();
+		fork.push(3);
+		assert_eq!(*fork.items(), [1, 3]);
+		assert_eq!(*log.items(), [1, 2]);
+		Ok(())
+	}
 }
\ No newline at end of file
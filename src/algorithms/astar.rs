@@ -0,0 +1,297 @@
+use crate::{
+    algorithms::algorithm::{Algorithm, MAX_RECORDED_STEPS, Snapshot, record, record_last},
+    algorithms::inspect::{Inspect, Overlay, Variable, VariableKind},
+    graph::graph::Graph,
+    layouts::layout::position,
+};
+use ordered_float::OrderedFloat;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+/// Estimate of the remaining distance to the target. The geometric ones
+/// measure canvas positions, so they only underestimate when weights are at
+/// least as long as the drawn edges.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Heuristic {
+    Euclidean,
+    Manhattan,
+    /// Turns A* into Dijkstra.
+    Zero,
+    /// Values given per node; nodes left out get 0.
+    Table(HashMap<u32, f64>),
+}
+
+impl Heuristic {
+    pub fn estimate(&self, graph: &Graph, node: u32, target: u32) -> f64 {
+        let ends = position(graph, node).zip(position(graph, target));
+        match (self, ends) {
+            (Heuristic::Euclidean, Some(((x1, y1), (x2, y2)))) => (x1 - x2).hypot(y1 - y2),
+            (Heuristic::Manhattan, Some(((x1, y1), (x2, y2)))) => (x1 - x2).abs() + (y1 - y2).abs(),
+            (Heuristic::Table(values), _) => values.get(&node).copied().unwrap_or(0.0),
+            _ => 0.0,
+        }
+    }
+}
+
+/// Reads a heuristic table written as `node=value` pairs, separated by
+/// commas or spaces, e.g. `1=4, 2=2.5`.
+pub fn parse_heuristic(input: &str) -> Result<HashMap<u32, f64>, String> {
+    input
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let invalid = || format!("Invalid heuristic entry: {}", pair);
+            let (node, value) = pair.split_once('=').ok_or_else(invalid)?;
+            let node = node.parse().map_err(|_| invalid())?;
+            let value: f64 = value.parse().map_err(|_| invalid())?;
+            match value.is_finite() && value >= 0.0 {
+                true => Ok((node, value)),
+                false => Err(invalid()),
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Runtime {
+    pub active_node: Option<u32>,
+    /// Open set as `(f, node)`, smallest `f` first.
+    pub open: Vec<(f64, u32)>,
+    pub closed: HashSet<u32>,
+    /// Cheapest known distance from the source.
+    pub g: HashMap<u32, u64>,
+    pub h: HashMap<u32, f64>,
+    pub parent: HashMap<u32, u32>,
+    /// Source to target, once the target is closed.
+    pub path: Vec<u32>,
+}
+/// A* from `source` to `target`: expands the open node with the smallest
+/// `f = g + h`. A closed node that is later reached more cheaply, which only
+/// happens with an inconsistent heuristic, is opened again.
+#[derive(Debug)]
+pub struct AStar {
+    pub graph: Graph,
+    pub source: u32,
+    pub target: u32,
+    pub heuristic: Heuristic,
+    pub runtime: Runtime,
+    pub history: Vec<Runtime>,
+    heap: BinaryHeap<Reverse<(OrderedFloat<f64>, u32)>>,
+}
+
+impl Runtime {
+    fn f(&self, node: u32) -> f64 {
+        self.g[&node] as f64 + self.h[&node]
+    }
+}
+
+impl AStar {
+    pub fn new(graph: Graph, source: u32, target: u32, heuristic: Heuristic) -> Option<Self> {
+        if !graph.nodes.contains_key(&source) || !graph.nodes.contains_key(&target) {
+            return None;
+        }
+        Some(AStar {
+            graph,
+            source,
+            target,
+            heuristic,
+            runtime: Runtime {
+                active_node: None,
+                open: Vec::new(),
+                closed: HashSet::new(),
+                g: HashMap::new(),
+                h: HashMap::new(),
+                parent: HashMap::new(),
+                path: Vec::new(),
+            },
+            history: Vec::new(),
+            heap: BinaryHeap::new(),
+        })
+    }
+    /// Live heap entries, for display only.
+    fn open_set(&self) -> Vec<(f64, u32)> {
+        let mut open: Vec<(f64, u32)> = self
+            .heap
+            .iter()
+            .map(|Reverse((f, node))| (f.into_inner(), *node))
+            .filter(|(f, node)| !self.runtime.closed.contains(node) && *f == self.runtime.f(*node))
+            .collect();
+        open.sort_unstable_by(|(f, node), (g, other)| f.total_cmp(g).then(node.cmp(other)));
+        open.dedup();
+        open
+    }
+    fn record_step(&mut self) {
+        if self.history.len() < MAX_RECORDED_STEPS {
+            self.runtime.open = self.open_set();
+            record(&mut self.history, &self.runtime);
+        }
+    }
+    fn open(&mut self, node: u32, g: u64) {
+        let h = *self
+            .runtime
+            .h
+            .entry(node)
+            .or_insert_with(|| self.heuristic.estimate(&self.graph, node, self.target));
+        self.runtime.g.insert(node, g);
+        self.runtime.closed.remove(&node);
+        self.heap.push(Reverse((OrderedFloat(g as f64 + h), node)));
+    }
+}
+impl Algorithm<Runtime> for AStar {
+    fn get_snapshot(&self) -> Snapshot<Runtime> {
+        Snapshot {
+            data: self.runtime.clone(),
+            graph: self.graph.to_snapshot().unwrap(),
+        }
+    }
+    fn get_history(&self) -> &[Runtime] {
+        &self.history
+    }
+    fn run(&mut self) {
+        self.open(self.source, 0);
+        self.record_step();
+        while let Some(Reverse((f, u))) = self.heap.pop() {
+            if self.runtime.closed.contains(&u) || f.into_inner() != self.runtime.f(u) {
+                continue;
+            }
+            self.runtime.active_node = Some(u);
+            self.runtime.closed.insert(u);
+            if u == self.target {
+                let mut path = vec![u];
+                while let Some(parent) = path.last().and_then(|v| self.runtime.parent.get(v)) {
+                    path.push(*parent);
+                }
+                path.reverse();
+                self.runtime.path = path;
+                break;
+            }
+            let edges: Vec<(u32, u32)> = self.graph.nodes[&u]
+                .runtime
+                .edges
+                .values()
+                .map(|edge| (edge.runtime.node_end, edge.runtime.weight))
+                .filter(|(v, _)| self.graph.nodes.contains_key(v))
+                .collect();
+            for (v, weight) in edges {
+                let g = self.runtime.g[&u] + weight as u64;
+                if self.runtime.g.get(&v).is_some_and(|known| *known <= g) {
+                    continue;
+                }
+                self.runtime.parent.insert(v, u);
+                self.open(v, g);
+            }
+            self.record_step();
+        }
+        if self.runtime.path.is_empty() {
+            self.runtime.active_node = None;
+        }
+        self.runtime.open = self.open_set();
+        record_last(&mut self.history, &self.runtime);
+    }
+}
+impl Inspect for Runtime {
+    fn variables(&self) -> Vec<Variable> {
+        let active = self
+            .active_node
+            .map_or(String::from("-"), |id| id.to_string());
+        let f: HashMap<u32, f64> = self.g.keys().map(|id| (*id, self.f(*id))).collect();
+        let open = self
+            .open
+            .iter()
+            .map(|(f, node)| format!("{} (f={:.1})", node, f));
+        let mut closed: Vec<u32> = self.closed.iter().copied().collect();
+        closed.sort_unstable();
+        vec![
+            Variable::scalar("active_node", active),
+            Variable::list("open", VariableKind::Queue, open),
+            Variable::list("closed", VariableKind::Array, closed.iter()),
+            Variable::map("g", &self.g, |g| g.to_string()),
+            Variable::map("h", &self.h, |h| format!("{:.1}", h)),
+            Variable::map("f", &f, |f| format!("{:.1}", f)),
+            Variable::map("parent", &self.parent, |id| id.to_string()),
+            Variable::list("path", VariableKind::Array, self.path.iter()),
+        ]
+    }
+    fn overlay(&self) -> Overlay {
+        let mut node_colors: HashMap<u32, usize> =
+            self.open.iter().map(|(_, node)| (*node, 0)).collect();
+        node_colors.extend(self.closed.iter().map(|node| (*node, 1)));
+        Overlay {
+            node_annotations: self
+                .g
+                .iter()
+                .map(|(id, g)| (*id, format!("{}+{:.1}", g, self.h[id])))
+                .collect(),
+            highlighted_nodes: self.active_node.into_iter().collect(),
+            highlighted_edges: self
+                .path
+                .windows(2)
+                .map(|pair| (pair[0], pair[1]))
+                .collect(),
+            marked_edges: self.parent.iter().map(|(v, u)| (*u, *v)).collect(),
+            node_colors,
+            ..Overlay::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn setup_grid() -> Graph {
+        // A 3x3 grid with nodes 100 apart and weights matching the drawing.
+        let mut edges = Vec::new();
+        for row in 0..3 {
+            for column in 0..3 {
+                let id = row * 3 + column;
+                if column < 2 {
+                    edges.push((id, id + 1, 100));
+                }
+                if row < 2 {
+                    edges.push((id, id + 3, 100));
+                }
+            }
+        }
+        let mut graph = Graph::from_list_of_weighted_edges(edges, true);
+        for node in graph.nodes.values_mut() {
+            node.gui_model.position_x = OrderedFloat((node.id % 3) as f64 * 100.0);
+            node.gui_model.position_y = OrderedFloat((node.id / 3) as f64 * 100.0);
+        }
+        graph
+    }
+
+    #[test]
+    fn test_astar_heuristics_agree_on_distance() -> Result<(), String> {
+        let mut closed = Vec::new();
+        for heuristic in [Heuristic::Zero, Heuristic::Euclidean, Heuristic::Manhattan] {
+            let mut my_search = AStar::new(setup_grid(), 0, 2, heuristic).ok_or("Valid ends")?;
+
+            my_search.run();
+
+            assert_eq!(my_search.runtime.path, vec![0, 1, 2]);
+            assert_eq!(my_search.runtime.g[&2], 200);
+            closed.push(my_search.runtime.closed.len());
+        }
+        // Only the top row looks promising to the geometric heuristics.
+        assert_eq!((closed[1], closed[2]), (3, 3));
+        assert!(closed[0] > 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_astar_table_heuristic() -> Result<(), String> {
+        // Manhattan distances, except node 3 looks worse than it is.
+        let table = parse_heuristic("0=400, 1=300, 2=200, 3=350 4=200 5=100 6=200 7=100")?;
+        assert_eq!(table[&2], 200.0);
+        assert!(parse_heuristic("1:4").is_err());
+        let mut my_search =
+            AStar::new(setup_grid(), 0, 8, Heuristic::Table(table)).ok_or("Valid ends")?;
+
+        my_search.run();
+
+        assert_eq!(my_search.runtime.path, vec![0, 1, 2, 5, 8]);
+        let first = &my_search.get_history()[1];
+        assert_eq!(first.open, vec![(400.0, 1), (450.0, 3)]);
+        Ok(())
+    }
+}
@@ -1,20 +1,26 @@
 use crate::{
-    algorithms::algorithm::{Algorithm, Snapshot},
+    algorithms::algorithm::{Algorithm, SharedLog, Snapshot, record, record_last},
+    algorithms::inspect::{Inspect, Overlay, Variable, VariableKind},
     graph::graph::Graph,
 };
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Runtime {
     pub active_node: u32,
     pub distances: HashMap<u32, i32>,
     pub queue: VecDeque<u32>,
-    pub log: Vec<String>,
+    pub parents: HashMap<u32, u32>,
+    /// Nodes grouped by BFS level, in discovery order.
+    pub levels: Vec<Vec<u32>>,
+    pub log: SharedLog<String>,
 }
 #[derive(Debug, PartialEq)]
 pub struct BFS {
     pub graph: Graph,
+    pub source: u32,
     pub runtime: Runtime,
+    pub history: Vec<Runtime>,
 }
 
 impl BFS {
@@ -25,12 +31,16 @@ impl BFS {
         let distances = graph.nodes.keys().map(|&key| (key, -1)).collect();
         return Some(BFS {
             graph,
+            source,
             runtime: Runtime {
                 queue: VecDeque::new(),
                 distances,
+                parents: HashMap::new(),
+                levels: Vec::new(),
                 active_node: 9999,
-                log: Vec::new(),
+                log: SharedLog::new(),
             },
+            history: Vec::new(),
         });
     }
 }
@@ -38,39 +48,36 @@ impl BFS {
     fn add_log_line(&mut self, data: String) {
         self.runtime.log.push(data);
     }
+    fn record_step(&mut self) {
+        record(&mut self.history, &self.runtime);
+    }
+    /// Textbook BFS: a node is marked with its level when first enqueued and
+    /// never enqueued again, so edge weights play no part.
     fn bfs_loop(&mut self, source: u32) {
-        self.runtime.queue.push_front(source);
+        self.runtime.queue.push_back(source);
         self.runtime.distances.insert(source, 0);
+        self.runtime.levels = vec![vec![source]];
         self.runtime.active_node = source;
+        self.record_step();
 
         while let Some(u) = self.runtime.queue.pop_front() {
             self.runtime.active_node = u;
-            let u_distance = *self.runtime.distances.get(&u).expect("It exists");
-            println!(
-                "Tamos en el nodo {} con distancia: {}",
-                self.runtime.active_node, u_distance
-            );
-            for v in self
-                .graph
-                .nodes
-                .get(&u)
-                .expect("It should exist")
-                .runtime
-                .edges
-                .values()
-            {
-                let current_distance = *self
-                    .runtime
-                    .distances
-                    .get(&v.runtime.node_end)
-                    .expect("It exists");
-                let weight = v.runtime.weight as i32;
-                if current_distance == -1 || current_distance >= weight + u_distance {
-                    self.runtime
-                        .distances
-                        .insert(v.runtime.node_end, weight + u_distance);
-                    self.runtime.queue.push_back(v.runtime.node_end);
+            self.record_step();
+            let level = self.runtime.distances[&u] + 1;
+            let mut ends: Vec<u32> = self.graph.nodes[&u].runtime.edges.keys().copied().collect();
+            ends.sort_unstable();
+            for v in ends {
+                if self.runtime.distances.get(&v) != Some(&-1) {
+                    continue;
+                }
+                self.runtime.distances.insert(v, level);
+                self.runtime.parents.insert(v, u);
+                self.runtime.queue.push_back(v);
+                match self.runtime.levels.get_mut(level as usize) {
+                    Some(nodes) => nodes.push(v),
+                    None => self.runtime.levels.push(vec![v]),
                 }
+                self.record_step();
             }
         }
 
@@ -98,6 +105,9 @@ impl Algorithm<Runtime> for BFS {
             graph: self.graph.to_snapshot().unwrap(),
         }
     }
+    fn get_history(&self) -> &[Runtime] {
+        &self.history
+    }
 
     fn run(&mut self) {
         let node_indexes: Vec<u32> = self.graph.nodes.keys().cloned().collect();
@@ -118,13 +128,43 @@ impl Algorithm<Runtime> for BFS {
         }
 
         // Start with the BFS
-        self.bfs_loop(0);
+        self.bfs_loop(self.source);
+        record_last(&mut self.history, &self.runtime);
+    }
+}
+impl Inspect for Runtime {
+    fn variables(&self) -> Vec<Variable> {
+        let levels = self.levels.iter().map(|nodes| format!("{:?}", nodes));
+        vec![
+            Variable::scalar("active_node", self.active_node),
+            Variable::list("queue", VariableKind::Queue, self.queue.iter()),
+            Variable::map("distances", &self.distances, |distance| match distance {
+                -1 => String::from("inf"),
+                d => d.to_string(),
+            }),
+            Variable::map("parents", &self.parents, |parent| parent.to_string()),
+            Variable::list("levels", VariableKind::Array, levels),
+        ]
+    }
+    fn overlay(&self) -> Overlay {
+        Overlay {
+            node_annotations: self
+                .distances
+                .iter()
+                .filter(|(_, distance)| **distance != -1)
+                .map(|(node, distance)| (*node, format!("d={}", distance)))
+                .collect(),
+            highlighted_nodes: HashSet::from([self.active_node]),
+            highlighted_edges: self.parents.iter().map(|(v, u)| (*u, *v)).collect(),
+            ..Overlay::default()
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::algorithms::algorithm::MAX_RECORDED_STEPS;
     fn setup_left_chain() -> Graph {
         let edges = vec![(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 6)];
         Graph::from_list_of_edges(edges, false)
@@ -146,4 +186,62 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_bfs_visits_each_node_once() -> Result<(), String> {
+        // Weights must not matter: the heavy edge 0-3 still gives level 1.
+        let edges = vec![(0, 1, 1), (0, 2, 1), (1, 3, 1), (2, 3, 1), (0, 3, 9)];
+        let my_graph = Graph::from_list_of_weighted_edges(edges, true);
+        let mut my_bfs = BFS::new(my_graph, 0).ok_or("Source exists")?;
+
+        my_bfs.run();
+
+        // Besides one step per dequeued node, there are the start and the end.
+        let enqueued = my_bfs.get_history().len() - 2 - my_bfs.graph.nodes.len();
+        assert_eq!(enqueued, 3);
+        assert_eq!(my_bfs.runtime.distances[&3], 1);
+        assert_eq!(my_bfs.runtime.levels, vec![vec![0], vec![1, 2, 3]]);
+        assert_eq!(my_bfs.runtime.parents.len(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_bfs_history_is_capped() -> Result<(), String> {
+        let path: Vec<(u32, u32)> = (0..2000).map(|id| (id, id + 1)).collect();
+        let mut my_bfs = BFS::new(Graph::from_list_of_edges(path, false), 0).ok_or("Source")?;
+
+        my_bfs.run();
+
+        let history = my_bfs.get_history();
+        assert_eq!(history.len(), MAX_RECORDED_STEPS + 1);
+        assert_eq!(history.last(), Some(&my_bfs.runtime));
+        assert_eq!(my_bfs.runtime.distances[&2000], 2000);
+        Ok(())
+    }
+
+    #[test]
+    fn test_bfs_records_history_and_variables() -> Result<(), String> {
+        let my_graph = setup_left_chain();
+        let mut my_bfs = BFS::new(my_graph, 2).unwrap();
+
+        my_bfs.run();
+
+        let history = my_bfs.get_history();
+        assert!(history.len() > 1, "Every step should be recorded");
+        assert_eq!(history[0].active_node, 2);
+        assert_eq!(my_bfs.runtime.distances.get(&0).unwrap().clone(), -1);
+        assert_eq!(my_bfs.runtime.parents.get(&6), Some(&5));
+
+        let names: Vec<String> = my_bfs
+            .runtime
+            .variables()
+            .into_iter()
+            .map(|variable| variable.name)
+            .collect();
+        assert_eq!(
+            names,
+            vec!["active_node", "queue", "distances", "parents", "levels"]
+        );
+        Ok(())
+    }
 }
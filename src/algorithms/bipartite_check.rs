@@ -0,0 +1,214 @@
+use crate::{
+    algorithms::algorithm::{Algorithm, Snapshot, record, record_last},
+    algorithms::inspect::{Inspect, Overlay, Variable, VariableKind},
+    graph::graph::Graph,
+    layouts::layout::{sorted_ids, undirected_neighbors},
+};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Runtime {
+    pub active_node: Option<u32>,
+    /// Side of every colored node: `false` for the left, `true` for the right.
+    pub side: HashMap<u32, bool>,
+    pub parent: HashMap<u32, u32>,
+    pub queue: VecDeque<u32>,
+    /// Edge found inside one side, if any.
+    pub conflict: Option<(u32, u32)>,
+    /// Odd cycle through the conflict edge, in edge order.
+    pub odd_cycle: Vec<u32>,
+}
+/// BFS 2-coloring, ignoring edge direction. Either every node ends up on a
+/// side with no edge inside a side, or an odd cycle proves that impossible.
+#[derive(Debug, PartialEq)]
+pub struct BipartiteCheck {
+    pub graph: Graph,
+    pub runtime: Runtime,
+    pub history: Vec<Runtime>,
+}
+
+impl BipartiteCheck {
+    pub fn new(graph: Graph) -> Self {
+        BipartiteCheck {
+            graph,
+            runtime: Runtime {
+                active_node: None,
+                side: HashMap::new(),
+                parent: HashMap::new(),
+                queue: VecDeque::new(),
+                conflict: None,
+                odd_cycle: Vec::new(),
+            },
+            history: Vec::new(),
+        }
+    }
+    fn record_step(&mut self) {
+        record(&mut self.history, &self.runtime);
+    }
+    fn ancestors(&self, mut node: u32) -> Vec<u32> {
+        let mut path = vec![node];
+        while let Some(parent) = self.runtime.parent.get(&node) {
+            path.push(*parent);
+            node = *parent;
+        }
+        path
+    }
+    /// Both BFS tree paths from `u` and `v` up to their common ancestor,
+    /// closed by the edge `v - u`. Same side means same depth parity, so the
+    /// cycle is odd.
+    fn odd_cycle(&self, u: u32, v: u32) -> Vec<u32> {
+        let from_u = self.ancestors(u);
+        let on_u_path: HashSet<u32> = from_u.iter().copied().collect();
+        let mut from_v = self.ancestors(v);
+        let meet = from_v
+            .iter()
+            .position(|node| on_u_path.contains(node))
+            .expect("Same BFS tree");
+        let common = from_v[meet];
+        from_v.truncate(meet);
+        let up_to_common = from_u.iter().position(|node| *node == common).unwrap();
+        let mut cycle = from_u[..=up_to_common].to_vec();
+        cycle.extend(from_v.into_iter().rev());
+        cycle
+    }
+}
+impl Algorithm<Runtime> for BipartiteCheck {
+    fn get_snapshot(&self) -> Snapshot<Runtime> {
+        Snapshot {
+            data: self.runtime.clone(),
+            graph: self.graph.to_snapshot().unwrap(),
+        }
+    }
+    fn get_history(&self) -> &[Runtime] {
+        &self.history
+    }
+    fn run(&mut self) {
+        let neighbors = undirected_neighbors(&self.graph);
+        for root in sorted_ids(&self.graph) {
+            if self.runtime.side.contains_key(&root) {
+                continue;
+            }
+            self.runtime.side.insert(root, false);
+            self.runtime.queue.push_back(root);
+            while let Some(u) = self.runtime.queue.pop_front() {
+                self.runtime.active_node = Some(u);
+                self.record_step();
+                let color = self.runtime.side[&u];
+                for v in neighbors[&u].iter() {
+                    match self.runtime.side.get(v) {
+                        None => {
+                            self.runtime.side.insert(*v, !color);
+                            self.runtime.parent.insert(*v, u);
+                            self.runtime.queue.push_back(*v);
+                        }
+                        Some(other) if *other == color => {
+                            self.runtime.conflict = Some((u, *v));
+                            self.runtime.odd_cycle = self.odd_cycle(u, *v);
+                            self.runtime.active_node = None;
+                            record_last(&mut self.history, &self.runtime);
+                            return;
+                        }
+                        Some(_) => {}
+                    }
+                }
+            }
+        }
+        self.runtime.active_node = None;
+        record_last(&mut self.history, &self.runtime);
+    }
+}
+impl Inspect for Runtime {
+    fn variables(&self) -> Vec<Variable> {
+        let active = self
+            .active_node
+            .map_or(String::from("-"), |id| id.to_string());
+        let conflict = self
+            .conflict
+            .map_or(String::from("-"), |(u, v)| format!("{}-{}", u, v));
+        vec![
+            Variable::scalar("active_node", active),
+            Variable::map("side", &self.side, |side| (*side as u8).to_string()),
+            Variable::map("parent", &self.parent, |id| id.to_string()),
+            Variable::list("queue", VariableKind::Queue, self.queue.iter()),
+            Variable::scalar("conflict", conflict),
+            Variable::list("odd_cycle", VariableKind::Array, self.odd_cycle.iter()),
+        ]
+    }
+    fn overlay(&self) -> Overlay {
+        let mut overlay = Overlay {
+            highlighted_nodes: self.active_node.into_iter().collect(),
+            node_colors: self
+                .side
+                .iter()
+                .map(|(id, side)| (*id, *side as usize))
+                .collect(),
+            ..Overlay::default()
+        };
+        overlay.highlight_cycle(&self.odd_cycle);
+        // Direction is ignored, so the cycle may use edges stored either way.
+        let reversed: Vec<(u32, u32)> = overlay
+            .highlighted_edges
+            .iter()
+            .map(|(u, v)| (*v, *u))
+            .collect();
+        overlay.highlighted_edges.extend(reversed);
+        overlay
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bipartite_check_colors_even_cycle() -> Result<(), String> {
+        let edges = vec![(0, 1), (1, 2), (2, 3), (3, 0), (4, 5)];
+        let mut my_check = BipartiteCheck::new(Graph::from_list_of_edges(edges, true));
+
+        my_check.run();
+
+        let side = &my_check.runtime.side;
+        assert!(my_check.runtime.conflict.is_none());
+        assert_eq!(
+            (side[&0], side[&1], side[&2], side[&3]),
+            (false, true, false, true)
+        );
+        assert_ne!(side[&4], side[&5]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_bipartite_check_returns_odd_cycle() -> Result<(), String> {
+        // A 5-cycle with a pendant node.
+        let edges = vec![(0, 1), (1, 2), (2, 3), (3, 4), (4, 0), (2, 5)];
+        let mut my_check = BipartiteCheck::new(Graph::from_list_of_edges(edges, true));
+
+        my_check.run();
+
+        let cycle = &my_check.runtime.odd_cycle;
+        assert_eq!(cycle.len(), 5);
+        assert!(!cycle.contains(&5));
+        let overlay = my_check.runtime.overlay();
+        for (u, v) in cycle.iter().zip(cycle.iter().cycle().skip(1)) {
+            assert!(my_check.graph.get_edge(*u, *v).is_some());
+            assert!(overlay.is_edge_highlighted(*u, *v, false));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_bipartite_check_odd_cycle_on_directed_graph() -> Result<(), String> {
+        // The 5-cycle 0 - 1 - 2 - 3 - 4 - 0 with mixed edge directions.
+        let edges = vec![(0, 1), (2, 1), (2, 3), (4, 3), (0, 4)];
+        let mut my_check = BipartiteCheck::new(Graph::from_list_of_edges(edges.clone(), false));
+
+        my_check.run();
+
+        assert_eq!(my_check.runtime.odd_cycle.len(), 5);
+        let overlay = my_check.runtime.overlay();
+        for (u, v) in edges {
+            assert!(overlay.is_edge_highlighted(u, v, true));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,321 @@
+use crate::{
+    algorithms::algorithm::{Algorithm, SharedLog, Snapshot, record, record_last},
+    algorithms::inspect::{Inspect, Overlay, Variable, VariableKind},
+    graph::graph::Graph,
+    layouts::layout::undirected_neighbors,
+};
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Runtime {
+    pub active_node: Option<u32>,
+    pub timer: u32,
+    pub tin: HashMap<u32, u32>,
+    /// Smallest `tin` reachable from the subtree through one back edge.
+    pub low: HashMap<u32, u32>,
+    pub parent: HashMap<u32, u32>,
+    pub tree_edges: SharedLog<(u32, u32)>,
+    pub back_edges: SharedLog<(u32, u32)>,
+    pub bridges: Vec<(u32, u32)>,
+    pub articulation_points: Vec<u32>,
+    /// Edges not yet assigned to a biconnected component.
+    pub edge_stack: Vec<(u32, u32)>,
+    /// Biconnected components, as sorted node lists.
+    pub blocks: Vec<Vec<u32>>,
+    /// Set at the end: components left after removing every bridge.
+    pub two_edge_component: HashMap<u32, usize>,
+}
+/// Bridges and articulation points of the graph taken as undirected. A tree
+/// edge `p - u` is a bridge when `low[u] > tin[p]`; `p` is a cut vertex when
+/// `low[u] >= tin[p]`, unless it is a root with a single child.
+#[derive(Debug, PartialEq)]
+pub struct Bridges {
+    pub graph: Graph,
+    pub runtime: Runtime,
+    pub history: Vec<Runtime>,
+}
+
+fn format_edges(edges: &[(u32, u32)]) -> Vec<String> {
+    edges.iter().map(|(u, v)| format!("{}-{}", u, v)).collect()
+}
+
+impl Bridges {
+    pub fn new(graph: Graph) -> Self {
+        Bridges {
+            graph,
+            runtime: Runtime {
+                active_node: None,
+                timer: 0,
+                tin: HashMap::new(),
+                low: HashMap::new(),
+                parent: HashMap::new(),
+                tree_edges: SharedLog::new(),
+                back_edges: SharedLog::new(),
+                bridges: Vec::new(),
+                articulation_points: Vec::new(),
+                edge_stack: Vec::new(),
+                blocks: Vec::new(),
+                two_edge_component: HashMap::new(),
+            },
+            history: Vec::new(),
+        }
+    }
+    fn record_step(&mut self) {
+        record(&mut self.history, &self.runtime);
+    }
+    fn enter(&mut self, u: u32) {
+        self.runtime.active_node = Some(u);
+        self.runtime.tin.insert(u, self.runtime.timer);
+        self.runtime.low.insert(u, self.runtime.timer);
+        self.runtime.timer += 1;
+        self.record_step();
+    }
+    /// Called when the subtree of `u`, a child of `p`, is done.
+    fn close_child(&mut self, p: u32, u: u32, is_root: bool, children: usize) {
+        let (low_u, tin_p) = (self.runtime.low[&u], self.runtime.tin[&p]);
+        let low_p = self.runtime.low.get_mut(&p).expect("Visited");
+        *low_p = (*low_p).min(low_u);
+        if low_u > tin_p {
+            self.runtime.bridges.push((p, u));
+        }
+        if low_u >= tin_p {
+            if (!is_root || children > 1) && !self.runtime.articulation_points.contains(&p) {
+                self.runtime.articulation_points.push(p);
+                self.runtime.articulation_points.sort_unstable();
+            }
+            let mut block = HashSet::new();
+            while let Some((a, b)) = self.runtime.edge_stack.pop() {
+                block.extend([a, b]);
+                if (a, b) == (p, u) {
+                    break;
+                }
+            }
+            let mut block: Vec<u32> = block.into_iter().collect();
+            block.sort_unstable();
+            self.runtime.blocks.push(block);
+        }
+        self.runtime.active_node = Some(p);
+        self.record_step();
+    }
+    fn visit(&mut self, root: u32, neighbors: &HashMap<u32, Vec<u32>>) {
+        self.enter(root);
+        let mut calls = vec![root];
+        let mut pending = vec![neighbors[&root].iter()];
+        let mut children = 0;
+        while let Some(next) = pending.last_mut().map(|ends| ends.next()) {
+            let u = *calls.last().expect("Open call");
+            match next {
+                Some(v) if self.runtime.parent.get(&u) == Some(v) => {}
+                Some(v) if !self.runtime.tin.contains_key(v) => {
+                    if u == root {
+                        children += 1;
+                    }
+                    self.runtime.parent.insert(*v, u);
+                    self.runtime.tree_edges.push((u, *v));
+                    self.runtime.edge_stack.push((u, *v));
+                    self.enter(*v);
+                    calls.push(*v);
+                    pending.push(neighbors[v].iter());
+                }
+                Some(v) if self.runtime.tin[v] < self.runtime.tin[&u] => {
+                    self.runtime.back_edges.push((u, *v));
+                    self.runtime.edge_stack.push((u, *v));
+                    let low = self.runtime.low.get_mut(&u).expect("Visited");
+                    *low = (*low).min(self.runtime.tin[v]);
+                    self.record_step();
+                }
+                // A descendant whose back edge to `u` was already seen.
+                Some(_) => {}
+                None => {
+                    pending.pop();
+                    calls.pop();
+                    if let Some(p) = calls.last() {
+                        self.close_child(*p, u, *p == root, children);
+                    }
+                }
+            }
+        }
+    }
+    /// Labels the components that remain connected without the bridges.
+    fn label_two_edge_components(&mut self, neighbors: &HashMap<u32, Vec<u32>>) {
+        let bridges: HashSet<(u32, u32)> = self
+            .runtime
+            .bridges
+            .iter()
+            .flat_map(|(u, v)| [(*u, *v), (*v, *u)])
+            .collect();
+        let mut ids: Vec<u32> = neighbors.keys().copied().collect();
+        ids.sort_unstable();
+        let component = &mut self.runtime.two_edge_component;
+        let mut next_id = 0;
+        for start in ids {
+            if component.contains_key(&start) {
+                continue;
+            }
+            let id = next_id;
+            next_id += 1;
+            component.insert(start, id);
+            let mut stack = vec![start];
+            while let Some(u) = stack.pop() {
+                for v in neighbors[&u].iter() {
+                    if !bridges.contains(&(u, *v)) && !component.contains_key(v) {
+                        component.insert(*v, id);
+                        stack.push(*v);
+                    }
+                }
+            }
+        }
+    }
+}
+impl Algorithm<Runtime> for Bridges {
+    fn get_snapshot(&self) -> Snapshot<Runtime> {
+        Snapshot {
+            data: self.runtime.clone(),
+            graph: self.graph.to_snapshot().unwrap(),
+        }
+    }
+    fn get_history(&self) -> &[Runtime] {
+        &self.history
+    }
+    fn run(&mut self) {
+        let neighbors = undirected_neighbors(&self.graph);
+        let mut roots: Vec<u32> = neighbors.keys().copied().collect();
+        roots.sort_unstable();
+        for root in roots {
+            if !self.runtime.tin.contains_key(&root) {
+                self.visit(root, &neighbors);
+            }
+        }
+        self.label_two_edge_components(&neighbors);
+        self.runtime.active_node = None;
+        record_last(&mut self.history, &self.runtime);
+    }
+}
+impl Inspect for Runtime {
+    fn variables(&self) -> Vec<Variable> {
+        let active = self
+            .active_node
+            .map_or(String::from("-"), |id| id.to_string());
+        let blocks = self.blocks.iter().map(|block| {
+            let members: Vec<String> = block.iter().map(|id| id.to_string()).collect();
+            format!("{{{}}}", members.join(","))
+        });
+        vec![
+            Variable::scalar("active_node", active),
+            Variable::map("tin", &self.tin, |time| time.to_string()),
+            Variable::map("low", &self.low, |time| time.to_string()),
+            Variable::map("parent", &self.parent, |id| id.to_string()),
+            Variable::list(
+                "tree_edges",
+                VariableKind::Array,
+                format_edges(&self.tree_edges.items()),
+            ),
+            Variable::list(
+                "back_edges",
+                VariableKind::Array,
+                format_edges(&self.back_edges.items()),
+            ),
+            Variable::list(
+                "edge_stack",
+                VariableKind::Stack,
+                format_edges(&self.edge_stack),
+            ),
+            Variable::list("bridges", VariableKind::Array, format_edges(&self.bridges)),
+            Variable::list(
+                "articulation_points",
+                VariableKind::Array,
+                self.articulation_points.iter(),
+            ),
+            Variable::list("blocks", VariableKind::Array, blocks),
+        ]
+    }
+    fn overlay(&self) -> Overlay {
+        Overlay {
+            node_annotations: self
+                .tin
+                .iter()
+                .map(|(id, tin)| (*id, format!("{}/{}", tin, self.low[id])))
+                .collect(),
+            highlighted_nodes: self.articulation_points.iter().copied().collect(),
+            // Edges count as undirected, so match however they are stored.
+            highlighted_edges: self
+                .bridges
+                .iter()
+                .flat_map(|(u, v)| [(*u, *v), (*v, *u)])
+                .collect(),
+            node_colors: self.two_edge_component.clone(),
+            ..Overlay::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn setup_bowtie() -> Graph {
+        // Triangles {0, 1, 2} and {3, 4, 5} joined by the bridge 2 - 3, plus
+        // the pendant edge 5 - 6.
+        let edges = vec![
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (2, 3),
+            (3, 4),
+            (4, 5),
+            (5, 3),
+            (5, 6),
+        ];
+        Graph::from_list_of_edges(edges, true)
+    }
+
+    #[test]
+    fn test_bridges_and_articulation_points() -> Result<(), String> {
+        let mut my_bridges = Bridges::new(setup_bowtie());
+
+        my_bridges.run();
+
+        let mut bridges = my_bridges.runtime.bridges.clone();
+        bridges.sort_unstable();
+        assert_eq!(bridges, vec![(2, 3), (5, 6)]);
+        assert_eq!(my_bridges.runtime.articulation_points, vec![2, 3, 5]);
+        assert_eq!(my_bridges.runtime.tree_edges.len(), 6);
+        assert_eq!(my_bridges.runtime.back_edges.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_bridges_components() -> Result<(), String> {
+        let mut my_bridges = Bridges::new(setup_bowtie());
+
+        my_bridges.run();
+
+        let mut blocks = my_bridges.runtime.blocks.clone();
+        blocks.sort();
+        assert_eq!(
+            blocks,
+            vec![vec![0, 1, 2], vec![2, 3], vec![3, 4, 5], vec![5, 6]]
+        );
+        let component = &my_bridges.runtime.two_edge_component;
+        assert_eq!(component[&0], component[&2]);
+        assert_eq!(component[&3], component[&5]);
+        assert_ne!(component[&2], component[&3]);
+        assert_ne!(component[&5], component[&6]);
+        let overlay = my_bridges.runtime.overlay();
+        assert!(overlay.is_edge_highlighted(3, 2, false));
+        Ok(())
+    }
+
+    #[test]
+    fn test_bridges_highlighted_on_directed_graph() -> Result<(), String> {
+        // The bridge is stored as 3 -> 2 but found from 2.
+        let edges = vec![(0, 1), (1, 2), (2, 0), (3, 2)];
+        let mut my_bridges = Bridges::new(Graph::from_list_of_edges(edges, false));
+
+        my_bridges.run();
+
+        assert_eq!(my_bridges.runtime.bridges, vec![(2, 3)]);
+        let overlay = my_bridges.runtime.overlay();
+        assert!(overlay.is_edge_highlighted(3, 2, true));
+        Ok(())
+    }
+}
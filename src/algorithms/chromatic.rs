@@ -0,0 +1,167 @@
+use crate::{
+    algorithms::algorithm::{Algorithm, Snapshot, record, record_last},
+    algorithms::coloring::{coloring_overlay, first_free, forbidden_colors},
+    algorithms::inspect::{Inspect, Overlay, Variable, VariableKind},
+    graph::graph::Graph,
+    layouts::layout::{sorted_ids, undirected_neighbors},
+};
+use std::collections::HashMap;
+
+/// Larger graphs are refused: the search is exponential.
+pub const MAX_EXACT_NODES: usize = 12;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Runtime {
+    /// Number of colors the current search may use.
+    pub colors: usize,
+    pub active_node: Option<u32>,
+    pub forbidden: Vec<usize>,
+    pub color: HashMap<u32, usize>,
+    pub backtracks: u64,
+    pub chromatic_number: Option<usize>,
+}
+/// Exact chromatic number by backtracking: tries 1, 2, ... colors, nodes by
+/// decreasing degree, until a proper coloring exists. A node never opens more
+/// than one new color, which skips colorings that only rename colors.
+#[derive(Debug, PartialEq)]
+pub struct ExactColoring {
+    pub graph: Graph,
+    pub runtime: Runtime,
+    pub history: Vec<Runtime>,
+}
+
+impl ExactColoring {
+    /// `None` for graphs with more than `MAX_EXACT_NODES` nodes.
+    pub fn new(graph: Graph) -> Option<Self> {
+        if graph.nodes.len() > MAX_EXACT_NODES {
+            return None;
+        }
+        Some(ExactColoring {
+            graph,
+            runtime: Runtime {
+                colors: 0,
+                active_node: None,
+                forbidden: Vec::new(),
+                color: HashMap::new(),
+                backtracks: 0,
+                chromatic_number: None,
+            },
+            history: Vec::new(),
+        })
+    }
+    fn record_step(&mut self) {
+        record(&mut self.history, &self.runtime);
+    }
+    fn solve(&mut self, neighbors: &HashMap<u32, Vec<u32>>, order: &[u32]) -> bool {
+        let Some((u, rest)) = order.split_first() else {
+            return true;
+        };
+        let forbidden = forbidden_colors(neighbors, &self.runtime.color, *u);
+        let opened = self.runtime.color.values().max().map_or(0, |max| max + 1);
+        self.runtime.active_node = Some(*u);
+        self.runtime.forbidden = forbidden.clone();
+        self.record_step();
+        for color in 0..=opened.min(self.runtime.colors - 1) {
+            if forbidden.contains(&color) {
+                continue;
+            }
+            self.runtime.color.insert(*u, color);
+            self.record_step();
+            if self.solve(neighbors, rest) {
+                return true;
+            }
+            self.runtime.color.remove(u);
+            self.runtime.backtracks += 1;
+        }
+        false
+    }
+}
+impl Algorithm<Runtime> for ExactColoring {
+    fn get_snapshot(&self) -> Snapshot<Runtime> {
+        Snapshot {
+            data: self.runtime.clone(),
+            graph: self.graph.to_snapshot().unwrap(),
+        }
+    }
+    fn get_history(&self) -> &[Runtime] {
+        &self.history
+    }
+    fn run(&mut self) {
+        let neighbors = undirected_neighbors(&self.graph);
+        let mut order = sorted_ids(&self.graph);
+        order.sort_by_key(|id| std::cmp::Reverse(neighbors[id].len()));
+        // First-fit in this order bounds the answer from above.
+        let mut greedy = HashMap::new();
+        for u in order.iter() {
+            greedy.insert(*u, first_free(&forbidden_colors(&neighbors, &greedy, *u)));
+        }
+        let upper = greedy.values().max().map_or(0, |max| max + 1);
+        for colors in 1..=upper {
+            self.runtime.colors = colors;
+            self.runtime.color.clear();
+            if self.solve(&neighbors, &order) {
+                break;
+            }
+        }
+        self.runtime.chromatic_number = Some(self.runtime.colors);
+        self.runtime.active_node = None;
+        self.runtime.forbidden.clear();
+        record_last(&mut self.history, &self.runtime);
+    }
+}
+impl Inspect for Runtime {
+    fn variables(&self) -> Vec<Variable> {
+        let active = self
+            .active_node
+            .map_or(String::from("-"), |id| id.to_string());
+        let chromatic = self
+            .chromatic_number
+            .map_or(String::from("-"), |count| count.to_string());
+        vec![
+            Variable::scalar("colors", self.colors),
+            Variable::scalar("active_node", active),
+            Variable::list("forbidden", VariableKind::Array, self.forbidden.iter()),
+            Variable::map("color", &self.color, |color| color.to_string()),
+            Variable::scalar("backtracks", self.backtracks),
+            Variable::scalar("chromatic_number", chromatic),
+        ]
+    }
+    fn overlay(&self) -> Overlay {
+        coloring_overlay(&self.color, self.active_node, &self.forbidden)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact_coloring_finds_chromatic_number() -> Result<(), String> {
+        // The 5-cycle needs 3 colors, the Petersen graph too.
+        let cycle = vec![(0, 1), (1, 2), (2, 3), (3, 4), (4, 0)];
+        let mut my_coloring =
+            ExactColoring::new(Graph::from_list_of_edges(cycle, true)).ok_or("Small graph")?;
+        my_coloring.run();
+        assert_eq!(my_coloring.runtime.chromatic_number, Some(3));
+        assert!(my_coloring.runtime.backtracks > 0);
+
+        let mut petersen = Vec::new();
+        for i in 0..5 {
+            petersen.extend([(i, (i + 1) % 5), (i, i + 5), (i + 5, (i + 2) % 5 + 5)]);
+        }
+        let graph = Graph::from_list_of_edges(petersen, true);
+        let mut my_coloring = ExactColoring::new(graph.clone()).ok_or("Small graph")?;
+        my_coloring.run();
+        let color = &my_coloring.runtime.color;
+        assert_eq!(my_coloring.runtime.chromatic_number, Some(3));
+        for node in graph.nodes.values() {
+            assert!(
+                node.runtime
+                    .edges
+                    .keys()
+                    .all(|v| color[v] != color[&node.id])
+            );
+        }
+        Ok(())
+    }
+}
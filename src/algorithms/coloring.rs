@@ -0,0 +1,258 @@
+use crate::{
+    algorithms::algorithm::{Algorithm, SharedLog, Snapshot, record, record_last},
+    algorithms::inspect::{Inspect, Overlay, Variable, VariableKind},
+    graph::graph::Graph,
+    layouts::layout::{sorted_ids, undirected_neighbors},
+};
+use std::collections::HashMap;
+
+/// Which uncolored node gets the next color. Every order then takes the
+/// smallest color its neighbors allow.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ColoringOrder {
+    /// By node id.
+    Greedy,
+    /// By decreasing degree.
+    WelshPowell,
+    /// Most distinct neighbor colors first, ties by degree.
+    DSatur,
+}
+
+/// Colors already taken by the neighbors of `node`, sorted.
+pub fn forbidden_colors(
+    neighbors: &HashMap<u32, Vec<u32>>,
+    color: &HashMap<u32, usize>,
+    node: u32,
+) -> Vec<usize> {
+    let mut forbidden: Vec<usize> = neighbors[&node]
+        .iter()
+        .filter_map(|v| color.get(v).copied())
+        .collect();
+    forbidden.sort_unstable();
+    forbidden.dedup();
+    forbidden
+}
+
+/// Smallest color missing from the sorted `forbidden`.
+pub fn first_free(forbidden: &[usize]) -> usize {
+    forbidden
+        .iter()
+        .enumerate()
+        .find(|(index, color)| index != *color)
+        .map_or(forbidden.len(), |(index, _)| index)
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Runtime {
+    pub active_node: Option<u32>,
+    /// Colors the neighbors of the active node already use.
+    pub forbidden: Vec<usize>,
+    /// Palette index of every colored node.
+    pub color: HashMap<u32, usize>,
+    /// Distinct neighbor colors per uncolored node, for DSatur; nodes left
+    /// out have none.
+    pub saturation: HashMap<u32, usize>,
+    /// Nodes in the order they were colored.
+    pub order: SharedLog<u32>,
+    pub colors_used: usize,
+}
+/// Sequential vertex coloring, edge direction ignored. Self-loops are
+/// skipped, since no coloring could satisfy them.
+#[derive(Debug, PartialEq)]
+pub struct Coloring {
+    pub graph: Graph,
+    pub strategy: ColoringOrder,
+    pub runtime: Runtime,
+    pub history: Vec<Runtime>,
+}
+
+impl Coloring {
+    pub fn new(graph: Graph, strategy: ColoringOrder) -> Self {
+        Coloring {
+            graph,
+            strategy,
+            runtime: Runtime {
+                active_node: None,
+                forbidden: Vec::new(),
+                color: HashMap::new(),
+                saturation: HashMap::new(),
+                order: SharedLog::new(),
+                colors_used: 0,
+            },
+            history: Vec::new(),
+        }
+    }
+    fn record_step(&mut self) {
+        record(&mut self.history, &self.runtime);
+    }
+    /// Fixed orders walk `ids` once from `cursor`; only DSatur rescans, since
+    /// saturation changes after every coloring.
+    fn next_node(
+        &self,
+        neighbors: &HashMap<u32, Vec<u32>>,
+        ids: &[u32],
+        cursor: &mut usize,
+    ) -> Option<u32> {
+        let colored = |id: &u32| self.runtime.color.contains_key(id);
+        match self.strategy {
+            ColoringOrder::Greedy | ColoringOrder::WelshPowell => {
+                while ids.get(*cursor).is_some_and(colored) {
+                    *cursor += 1;
+                }
+                ids.get(*cursor).copied()
+            }
+            ColoringOrder::DSatur => {
+                ids.iter()
+                    .copied()
+                    .filter(|id| !colored(id))
+                    .max_by_key(|id| {
+                        let saturation = self.runtime.saturation.get(id).copied().unwrap_or(0);
+                        (saturation, neighbors[id].len(), std::cmp::Reverse(*id))
+                    })
+            }
+        }
+    }
+}
+impl Algorithm<Runtime> for Coloring {
+    fn get_snapshot(&self) -> Snapshot<Runtime> {
+        Snapshot {
+            data: self.runtime.clone(),
+            graph: self.graph.to_snapshot().unwrap(),
+        }
+    }
+    fn get_history(&self) -> &[Runtime] {
+        &self.history
+    }
+    fn run(&mut self) {
+        let neighbors = undirected_neighbors(&self.graph);
+        let mut ids = sorted_ids(&self.graph);
+        if self.strategy == ColoringOrder::WelshPowell {
+            ids.sort_by_key(|id| std::cmp::Reverse(neighbors[id].len()));
+        }
+        let mut cursor = 0;
+        while let Some(u) = self.next_node(&neighbors, &ids, &mut cursor) {
+            self.runtime.active_node = Some(u);
+            self.runtime.forbidden = forbidden_colors(&neighbors, &self.runtime.color, u);
+            self.record_step();
+            let color = first_free(&self.runtime.forbidden);
+            self.runtime.color.insert(u, color);
+            self.runtime.order.push(u);
+            self.runtime.colors_used = self.runtime.colors_used.max(color + 1);
+            if self.strategy == ColoringOrder::DSatur {
+                self.runtime.saturation.remove(&u);
+                for v in neighbors[&u].iter() {
+                    if !self.runtime.color.contains_key(v) {
+                        let count = forbidden_colors(&neighbors, &self.runtime.color, *v).len();
+                        self.runtime.saturation.insert(*v, count);
+                    }
+                }
+            }
+            self.record_step();
+        }
+        self.runtime.active_node = None;
+        self.runtime.forbidden.clear();
+        record_last(&mut self.history, &self.runtime);
+    }
+}
+impl Inspect for Runtime {
+    fn variables(&self) -> Vec<Variable> {
+        let active = self
+            .active_node
+            .map_or(String::from("-"), |id| id.to_string());
+        vec![
+            Variable::scalar("active_node", active),
+            Variable::list("forbidden", VariableKind::Array, self.forbidden.iter()),
+            Variable::map("color", &self.color, |color| color.to_string()),
+            Variable::map("saturation", &self.saturation, |count| count.to_string()),
+            Variable::list("order", VariableKind::Array, self.order.items().iter()),
+            Variable::scalar("colors_used", self.colors_used),
+        ]
+    }
+    fn overlay(&self) -> Overlay {
+        coloring_overlay(&self.color, self.active_node, &self.forbidden)
+    }
+}
+
+/// Nodes filled with their palette color, the active one highlighted and
+/// annotated with the colors it may not take.
+pub fn coloring_overlay(
+    color: &HashMap<u32, usize>,
+    active_node: Option<u32>,
+    forbidden: &[usize],
+) -> Overlay {
+    let mut overlay = Overlay {
+        node_annotations: color
+            .iter()
+            .map(|(id, color)| (*id, format!("c={}", color)))
+            .collect(),
+        highlighted_nodes: active_node.into_iter().collect(),
+        node_colors: color.clone(),
+        ..Overlay::default()
+    };
+    if let Some(id) = active_node
+        && !color.contains_key(&id)
+    {
+        overlay
+            .node_annotations
+            .insert(id, format!("not {:?}", forbidden));
+    }
+    overlay
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn setup_crown() -> Graph {
+        // K(4,4) minus a perfect matching: greedy by id needs 4 colors,
+        // though the graph is bipartite.
+        let mut edges = Vec::new();
+        for i in 0..4 {
+            for j in 0..4 {
+                if i != j {
+                    edges.push((2 * i, 2 * j + 1));
+                }
+            }
+        }
+        Graph::from_list_of_edges(edges, true)
+    }
+    fn color(strategy: ColoringOrder) -> Coloring {
+        let mut my_coloring = Coloring::new(setup_crown(), strategy);
+        my_coloring.run();
+        my_coloring
+    }
+
+    #[test]
+    fn test_colorings_are_proper() -> Result<(), String> {
+        let neighbors = undirected_neighbors(&setup_crown());
+        for strategy in [
+            ColoringOrder::Greedy,
+            ColoringOrder::WelshPowell,
+            ColoringOrder::DSatur,
+        ] {
+            let my_coloring = color(strategy);
+            let color = &my_coloring.runtime.color;
+            assert_eq!(color.len(), 8);
+            for (u, list) in neighbors.iter() {
+                assert!(list.iter().all(|v| color[u] != color[v]));
+            }
+        }
+        assert_eq!(color(ColoringOrder::Greedy).runtime.colors_used, 4);
+        assert_eq!(color(ColoringOrder::DSatur).runtime.colors_used, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_coloring_snapshots_show_forbidden() -> Result<(), String> {
+        let my_coloring = color(ColoringOrder::Greedy);
+
+        // Node 5 sees 0 (color 0) and 2 (color 1) already colored.
+        let step = my_coloring
+            .get_history()
+            .iter()
+            .find(|step| step.active_node == Some(5) && !step.color.contains_key(&5))
+            .ok_or("Node 5 is colored")?;
+        assert_eq!(step.forbidden, vec![0, 1]);
+        assert_eq!(first_free(&[0, 2]), 1);
+        Ok(())
+    }
+}
@@ -0,0 +1,78 @@
+use crate::{
+    algorithms::inspect::{Overlay, SecondaryView},
+    graph::graph::Graph,
+};
+use std::collections::HashMap;
+
+const MAX_LISTED_MEMBERS: usize = 6;
+
+/// Condensation DAG of `graph`: one node per component, with the component id
+/// as node id, placed at the centroid of its members and annotated with them.
+/// Components are linked when any edge crosses between them.
+pub fn condensation(graph: &Graph, component: &HashMap<u32, usize>) -> SecondaryView {
+    let mut members: HashMap<usize, Vec<u32>> = HashMap::new();
+    for (node, id) in component.iter() {
+        members.entry(*id).or_default().push(*node);
+    }
+    let mut condensed = Graph::new();
+    let mut overlay = Overlay::default();
+    for (id, nodes) in members.iter_mut() {
+        nodes.sort_unstable();
+        let count = nodes.len() as f64;
+        let (x, y) = nodes.iter().fold((0.0, 0.0), |(x, y), node| {
+            let model = &graph.nodes[node].gui_model;
+            (
+                x + model.position_x.into_inner() / count,
+                y + model.position_y.into_inner() / count,
+            )
+        });
+        condensed.add_new_node_to_graph(*id as u32, x, y);
+        overlay.node_colors.insert(*id as u32, *id);
+
+        let mut listed: Vec<String> = nodes
+            .iter()
+            .take(MAX_LISTED_MEMBERS)
+            .map(|node| node.to_string())
+            .collect();
+        if nodes.len() > MAX_LISTED_MEMBERS {
+            listed.push(String::from("..."));
+        }
+        overlay
+            .node_annotations
+            .insert(*id as u32, format!("{{{}}}", listed.join(",")));
+    }
+    for node in graph.nodes.values() {
+        for end in node.runtime.edges.keys() {
+            if let (Some(from), Some(to)) = (component.get(&node.id), component.get(end))
+                && from != to
+            {
+                condensed.add_directed_edge(*from as u32, *to as u32);
+            }
+        }
+    }
+    SecondaryView {
+        title: String::from("condensation"),
+        graph: condensed,
+        overlay,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_condensation_merges_components() -> Result<(), String> {
+        let edges = vec![(0, 1), (1, 0), (1, 2), (2, 3), (3, 2)];
+        let my_graph = Graph::from_list_of_edges(edges, false);
+        let component = HashMap::from([(0, 0), (1, 0), (2, 1), (3, 1)]);
+
+        let view = condensation(&my_graph, &component);
+
+        assert_eq!(view.graph.nodes.len(), 2);
+        assert!(view.graph.get_edge(0, 1).is_some());
+        assert!(view.graph.get_edge(1, 0).is_none());
+        assert_eq!(view.overlay.node_annotations[&1], "{2,3}");
+        Ok(())
+    }
+}
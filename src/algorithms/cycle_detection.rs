@@ -0,0 +1,246 @@
+use crate::{
+    algorithms::algorithm::{Algorithm, Snapshot, record, record_last},
+    algorithms::inspect::{Inspect, Overlay, Variable, VariableKind},
+    graph::graph::Graph,
+    layouts::layout::sorted_ids,
+};
+use std::collections::HashMap;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Color {
+    /// Not reached yet.
+    White,
+    /// On the DFS stack.
+    Gray,
+    /// Finished, with every edge out of it explored.
+    Black,
+}
+
+impl std::fmt::Display for Color {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Color::White => "white",
+            Color::Gray => "gray",
+            Color::Black => "black",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Explicit DFS stack entry: a node, its edges from `edges`, the next index.
+type Frame = (u32, Vec<(u32, bool)>, usize);
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Runtime {
+    pub active_node: Option<u32>,
+    /// Nodes left out are white.
+    pub color: HashMap<u32, Color>,
+    pub parent: HashMap<u32, u32>,
+    /// Gray nodes, from the DFS root to the active node.
+    pub stack: Vec<u32>,
+    /// Edge being explored.
+    pub edge: Option<(u32, u32)>,
+    /// The cycle found, in edge order; empty while none is known.
+    pub cycle: Vec<u32>,
+}
+/// DFS cycle detection with white/gray/black coloring: an edge reaching a
+/// gray node closes a cycle along the stack. Undirected edges are tracked
+/// through the parent, so going straight back over the edge just used is not
+/// a cycle. Stops at the first cycle.
+#[derive(Debug, PartialEq)]
+pub struct CycleDetection {
+    pub graph: Graph,
+    pub runtime: Runtime,
+    pub history: Vec<Runtime>,
+}
+
+impl Runtime {
+    pub fn color(&self, node: u32) -> Color {
+        self.color.get(&node).copied().unwrap_or(Color::White)
+    }
+}
+
+impl CycleDetection {
+    pub fn new(graph: Graph) -> Self {
+        CycleDetection {
+            runtime: Runtime {
+                active_node: None,
+                color: HashMap::new(),
+                parent: HashMap::new(),
+                stack: Vec::new(),
+                edge: None,
+                cycle: Vec::new(),
+            },
+            graph,
+            history: Vec::new(),
+        }
+    }
+    fn record_step(&mut self) {
+        record(&mut self.history, &self.runtime);
+    }
+    /// Edges out of `u` as `(end, directed)`, sorted by end.
+    fn edges(&self, u: u32) -> Vec<(u32, bool)> {
+        let mut edges: Vec<(u32, bool)> = self.graph.nodes[&u]
+            .runtime
+            .edges
+            .values()
+            .map(|edge| (edge.runtime.node_end, edge.runtime.directed))
+            .filter(|(v, _)| self.graph.nodes.contains_key(v))
+            .collect();
+        edges.sort_unstable();
+        edges
+    }
+    /// Stack nodes from `v` up to the active node, which has an edge back to `v`.
+    fn extract(&self, v: u32) -> Vec<u32> {
+        let start = self
+            .runtime
+            .stack
+            .iter()
+            .position(|id| *id == v)
+            .expect("Gray nodes are on the stack");
+        self.runtime.stack[start..].to_vec()
+    }
+    /// DFS from `root`; returns whether a cycle was found.
+    fn visit(&mut self, root: u32) -> bool {
+        let mut next: Vec<Frame> = vec![(root, self.edges(root), 0)];
+        self.runtime.color.insert(root, Color::Gray);
+        self.runtime.stack.push(root);
+        self.runtime.active_node = Some(root);
+        self.record_step();
+        while let Some((u, edges, index)) = next.last_mut() {
+            let u = *u;
+            let Some((v, directed)) = edges.get(*index).copied() else {
+                next.pop();
+                self.runtime.stack.pop();
+                self.runtime.color.insert(u, Color::Black);
+                self.runtime.active_node = self.runtime.stack.last().copied();
+                self.runtime.edge = None;
+                self.record_step();
+                continue;
+            };
+            *index += 1;
+            self.runtime.edge = Some((u, v));
+            match self.runtime.color(v) {
+                Color::White => {
+                    self.runtime.color.insert(v, Color::Gray);
+                    self.runtime.parent.insert(v, u);
+                    self.runtime.stack.push(v);
+                    self.runtime.active_node = Some(v);
+                    next.push((v, self.edges(v), 0));
+                    self.record_step();
+                }
+                Color::Gray if !directed && self.runtime.parent.get(&u) == Some(&v) => {}
+                Color::Gray => {
+                    self.runtime.cycle = self.extract(v);
+                    self.record_step();
+                    return true;
+                }
+                Color::Black => {}
+            }
+        }
+        false
+    }
+}
+impl Algorithm<Runtime> for CycleDetection {
+    fn get_snapshot(&self) -> Snapshot<Runtime> {
+        Snapshot {
+            data: self.runtime.clone(),
+            graph: self.graph.to_snapshot().unwrap(),
+        }
+    }
+    fn get_history(&self) -> &[Runtime] {
+        &self.history
+    }
+    fn run(&mut self) {
+        for root in sorted_ids(&self.graph) {
+            if self.runtime.color(root) == Color::White && self.visit(root) {
+                record_last(&mut self.history, &self.runtime);
+                return;
+            }
+        }
+        self.runtime.active_node = None;
+        record_last(&mut self.history, &self.runtime);
+    }
+}
+impl Inspect for Runtime {
+    fn variables(&self) -> Vec<Variable> {
+        let active = self
+            .active_node
+            .map_or(String::from("-"), |id| id.to_string());
+        let edge = self
+            .edge
+            .map_or(String::from("-"), |(u, v)| format!("{}->{}", u, v));
+        vec![
+            Variable::scalar("active_node", active),
+            Variable::scalar("edge", edge),
+            Variable::map("color", &self.color, |color| color.to_string()),
+            Variable::map("parent", &self.parent, |id| id.to_string()),
+            Variable::list("stack", VariableKind::Stack, self.stack.iter()),
+            Variable::list("cycle", VariableKind::Array, self.cycle.iter()),
+        ]
+    }
+    fn overlay(&self) -> Overlay {
+        let mut overlay = Overlay {
+            highlighted_nodes: self.active_node.into_iter().collect(),
+            marked_edges: self.edge.into_iter().collect(),
+            node_colors: self
+                .color
+                .iter()
+                .filter_map(|(id, color)| match color {
+                    Color::White => None,
+                    Color::Gray => Some((*id, 0)),
+                    Color::Black => Some((*id, 1)),
+                })
+                .collect(),
+            ..Overlay::default()
+        };
+        if !self.cycle.is_empty() {
+            overlay.highlighted_nodes.clear();
+            overlay.marked_edges.clear();
+            overlay.highlight_cycle(&self.cycle);
+        }
+        overlay
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cycle_detection_directed() -> Result<(), String> {
+        // 0 -> 2 is a forward edge into a black node, not a cycle.
+        let dag = vec![(0, 1), (1, 2), (0, 2), (2, 3)];
+        let mut my_search = CycleDetection::new(Graph::from_list_of_edges(dag, false));
+        my_search.run();
+        assert!(my_search.runtime.cycle.is_empty());
+        assert!(
+            my_search
+                .runtime
+                .color
+                .values()
+                .all(|color| *color == Color::Black)
+        );
+
+        let edges = vec![(0, 1), (1, 2), (2, 3), (3, 1), (3, 4)];
+        let mut my_search = CycleDetection::new(Graph::from_list_of_edges(edges, false));
+        my_search.run();
+        assert_eq!(my_search.runtime.cycle, vec![1, 2, 3]);
+        assert!(my_search.runtime.overlay().is_edge_highlighted(3, 1, true));
+        Ok(())
+    }
+
+    #[test]
+    fn test_cycle_detection_undirected() -> Result<(), String> {
+        let tree = vec![(0, 1), (1, 2), (1, 3)];
+        let mut my_search = CycleDetection::new(Graph::from_list_of_edges(tree, true));
+        my_search.run();
+        assert!(my_search.runtime.cycle.is_empty());
+
+        let edges = vec![(0, 1), (1, 2), (2, 3), (3, 1)];
+        let mut my_search = CycleDetection::new(Graph::from_list_of_edges(edges, true));
+        my_search.run();
+        assert_eq!(my_search.runtime.cycle, vec![1, 2, 3]);
+        Ok(())
+    }
+}
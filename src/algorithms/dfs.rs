@@ -1,17 +1,22 @@
 use crate::algorithms::algorithm::Snapshot;
-use crate::{algorithms::algorithm::Algorithm, graph::graph::Graph};
+use crate::algorithms::inspect::{Inspect, Variable, VariableKind};
+use crate::{algorithms::algorithm::{Algorithm, SharedLog, record, record_last}, graph::graph::Graph};
 use std::collections::HashMap;
 use std::{thread, time};
 #[derive(Debug, PartialEq, Clone)]
 pub struct Runtime {
     pub active_node: u32,
     pub distances: HashMap<u32, i32>,
-    pub log: Vec<String>,
+    pub stack: Vec<u32>,
+    pub parents: HashMap<u32, u32>,
+    pub log: SharedLog<String>,
 }
 #[derive(Debug, PartialEq)]
 pub struct DFS {
     pub graph: Graph,
+    pub source: u32,
     pub runtime: Runtime,
+    pub history: Vec<Runtime>,
 }
 
 impl DFS {
@@ -22,11 +27,15 @@ impl DFS {
         let distances = graph.nodes.keys().map(|&key| (key, -1)).collect();
         return Some(DFS {
             graph,
+            source,
             runtime: Runtime {
-                log: Vec::new(),
+                log: SharedLog::new(),
                 active_node: 9999,
                 distances,
+                stack: Vec::new(),
+                parents: HashMap::new(),
             },
+            history: Vec::new(),
         });
     }
 }
@@ -34,6 +43,11 @@ impl DFS {
     fn dfs_recursion(&mut self, u: u32, parent: u32) -> u32 {
         self.runtime.active_node = u;
         self.runtime.distances.insert(u, 0);
+        self.runtime.stack.push(u);
+        if u != parent {
+            self.runtime.parents.insert(u, parent);
+        }
+        self.record_step();
         let neighbors: Vec<u32> = self
             .graph
             .nodes
@@ -57,7 +71,10 @@ impl DFS {
             }
             println!("Current neighbor: {}", id);
             count += self.dfs_recursion(id, u);
+            self.runtime.active_node = u;
         }
+        self.runtime.stack.pop();
+        self.record_step();
         count
     }
     fn dfs_loop(&mut self, u: u32) -> u32 {
@@ -67,6 +84,9 @@ impl DFS {
     fn add_log_line(&mut self, data: String) {
         self.runtime.log.push(data);
     }
+    fn record_step(&mut self) {
+        record(&mut self.history, &self.runtime);
+    }
 }
 impl Algorithm<Runtime> for DFS {
     fn get_snapshot(&self) -> Snapshot<Runtime> {
@@ -75,6 +95,9 @@ impl Algorithm<Runtime> for DFS {
             graph: self.graph.to_snapshot().unwrap(),
         }
     }
+    fn get_history(&self) -> &[Runtime] {
+        &self.history
+    }
     fn run(&mut self) {
         // Graph description for logs
         let node_indexes: Vec<u32> = self.graph.nodes.keys().cloned().collect();
@@ -95,7 +118,20 @@ impl Algorithm<Runtime> for DFS {
         }
 
         // Start with the DFS
-        self.dfs_loop(0);
+        self.dfs_loop(self.source);
+        record_last(&mut self.history, &self.runtime);
+    }
+}
+impl Inspect for Runtime {
+    fn variables(&self) -> Vec<Variable> {
+        vec![
+            Variable::scalar("active_node", self.active_node),
+            Variable::list("stack", VariableKind::Stack, self.stack.iter()),
+            Variable::map("visited", &self.distances, |distance| {
+                (*distance != -1).to_string()
+            }),
+            Variable::map("parents", &self.parents, |parent| parent.to_string()),
+        ]
     }
 }
 #[cfg(test)]
@@ -127,4 +163,23 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_dfs_stack_follows_recursion() -> Result<(), String> {
+        let my_graph = setup_left_chain();
+        let mut my_dfs = DFS::new(my_graph, 0).unwrap();
+
+        my_dfs.run();
+
+        let deepest = my_dfs
+            .get_history()
+            .iter()
+            .map(|runtime| runtime.stack.len())
+            .max()
+            .unwrap();
+        assert_eq!(deepest, 7, "The whole chain should be on the stack at once");
+        assert!(my_dfs.runtime.stack.is_empty());
+        assert_eq!(my_dfs.runtime.parents.get(&3), Some(&2));
+        Ok(())
+    }
 }
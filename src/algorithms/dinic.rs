@@ -0,0 +1,226 @@
+use crate::{
+    algorithms::algorithm::{Algorithm, Snapshot, record, record_last},
+    algorithms::flow::FlowNetwork,
+    algorithms::inspect::{Inspect, Overlay, Variable, VariableKind},
+    graph::graph::Graph,
+};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::rc::Rc;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Runtime {
+    pub network: FlowNetwork,
+    pub phase: u32,
+    /// BFS distance from the source in the residual graph of this phase.
+    pub level: HashMap<u32, u32>,
+    /// Path of the blocking flow search that just reached the sink.
+    pub path: Vec<u32>,
+    /// Flow sent in the current phase so far.
+    pub blocking_flow: i64,
+    pub max_flow: i64,
+    /// Set at the end: nodes the source still reaches in the residual graph.
+    pub source_side: HashSet<u32>,
+    pub min_cut: Vec<(u32, u32)>,
+}
+/// Dinic's maximum flow: every phase builds the BFS level graph and saturates
+/// it with a blocking flow of shortest paths.
+#[derive(Debug, PartialEq)]
+pub struct Dinic {
+    pub graph: Graph,
+    pub source: u32,
+    pub sink: u32,
+    pub runtime: Runtime,
+    pub history: Vec<Runtime>,
+}
+
+impl Runtime {
+    fn is_level_arc(&self, u: u32, v: u32) -> bool {
+        match (self.level.get(&u), self.level.get(&v)) {
+            (Some(lu), Some(lv)) => lu + 1 == *lv && self.network.residual(u, v) > 0,
+            _ => false,
+        }
+    }
+}
+
+impl Dinic {
+    pub fn new(graph: Graph, source: u32, sink: u32) -> Option<Self> {
+        if source == sink || !graph.nodes.contains_key(&source) || !graph.nodes.contains_key(&sink)
+        {
+            return None;
+        }
+        Some(Dinic {
+            runtime: Runtime {
+                network: FlowNetwork::new(&graph),
+                phase: 0,
+                level: HashMap::new(),
+                path: Vec::new(),
+                blocking_flow: 0,
+                max_flow: 0,
+                source_side: HashSet::new(),
+                min_cut: Vec::new(),
+            },
+            graph,
+            source,
+            sink,
+            history: Vec::new(),
+        })
+    }
+    fn record_step(&mut self) {
+        record(&mut self.history, &self.runtime);
+    }
+    /// Levels from the source. Returns whether the sink got one.
+    fn build_levels(&mut self) -> bool {
+        let network = &self.runtime.network;
+        let mut level = HashMap::from([(self.source, 0)]);
+        let mut queue = VecDeque::from([self.source]);
+        while let Some(u) = queue.pop_front() {
+            for v in network.neighbors(u) {
+                if network.residual(u, *v) > 0 && !level.contains_key(v) {
+                    level.insert(*v, level[&u] + 1);
+                    queue.push_back(*v);
+                }
+            }
+        }
+        self.runtime.level = level;
+        self.runtime.level.contains_key(&self.sink)
+    }
+    /// Repeated DFS over level arcs. `next` remembers, per node, the first
+    /// neighbor not yet known to be a dead end.
+    fn blocking_flow(&mut self) {
+        let mut next: HashMap<u32, usize> = HashMap::new();
+        loop {
+            let mut path = vec![self.source];
+            while let Some(u) = path.last().copied().filter(|u| *u != self.sink) {
+                let index = next.entry(u).or_insert(0);
+                match self.runtime.network.neighbors(u).get(*index).copied() {
+                    Some(v) if self.runtime.is_level_arc(u, v) => path.push(v),
+                    Some(_) => *index += 1,
+                    None => {
+                        path.pop();
+                        if let Some(parent) = path.last() {
+                            *next.get_mut(parent).expect("Visited") += 1;
+                        }
+                    }
+                }
+            }
+            if path.is_empty() {
+                break;
+            }
+            let bottleneck = self.runtime.network.bottleneck(&path);
+            self.runtime.network.augment(&path, bottleneck);
+            self.runtime.path = path;
+            self.runtime.blocking_flow += bottleneck as i64;
+            self.runtime.max_flow = self.runtime.network.value(self.source);
+            self.record_step();
+        }
+    }
+}
+impl Algorithm<Runtime> for Dinic {
+    fn get_snapshot(&self) -> Snapshot<Runtime> {
+        Snapshot {
+            data: self.runtime.clone(),
+            graph: self.graph.to_snapshot().unwrap(),
+        }
+    }
+    fn get_history(&self) -> &[Runtime] {
+        &self.history
+    }
+    fn run(&mut self) {
+        while self.build_levels() {
+            self.runtime.phase += 1;
+            self.runtime.path.clear();
+            self.runtime.blocking_flow = 0;
+            self.record_step();
+            self.blocking_flow();
+        }
+        self.runtime.path.clear();
+        self.runtime.source_side = self.runtime.network.reachable(self.source);
+        self.runtime.min_cut = self.runtime.network.cut(&self.runtime.source_side);
+        record_last(&mut self.history, &self.runtime);
+    }
+}
+impl Inspect for Runtime {
+    fn variables(&self) -> Vec<Variable> {
+        let mut flow: Vec<String> = self
+            .network
+            .flow
+            .iter()
+            .map(|((u, v), flow)| format!("{}->{}: {}", u, v, flow))
+            .collect();
+        flow.sort();
+        let cut = self.min_cut.iter().map(|(u, v)| format!("{}->{}", u, v));
+        vec![
+            Variable::scalar("phase", self.phase),
+            Variable::map("level", &self.level, |level| level.to_string()),
+            Variable::list("path", VariableKind::Array, self.path.iter()),
+            Variable::scalar("blocking_flow", self.blocking_flow),
+            Variable::list("flow", VariableKind::Array, flow),
+            Variable::scalar("max_flow", self.max_flow),
+            Variable::list("min_cut", VariableKind::Array, cut),
+        ]
+    }
+    fn overlay(&self) -> Overlay {
+        let level_arcs: HashSet<(u32, u32)> = self
+            .level
+            .keys()
+            .flat_map(|u| self.network.neighbors(*u).iter().map(move |v| (*u, *v)))
+            .filter(|(u, v)| self.is_level_arc(*u, *v))
+            .collect();
+        let residual = self.network.residual_view(&self.path, level_arcs.clone());
+        Overlay {
+            node_annotations: self
+                .level
+                .iter()
+                .map(|(id, level)| (*id, format!("L={}", level)))
+                .collect(),
+            marked_edges: level_arcs,
+            secondary: Some(Rc::new(residual)),
+            ..self.network.overlay(&self.path, &self.source_side)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn setup_network() -> Graph {
+        let edges = vec![
+            (0, 1, 16),
+            (0, 2, 13),
+            (1, 3, 12),
+            (2, 1, 4),
+            (2, 4, 14),
+            (3, 2, 9),
+            (3, 5, 20),
+            (4, 3, 7),
+            (4, 5, 4),
+        ];
+        Graph::from_list_of_weighted_edges(edges, false)
+    }
+
+    #[test]
+    fn test_dinic_max_flow_and_cut() -> Result<(), String> {
+        let mut my_flow = Dinic::new(setup_network(), 0, 5).ok_or("Valid ends")?;
+
+        my_flow.run();
+
+        assert_eq!(my_flow.runtime.max_flow, 23);
+        assert_eq!(my_flow.runtime.min_cut, vec![(1, 3), (4, 3), (4, 5)]);
+        let first = &my_flow.get_history()[0];
+        assert_eq!((first.phase, first.level[&5]), (1, 3));
+        Ok(())
+    }
+
+    #[test]
+    fn test_dinic_undirected_edges_carry_flow_both_ways() -> Result<(), String> {
+        let edges = vec![(0, 1, 2), (1, 2, 1), (0, 2, 1)];
+        let graph = Graph::from_list_of_weighted_edges(edges, true);
+        let mut my_flow = Dinic::new(graph, 2, 0).ok_or("Valid ends")?;
+
+        my_flow.run();
+
+        assert_eq!(my_flow.runtime.max_flow, 2);
+        assert_eq!(my_flow.runtime.network.flow[&(2, 1)], 1);
+        Ok(())
+    }
+}
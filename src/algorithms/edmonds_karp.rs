@@ -0,0 +1,193 @@
+use crate::{
+    algorithms::algorithm::{Algorithm, Snapshot, record, record_last},
+    algorithms::flow::FlowNetwork,
+    algorithms::inspect::{Inspect, Overlay, Variable, VariableKind},
+    graph::graph::Graph,
+};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::rc::Rc;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Runtime {
+    pub network: FlowNetwork,
+    /// Shortest augmenting path found by the last BFS.
+    pub path: Vec<u32>,
+    pub bottleneck: u32,
+    pub max_flow: i64,
+    /// Set at the end: nodes the source still reaches in the residual graph.
+    pub source_side: HashSet<u32>,
+    pub min_cut: Vec<(u32, u32)>,
+}
+/// Edmonds–Karp maximum flow: augments along a shortest residual path,
+/// found by BFS, until the sink is unreachable.
+#[derive(Debug, PartialEq)]
+pub struct EdmondsKarp {
+    pub graph: Graph,
+    pub source: u32,
+    pub sink: u32,
+    pub runtime: Runtime,
+    pub history: Vec<Runtime>,
+}
+
+/// Shortest path from `source` to `sink` over arcs with residual capacity.
+pub fn shortest_residual_path(network: &FlowNetwork, source: u32, sink: u32) -> Vec<u32> {
+    let mut parent = HashMap::from([(source, source)]);
+    let mut queue = VecDeque::from([source]);
+    while let Some(u) = queue.pop_front() {
+        if u == sink {
+            break;
+        }
+        for v in network.neighbors(u) {
+            if network.residual(u, *v) > 0 && !parent.contains_key(v) {
+                parent.insert(*v, u);
+                queue.push_back(*v);
+            }
+        }
+    }
+    if !parent.contains_key(&sink) {
+        return Vec::new();
+    }
+    let mut path = vec![sink];
+    while let Some(u) = path.last().filter(|u| **u != source) {
+        path.push(parent[u]);
+    }
+    path.reverse();
+    path
+}
+
+impl EdmondsKarp {
+    pub fn new(graph: Graph, source: u32, sink: u32) -> Option<Self> {
+        if source == sink || !graph.nodes.contains_key(&source) || !graph.nodes.contains_key(&sink)
+        {
+            return None;
+        }
+        Some(EdmondsKarp {
+            runtime: Runtime {
+                network: FlowNetwork::new(&graph),
+                path: Vec::new(),
+                bottleneck: 0,
+                max_flow: 0,
+                source_side: HashSet::new(),
+                min_cut: Vec::new(),
+            },
+            graph,
+            source,
+            sink,
+            history: Vec::new(),
+        })
+    }
+    fn record_step(&mut self) {
+        record(&mut self.history, &self.runtime);
+    }
+}
+impl Algorithm<Runtime> for EdmondsKarp {
+    fn get_snapshot(&self) -> Snapshot<Runtime> {
+        Snapshot {
+            data: self.runtime.clone(),
+            graph: self.graph.to_snapshot().unwrap(),
+        }
+    }
+    fn get_history(&self) -> &[Runtime] {
+        &self.history
+    }
+    fn run(&mut self) {
+        self.record_step();
+        loop {
+            let path = shortest_residual_path(&self.runtime.network, self.source, self.sink);
+            if path.is_empty() {
+                break;
+            }
+            self.runtime.bottleneck = self.runtime.network.bottleneck(&path);
+            self.runtime.path = path;
+            self.record_step();
+            let (path, bottleneck) = (&self.runtime.path, self.runtime.bottleneck);
+            self.runtime.network.augment(path, bottleneck);
+            self.runtime.max_flow = self.runtime.network.value(self.source);
+            self.record_step();
+        }
+        self.runtime.path.clear();
+        self.runtime.bottleneck = 0;
+        self.runtime.source_side = self.runtime.network.reachable(self.source);
+        self.runtime.min_cut = self.runtime.network.cut(&self.runtime.source_side);
+        record_last(&mut self.history, &self.runtime);
+    }
+}
+impl Inspect for Runtime {
+    fn variables(&self) -> Vec<Variable> {
+        let format = |arcs: &HashMap<(u32, u32), u32>| -> Vec<String> {
+            let mut arcs: Vec<String> = arcs
+                .iter()
+                .map(|((u, v), flow)| format!("{}->{}: {}", u, v, flow))
+                .collect();
+            arcs.sort();
+            arcs
+        };
+        let cut = self.min_cut.iter().map(|(u, v)| format!("{}->{}", u, v));
+        vec![
+            Variable::list("path", VariableKind::Array, self.path.iter()),
+            Variable::scalar("bottleneck", self.bottleneck),
+            Variable::list("flow", VariableKind::Array, format(&self.network.flow)),
+            Variable::scalar("max_flow", self.max_flow),
+            Variable::list("min_cut", VariableKind::Array, cut),
+        ]
+    }
+    fn overlay(&self) -> Overlay {
+        let residual = self.network.residual_view(&self.path, HashSet::new());
+        Overlay {
+            secondary: Some(Rc::new(residual)),
+            ..self.network.overlay(&self.path, &self.source_side)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn setup_network() -> Graph {
+        // The classic CLRS network from 0 to 5, maximum flow 23.
+        let edges = vec![
+            (0, 1, 16),
+            (0, 2, 13),
+            (1, 3, 12),
+            (2, 1, 4),
+            (2, 4, 14),
+            (3, 2, 9),
+            (3, 5, 20),
+            (4, 3, 7),
+            (4, 5, 4),
+        ];
+        Graph::from_list_of_weighted_edges(edges, false)
+    }
+
+    #[test]
+    fn test_edmonds_karp_max_flow_and_cut() -> Result<(), String> {
+        let mut my_flow = EdmondsKarp::new(setup_network(), 0, 5).ok_or("Valid ends")?;
+
+        my_flow.run();
+
+        assert_eq!(my_flow.runtime.max_flow, 23);
+        let cut = &my_flow.runtime.min_cut;
+        let capacity: u32 = cut
+            .iter()
+            .map(|arc| my_flow.runtime.network.capacity[arc])
+            .sum();
+        assert_eq!(capacity, 23);
+        assert_eq!(my_flow.get_history()[1].path, vec![0, 1, 3, 5]);
+        assert!(EdmondsKarp::new(setup_network(), 0, 0).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_edmonds_karp_labels_and_residual_view() -> Result<(), String> {
+        let mut my_flow = EdmondsKarp::new(setup_network(), 0, 5).ok_or("Valid ends")?;
+
+        my_flow.run();
+
+        let overlay = my_flow.get_history()[2].overlay();
+        assert_eq!(overlay.edge_label(0, 1, true), Some("12/16"));
+        let residual = overlay.secondary.expect("Residual view");
+        assert_eq!(residual.graph.get_edge(1, 0).unwrap().runtime.weight, 12);
+        assert_eq!(residual.graph.get_edge(0, 1).unwrap().runtime.weight, 4);
+        Ok(())
+    }
+}
@@ -0,0 +1,367 @@
+use crate::{
+    algorithms::algorithm::{Algorithm, SharedLog, Snapshot, record, record_last},
+    algorithms::inspect::{Inspect, Overlay, Variable, VariableKind},
+    graph::graph::Graph,
+    layouts::layout::{sorted_ids, undirected_neighbors},
+};
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TourKind {
+    Circuit,
+    Path,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Runtime {
+    /// Degree of every node, `in/out` when the graph is directed.
+    pub degrees: Rc<HashMap<u32, String>>,
+    /// Why a tour exists or not, from the degree conditions.
+    pub verdict: String,
+    pub kind: Option<TourKind>,
+    pub stack: Vec<u32>,
+    /// Edges of the walk held by the stack: `trail[i]` enters `stack[i + 1]`.
+    pub trail: Vec<(u32, u32)>,
+    /// Every edge taken so far, in order.
+    pub used: SharedLog<(u32, u32)>,
+    /// Nodes popped off the stack; read backwards they form the tour.
+    pub circuit: SharedLog<u32>,
+    pub circuit_edges: SharedLog<(u32, u32)>,
+    /// The final tour as an ordered edge list.
+    pub tour: Rc<[(u32, u32)]>,
+    /// Edges of `tour` replayed so far.
+    pub shown: usize,
+}
+/// Hierholzer's algorithm: walks unused edges from the start, and whenever a
+/// node has none left moves it from the stack onto the circuit. Works on
+/// directed graphs and on undirected ones, not on a mix of both.
+#[derive(Debug, PartialEq)]
+pub struct Euler {
+    pub graph: Graph,
+    pub directed: bool,
+    pub runtime: Runtime,
+    pub history: Vec<Runtime>,
+}
+
+impl Euler {
+    pub fn new(graph: Graph) -> Self {
+        Euler {
+            graph,
+            directed: false,
+            runtime: Runtime {
+                degrees: Rc::default(),
+                verdict: String::new(),
+                kind: None,
+                stack: Vec::new(),
+                trail: Vec::new(),
+                used: SharedLog::new(),
+                circuit: SharedLog::new(),
+                circuit_edges: SharedLog::new(),
+                tour: Rc::default(),
+                shown: 0,
+            },
+            history: Vec::new(),
+        }
+    }
+    fn record_step(&mut self) {
+        record(&mut self.history, &self.runtime);
+    }
+    /// Every edge once; undirected edges as `(u, v)` with `u <= v`.
+    fn edges(&self) -> Result<Vec<(u32, u32)>, String> {
+        let mut edges = Vec::new();
+        let mut directed = HashSet::new();
+        for node in self.graph.nodes.values() {
+            for edge in node.runtime.edges.values() {
+                let (u, v) = (edge.runtime.node_start, edge.runtime.node_end);
+                directed.insert(edge.runtime.directed);
+                if self.graph.nodes.contains_key(&v) && (edge.runtime.directed || u <= v) {
+                    edges.push((u, v));
+                }
+            }
+        }
+        if directed.len() > 1 {
+            return Err(String::from(
+                "Graph mixes directed and undirected edges; make them all one kind",
+            ));
+        }
+        edges.sort_unstable();
+        Ok(edges)
+    }
+    /// Checks the degree conditions and connectivity, filling `degrees`, and
+    /// returns the start node of the tour.
+    fn check(&mut self, edges: &[(u32, u32)]) -> Result<u32, String> {
+        let mut in_degree: HashMap<u32, i64> = HashMap::new();
+        let mut out_degree: HashMap<u32, i64> = HashMap::new();
+        for (u, v) in edges {
+            *out_degree.entry(*u).or_insert(0) += 1;
+            *in_degree.entry(*v).or_insert(0) += 1;
+        }
+        let ids = sorted_ids(&self.graph);
+        let degree = |degrees: &HashMap<u32, i64>, id: &u32| degrees.get(id).copied().unwrap_or(0);
+        let mut degrees = HashMap::new();
+        for id in ids.iter() {
+            let (d_in, d_out) = (degree(&in_degree, id), degree(&out_degree, id));
+            let text = match self.directed {
+                true => format!("{}/{}", d_in, d_out),
+                false => (d_in + d_out).to_string(),
+            };
+            degrees.insert(*id, text);
+        }
+        self.runtime.degrees = Rc::new(degrees);
+        let touched: Vec<u32> = ids
+            .iter()
+            .copied()
+            .filter(|id| degree(&in_degree, id) + degree(&out_degree, id) > 0)
+            .collect();
+        let Some(first) = touched.first().copied() else {
+            return Err(String::from("Graph has no edges"));
+        };
+        let reached = reachable(&self.graph, first);
+        if let Some(other) = touched.iter().find(|id| !reached.contains(id)) {
+            return Err(format!(
+                "Edges lie in more than one component, e.g. at nodes {} and {}",
+                first, other
+            ));
+        }
+        if !self.directed {
+            let odd: Vec<u32> = touched
+                .iter()
+                .copied()
+                .filter(|id| (degree(&in_degree, id) + degree(&out_degree, id)) % 2 == 1)
+                .collect();
+            return match odd.len() {
+                0 => {
+                    self.runtime.verdict = String::from("Every degree is even: Euler circuit");
+                    self.runtime.kind = Some(TourKind::Circuit);
+                    Ok(first)
+                }
+                2 => {
+                    self.runtime.verdict = format!(
+                        "Exactly two nodes have odd degree: Euler path from {} to {}",
+                        odd[0], odd[1]
+                    );
+                    self.runtime.kind = Some(TourKind::Path);
+                    Ok(odd[0])
+                }
+                _ => {
+                    let odd: Vec<String> = odd.iter().map(|id| id.to_string()).collect();
+                    Err(format!(
+                        "Nodes {} have odd degree; a tour allows zero or two",
+                        odd.join(", ")
+                    ))
+                }
+            };
+        }
+        let balance: Vec<(u32, i64)> = touched
+            .iter()
+            .map(|id| (*id, degree(&out_degree, id) - degree(&in_degree, id)))
+            .filter(|(_, balance)| *balance != 0)
+            .collect();
+        match balance[..] {
+            [] => {
+                self.runtime.verdict = String::from("Every node has in = out: Euler circuit");
+                self.runtime.kind = Some(TourKind::Circuit);
+                Ok(first)
+            }
+            [(a, 1), (b, -1)] | [(b, -1), (a, 1)] => {
+                self.runtime.verdict = format!(
+                    "Node {} has out = in + 1 and node {} has in = out + 1: Euler path",
+                    a, b
+                );
+                self.runtime.kind = Some(TourKind::Path);
+                Ok(a)
+            }
+            _ => {
+                let nodes: Vec<String> = balance
+                    .iter()
+                    .map(|(id, balance)| format!("{} ({:+})", id, balance))
+                    .collect();
+                Err(format!(
+                    "Nodes with out - in != 0: {}; a tour allows none, or one +1 and one -1",
+                    nodes.join(", ")
+                ))
+            }
+        }
+    }
+}
+
+fn reachable(graph: &Graph, start: u32) -> HashSet<u32> {
+    let neighbors = undirected_neighbors(graph);
+    let mut seen = HashSet::from([start]);
+    let mut stack = vec![start];
+    while let Some(u) = stack.pop() {
+        for v in neighbors[&u].iter() {
+            if seen.insert(*v) {
+                stack.push(*v);
+            }
+        }
+    }
+    seen
+}
+
+impl Algorithm<Runtime> for Euler {
+    fn get_snapshot(&self) -> Snapshot<Runtime> {
+        Snapshot {
+            data: self.runtime.clone(),
+            graph: self.graph.to_snapshot().unwrap(),
+        }
+    }
+    fn get_history(&self) -> &[Runtime] {
+        &self.history
+    }
+    fn run(&mut self) {
+        self.directed = self.graph.nodes.values().any(|node| {
+            node.runtime
+                .edges
+                .values()
+                .any(|edge| edge.runtime.directed)
+        });
+        let (start, edges) = match self
+            .edges()
+            .and_then(|edges| Ok((self.check(&edges)?, edges)))
+        {
+            Ok(found) => found,
+            Err(reason) => {
+                self.runtime.verdict = reason;
+                record_last(&mut self.history, &self.runtime);
+                return;
+            }
+        };
+        let mut adjacency: HashMap<u32, Vec<(u32, usize)>> = HashMap::new();
+        for (index, (u, v)) in edges.iter().enumerate() {
+            adjacency.entry(*u).or_default().push((*v, index));
+            if !self.directed && u != v {
+                adjacency.entry(*v).or_default().push((*u, index));
+            }
+        }
+        let mut used = vec![false; edges.len()];
+        let mut next: HashMap<u32, usize> = HashMap::new();
+        self.runtime.stack.push(start);
+        self.record_step();
+        while let Some(u) = self.runtime.stack.last().copied() {
+            let arcs = adjacency.get(&u).map_or(&[][..], |arcs| arcs.as_slice());
+            let index = next.entry(u).or_insert(0);
+            while arcs.get(*index).is_some_and(|(_, edge)| used[*edge]) {
+                *index += 1;
+            }
+            match arcs.get(*index) {
+                Some((v, edge)) => {
+                    used[*edge] = true;
+                    self.runtime.stack.push(*v);
+                    self.runtime.trail.push((u, *v));
+                    self.runtime.used.push((u, *v));
+                }
+                None => {
+                    self.runtime.stack.pop();
+                    self.runtime.circuit.push(u);
+                    if let Some(edge) = self.runtime.trail.pop() {
+                        self.runtime.circuit_edges.push(edge);
+                    }
+                }
+            }
+            self.record_step();
+        }
+        self.runtime.tour = self
+            .runtime
+            .circuit_edges
+            .items()
+            .iter()
+            .rev()
+            .copied()
+            .collect();
+        // Replay the finished tour one edge at a time.
+        for shown in 1..=self.runtime.tour.len() {
+            self.runtime.shown = shown;
+            self.record_step();
+        }
+        record_last(&mut self.history, &self.runtime);
+    }
+}
+impl Inspect for Runtime {
+    fn variables(&self) -> Vec<Variable> {
+        let format = |edges: &[(u32, u32)]| -> Vec<String> {
+            edges.iter().map(|(u, v)| format!("{}-{}", u, v)).collect()
+        };
+        let circuit = self.circuit.items();
+        vec![
+            Variable::scalar("verdict", &self.verdict),
+            Variable::map("degree", &self.degrees, |degree| degree.clone()),
+            Variable::list("stack", VariableKind::Stack, self.stack.iter()),
+            Variable::list("used", VariableKind::Array, format(&self.used.items())),
+            Variable::list("circuit", VariableKind::Array, circuit.iter().rev()),
+            Variable::list("tour", VariableKind::Array, format(&self.tour)),
+        ]
+    }
+    fn overlay(&self) -> Overlay {
+        let mut overlay = Overlay {
+            node_annotations: self
+                .degrees
+                .iter()
+                .map(|(id, degree)| (*id, format!("d={}", degree)))
+                .collect(),
+            highlighted_nodes: self.stack.last().copied().into_iter().collect(),
+            marked_edges: self.trail.iter().copied().collect(),
+            highlighted_edges: self.circuit_edges.items().iter().copied().collect(),
+            ..Overlay::default()
+        };
+        if self.shown > 0 {
+            let shown = &self.tour[..self.shown];
+            overlay.highlighted_nodes = HashSet::from([shown[shown.len() - 1].1]);
+            overlay.highlighted_edges = shown.iter().copied().collect();
+            overlay.edge_labels = shown
+                .iter()
+                .enumerate()
+                .map(|(index, edge)| (*edge, format!("#{}", index + 1)))
+                .collect();
+        }
+        overlay
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_euler_undirected_path() -> Result<(), String> {
+        // A house shape: nodes 0 and 2 have odd degree.
+        let edges = vec![(0, 1), (0, 2), (1, 3), (2, 3), (2, 4), (3, 4), (0, 3)];
+        let mut my_euler = Euler::new(Graph::from_list_of_edges(edges.clone(), true));
+
+        my_euler.run();
+
+        let runtime = &my_euler.runtime;
+        assert_eq!(runtime.kind, Some(TourKind::Path));
+        assert_eq!(runtime.tour.len(), edges.len());
+        assert_eq!(runtime.tour[0].0, 0);
+        assert!(runtime.tour.windows(2).all(|pair| pair[0].1 == pair[1].0));
+        let covered: HashSet<(u32, u32)> = runtime
+            .tour
+            .iter()
+            .map(|(u, v)| (*u.min(v), *u.max(v)))
+            .collect();
+        assert_eq!(covered, edges.into_iter().collect());
+        Ok(())
+    }
+
+    #[test]
+    fn test_euler_directed_circuit_and_failures() -> Result<(), String> {
+        let edges = vec![(0, 1), (1, 2), (2, 0), (0, 3), (3, 0)];
+        let mut my_euler = Euler::new(Graph::from_list_of_edges(edges, false));
+
+        my_euler.run();
+
+        assert_eq!(my_euler.runtime.kind, Some(TourKind::Circuit));
+        assert_eq!(my_euler.runtime.tour.len(), 5);
+        assert_eq!(my_euler.runtime.tour[4].1, 0);
+
+        let star = Graph::from_list_of_edges(vec![(0, 1), (0, 2), (0, 3)], true);
+        let mut my_euler = Euler::new(star);
+        my_euler.run();
+        assert_eq!(my_euler.runtime.kind, None);
+        assert!(my_euler.runtime.verdict.contains("0, 1, 2, 3"));
+        assert_eq!(my_euler.get_history().len(), 1);
+        Ok(())
+    }
+}
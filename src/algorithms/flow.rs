@@ -0,0 +1,202 @@
+use crate::{
+    algorithms::inspect::{Overlay, SecondaryView},
+    edge::edge_model::Edge,
+    graph::graph::Graph,
+};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::rc::Rc;
+
+/// Flow on the arcs of a graph, with edge weights as capacities. An
+/// undirected edge is two opposite arcs. Flow pushed against an arc first
+/// cancels flow on the opposite arc.
+#[derive(Debug, PartialEq, Clone)]
+pub struct FlowNetwork {
+    pub capacity: HashMap<(u32, u32), u32>,
+    pub flow: HashMap<(u32, u32), u32>,
+    /// Residual neighbors of every node, sorted: arc ends and arc starts.
+    adjacency: Rc<HashMap<u32, Vec<u32>>>,
+    /// The input nodes without edges, for drawing the residual graph.
+    nodes: Rc<Graph>,
+}
+
+impl FlowNetwork {
+    pub fn new(graph: &Graph) -> Self {
+        let mut capacity = HashMap::new();
+        let mut adjacency: HashMap<u32, Vec<u32>> =
+            graph.nodes.keys().map(|id| (*id, Vec::new())).collect();
+        for node in graph.nodes.values() {
+            for edge in node.runtime.edges.values() {
+                let (start, end) = (edge.runtime.node_start, edge.runtime.node_end);
+                if start == end || !graph.nodes.contains_key(&end) {
+                    continue;
+                }
+                capacity.insert((start, end), edge.runtime.weight);
+                adjacency.entry(start).or_default().push(end);
+                adjacency.entry(end).or_default().push(start);
+            }
+        }
+        for list in adjacency.values_mut() {
+            list.sort_unstable();
+            list.dedup();
+        }
+        let mut nodes = graph.clone();
+        for node in nodes.nodes.values_mut() {
+            node.runtime.edges.clear();
+        }
+        FlowNetwork {
+            flow: capacity.keys().map(|arc| (*arc, 0)).collect(),
+            capacity,
+            adjacency: Rc::new(adjacency),
+            nodes: Rc::new(nodes),
+        }
+    }
+    pub fn neighbors(&self, u: u32) -> &[u32] {
+        self.adjacency.get(&u).map_or(&[], |list| list.as_slice())
+    }
+    /// How much more can go from `u` to `v`, saturating at `u32::MAX` when
+    /// both arcs of a pair are near it.
+    pub fn residual(&self, u: u32, v: u32) -> u32 {
+        let forward = self.capacity.get(&(u, v)).copied().unwrap_or(0);
+        let used = self.flow.get(&(u, v)).copied().unwrap_or(0);
+        let back = self.flow.get(&(v, u)).copied().unwrap_or(0);
+        (forward - used).saturating_add(back)
+    }
+    /// Sends `amount` from `u` to `v`, at most `residual(u, v)`.
+    pub fn push(&mut self, u: u32, v: u32, amount: u32) {
+        let mut amount = amount;
+        if let Some(back) = self.flow.get_mut(&(v, u)) {
+            let cancelled = amount.min(*back);
+            *back -= cancelled;
+            amount -= cancelled;
+        }
+        if amount > 0 {
+            *self.flow.get_mut(&(u, v)).expect("Arc with capacity") += amount;
+        }
+    }
+    /// Sends `amount` along every arc of `path`.
+    pub fn augment(&mut self, path: &[u32], amount: u32) {
+        for pair in path.windows(2) {
+            self.push(pair[0], pair[1], amount);
+        }
+    }
+    pub fn bottleneck(&self, path: &[u32]) -> u32 {
+        path.windows(2)
+            .map(|pair| self.residual(pair[0], pair[1]))
+            .min()
+            .unwrap_or(0)
+    }
+    /// Net flow leaving `source`.
+    pub fn value(&self, source: u32) -> i64 {
+        let (mut out, mut into) = (0, 0);
+        for ((u, v), flow) in self.flow.iter() {
+            if *u == source {
+                out += *flow as i64;
+            }
+            if *v == source {
+                into += *flow as i64;
+            }
+        }
+        out - into
+    }
+    /// Nodes reachable from `source` through arcs with residual capacity.
+    pub fn reachable(&self, source: u32) -> HashSet<u32> {
+        let mut seen = HashSet::from([source]);
+        let mut queue = VecDeque::from([source]);
+        while let Some(u) = queue.pop_front() {
+            for v in self.neighbors(u) {
+                if self.residual(u, *v) > 0 && seen.insert(*v) {
+                    queue.push_back(*v);
+                }
+            }
+        }
+        seen
+    }
+    /// Arcs leaving `side`, sorted. With `side` the residual reach of the
+    /// source after a maximum flow, these form a minimum cut.
+    pub fn cut(&self, side: &HashSet<u32>) -> Vec<(u32, u32)> {
+        let mut cut: Vec<(u32, u32)> = self
+            .capacity
+            .keys()
+            .filter(|(u, v)| side.contains(u) && !side.contains(v))
+            .copied()
+            .collect();
+        cut.sort_unstable();
+        cut
+    }
+    /// `flow/capacity` for every arc.
+    pub fn labels(&self) -> HashMap<(u32, u32), String> {
+        self.capacity
+            .iter()
+            .map(|(arc, capacity)| (*arc, format!("{}/{}", self.flow[arc], capacity)))
+            .collect()
+    }
+    /// Residual graph at the input positions, weighted by residual capacity,
+    /// with `path` highlighted and `marked` arcs dashed.
+    pub fn residual_view(&self, path: &[u32], marked: HashSet<(u32, u32)>) -> SecondaryView {
+        let mut graph = (*self.nodes).clone();
+        for (u, ends) in self.adjacency.iter() {
+            for v in ends {
+                let residual = self.residual(*u, *v);
+                if residual > 0 {
+                    graph.insert_edge(Edge::new(*u, *v, residual));
+                }
+            }
+        }
+        let overlay = Overlay {
+            highlighted_nodes: path.iter().copied().collect(),
+            highlighted_edges: path.windows(2).map(|pair| (pair[0], pair[1])).collect(),
+            marked_edges: marked,
+            ..Overlay::default()
+        };
+        SecondaryView {
+            title: String::from("residual graph"),
+            graph,
+            overlay,
+        }
+    }
+    /// Flow labels, the current `path` and, once `source_side` is known,
+    /// the minimum cut with both sides colored.
+    pub fn overlay(&self, path: &[u32], source_side: &HashSet<u32>) -> Overlay {
+        let mut overlay = Overlay {
+            edge_labels: self.labels(),
+            highlighted_nodes: path.iter().copied().collect(),
+            highlighted_edges: path.windows(2).map(|pair| (pair[0], pair[1])).collect(),
+            ..Overlay::default()
+        };
+        if !source_side.is_empty() {
+            overlay.highlighted_edges.extend(self.cut(source_side));
+            overlay.node_colors = self
+                .nodes
+                .nodes
+                .keys()
+                .map(|id| (*id, usize::from(!source_side.contains(id))))
+                .collect();
+        }
+        overlay
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_cancels_opposite_flow() -> Result<(), String> {
+        let edges = vec![(0, 1, 3), (1, 0, 2)];
+        let mut network = FlowNetwork::new(&Graph::from_list_of_weighted_edges(edges, false));
+
+        network.push(0, 1, 3);
+        assert_eq!(network.residual(1, 0), 5);
+        network.push(1, 0, 4);
+
+        assert_eq!(network.flow[&(0, 1)], 0);
+        assert_eq!(network.flow[&(1, 0)], 1);
+        assert_eq!(network.value(0), -1);
+        assert_eq!(network.value(1), 1);
+        let edges = vec![(0, 1, u32::MAX), (1, 0, u32::MAX)];
+        let mut network = FlowNetwork::new(&Graph::from_list_of_weighted_edges(edges, false));
+        network.push(1, 0, 1);
+        assert_eq!(network.residual(0, 1), u32::MAX);
+        Ok(())
+    }
+}
@@ -0,0 +1,210 @@
+use crate::{
+    algorithms::algorithm::{Algorithm, Snapshot, record, record_last},
+    algorithms::inspect::{Inspect, Overlay, Variable, VariableKind},
+    algorithms::matching::{Bipartition, flip, format_matching},
+    graph::graph::Graph,
+};
+use std::collections::{HashMap, VecDeque};
+use std::rc::Rc;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Runtime {
+    pub bipartition: Rc<Bipartition>,
+    pub phase: u32,
+    /// BFS layer of left nodes in this phase, free left nodes at 0.
+    pub dist: HashMap<u32, u32>,
+    /// Layer of the left nodes that reach a free right node, if any.
+    pub shortest: Option<u32>,
+    /// Augmenting path just found: left, right, left, ..., right.
+    pub path: Vec<u32>,
+    /// Partner of every matched node, stored for both ends.
+    pub mate: HashMap<u32, u32>,
+    pub size: usize,
+}
+/// Hopcroft–Karp maximum bipartite matching: every phase layers the graph by
+/// BFS from the free left nodes, then augments along a maximal set of
+/// vertex-disjoint shortest alternating paths.
+#[derive(Debug, PartialEq)]
+pub struct HopcroftKarp {
+    pub graph: Graph,
+    pub runtime: Runtime,
+    pub history: Vec<Runtime>,
+}
+
+impl HopcroftKarp {
+    /// `None` when the graph is not bipartite.
+    pub fn new(graph: Graph) -> Option<Self> {
+        let bipartition = Bipartition::new(&graph)?;
+        Some(HopcroftKarp {
+            graph,
+            runtime: Runtime {
+                bipartition: Rc::new(bipartition),
+                phase: 0,
+                dist: HashMap::new(),
+                shortest: None,
+                path: Vec::new(),
+                mate: HashMap::new(),
+                size: 0,
+            },
+            history: Vec::new(),
+        })
+    }
+    fn record_step(&mut self) {
+        record(&mut self.history, &self.runtime);
+    }
+    /// Layers from the free left nodes. Returns whether a free right node is
+    /// reachable.
+    fn build_layers(&mut self) -> bool {
+        let bipartition = &self.runtime.bipartition;
+        let mate = &self.runtime.mate;
+        let mut dist: HashMap<u32, u32> = HashMap::new();
+        let mut queue = VecDeque::new();
+        for u in bipartition.left.iter().filter(|u| !mate.contains_key(u)) {
+            dist.insert(*u, 0);
+            queue.push_back(*u);
+        }
+        let mut shortest = None;
+        while let Some(u) = queue.pop_front() {
+            if shortest.is_some_and(|layer| dist[&u] > layer) {
+                break;
+            }
+            for v in bipartition.neighbors(u) {
+                match mate.get(v) {
+                    None => shortest = shortest.or(Some(dist[&u])),
+                    Some(w) if !dist.contains_key(w) => {
+                        dist.insert(*w, dist[&u] + 1);
+                        queue.push_back(*w);
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+        self.runtime.dist = dist;
+        self.runtime.shortest = shortest;
+        shortest.is_some()
+    }
+    /// DFS along the layers from every free left node. A left node that leads
+    /// nowhere loses its layer, so later paths skip it and stay disjoint.
+    fn augment_phase(&mut self) {
+        let bipartition = Rc::clone(&self.runtime.bipartition);
+        let shortest = self.runtime.shortest.expect("Layered");
+        let mut next: HashMap<u32, usize> = HashMap::new();
+        for root in bipartition.left.iter() {
+            if self.runtime.mate.contains_key(root) || !self.runtime.dist.contains_key(root) {
+                continue;
+            }
+            let mut path = vec![*root];
+            let found = loop {
+                let Some(u) = path.last().copied() else {
+                    break false;
+                };
+                let index = next.entry(u).or_insert(0);
+                let Some(v) = bipartition.neighbors(u).get(*index).copied() else {
+                    self.runtime.dist.remove(&u);
+                    path.pop();
+                    path.pop();
+                    continue;
+                };
+                *index += 1;
+                let layer = self.runtime.dist[&u];
+                match self.runtime.mate.get(&v) {
+                    None if layer == shortest => {
+                        path.push(v);
+                        break true;
+                    }
+                    Some(w) if self.runtime.dist.get(w) == Some(&(layer + 1)) => {
+                        path.extend([v, *w]);
+                    }
+                    _ => {}
+                }
+            };
+            if found {
+                self.runtime.path = path;
+                self.record_step();
+                flip(&mut self.runtime.mate, &self.runtime.path);
+                self.runtime.size += 1;
+                // Used nodes must not appear on another path of this phase.
+                for u in self.runtime.path.iter().step_by(2) {
+                    self.runtime.dist.remove(u);
+                }
+            }
+        }
+    }
+}
+impl Algorithm<Runtime> for HopcroftKarp {
+    fn get_snapshot(&self) -> Snapshot<Runtime> {
+        Snapshot {
+            data: self.runtime.clone(),
+            graph: self.graph.to_snapshot().unwrap(),
+        }
+    }
+    fn get_history(&self) -> &[Runtime] {
+        &self.history
+    }
+    fn run(&mut self) {
+        while self.build_layers() {
+            self.runtime.phase += 1;
+            self.runtime.path.clear();
+            self.record_step();
+            self.augment_phase();
+        }
+        self.runtime.path.clear();
+        self.runtime.dist.clear();
+        record_last(&mut self.history, &self.runtime);
+    }
+}
+impl Inspect for Runtime {
+    fn variables(&self) -> Vec<Variable> {
+        let shortest = self
+            .shortest
+            .map_or(String::from("-"), |layer| layer.to_string());
+        vec![
+            Variable::scalar("phase", self.phase),
+            Variable::map("dist", &self.dist, |dist| dist.to_string()),
+            Variable::scalar("shortest", shortest),
+            Variable::list("path", VariableKind::Array, self.path.iter()),
+            Variable::list(
+                "matching",
+                VariableKind::Array,
+                format_matching(&self.bipartition, &self.mate),
+            ),
+            Variable::scalar("size", self.size),
+        ]
+    }
+    fn overlay(&self) -> Overlay {
+        Overlay {
+            node_annotations: self
+                .dist
+                .iter()
+                .map(|(id, dist)| (*id, format!("d={}", dist)))
+                .collect(),
+            ..self.bipartition.overlay(&self.mate, &self.path)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hopcroft_karp_phases() -> Result<(), String> {
+        // A path 0-4-1-5-2-6-3 plus 0-7: the first phase matches greedily,
+        // later phases need longer alternating paths.
+        let edges = vec![(0, 4), (1, 4), (1, 5), (2, 5), (2, 6), (3, 6), (0, 7)];
+        let graph = Graph::from_list_of_edges(edges, true);
+        let mut my_matching = HopcroftKarp::new(graph).ok_or("Bipartite")?;
+
+        my_matching.run();
+
+        assert_eq!(my_matching.runtime.size, 4);
+        let first = &my_matching.get_history()[0];
+        assert_eq!((first.phase, first.shortest), (1, Some(0)));
+        assert!(my_matching.runtime.phase >= 2);
+        for (u, v) in my_matching.runtime.mate.iter() {
+            assert_eq!(my_matching.runtime.mate[v], *u);
+            assert!(my_matching.graph.get_edge(*u, *v).is_some());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,205 @@
+use crate::graph::graph::Graph;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::rc::Rc;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum VariableKind {
+    Scalar,
+    Array,
+    Queue,
+    Stack,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum VariableValue {
+    Scalar(String),
+    List(Vec<String>),
+    Map(Vec<(u32, String)>),
+}
+
+/// A single named piece of an algorithm runtime, already formatted for display.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Variable {
+    pub name: String,
+    pub kind: VariableKind,
+    pub value: VariableValue,
+}
+
+impl Variable {
+    pub fn scalar<V: Display>(name: &str, value: V) -> Self {
+        Variable {
+            name: String::from(name),
+            kind: VariableKind::Scalar,
+            value: VariableValue::Scalar(value.to_string()),
+        }
+    }
+    pub fn list<I, V>(name: &str, kind: VariableKind, values: I) -> Self
+    where
+        I: IntoIterator<Item = V>,
+        V: Display,
+    {
+        Variable {
+            name: String::from(name),
+            kind,
+            value: VariableValue::List(values.into_iter().map(|v| v.to_string()).collect()),
+        }
+    }
+    /// Builds an array variable indexed by node id, sorted by id so consecutive
+    /// steps line up entry by entry.
+    pub fn map<V, F>(name: &str, values: &HashMap<u32, V>, format: F) -> Self
+    where
+        F: Fn(&V) -> String,
+    {
+        let mut entries: Vec<(u32, String)> = values
+            .iter()
+            .map(|(key, value)| (*key, format(value)))
+            .collect();
+        entries.sort_by_key(|(key, _)| *key);
+        Variable {
+            name: String::from(name),
+            kind: VariableKind::Array,
+            value: VariableValue::Map(entries),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match &self.value {
+            VariableValue::Scalar(_) => 1,
+            VariableValue::List(values) => values.len(),
+            VariableValue::Map(entries) => entries.len(),
+        }
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Flags, entry by entry, whether the value differs from the same variable
+    /// in the previous step. Everything counts as changed when there is none.
+    pub fn changed_entries(&self, previous: Option<&Variable>) -> Vec<bool> {
+        match (&self.value, previous.map(|variable| &variable.value)) {
+            (VariableValue::Scalar(value), Some(VariableValue::Scalar(old))) => {
+                vec![value != old]
+            }
+            (VariableValue::List(values), Some(VariableValue::List(old))) => values
+                .iter()
+                .enumerate()
+                .map(|(index, value)| old.get(index) != Some(value))
+                .collect(),
+            (VariableValue::Map(entries), Some(VariableValue::Map(old))) => {
+                let old: HashMap<u32, &String> =
+                    old.iter().map(|(key, value)| (*key, value)).collect();
+                entries
+                    .iter()
+                    .map(|(key, value)| old.get(key) != Some(&value))
+                    .collect()
+            }
+            _ => vec![true; self.len()],
+        }
+    }
+}
+
+/// Per-step decorations drawn on top of the graph canvas.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Overlay {
+    pub node_annotations: HashMap<u32, String>,
+    pub highlighted_nodes: HashSet<u32>,
+    /// Edges as `(start, end)`; an undirected edge matches either orientation.
+    pub highlighted_edges: HashSet<(u32, u32)>,
+    /// Edges drawn dashed, e.g. the frontier of a search.
+    pub marked_edges: HashSet<(u32, u32)>,
+    /// Text drawn instead of the weight, e.g. `flow/capacity`.
+    pub edge_labels: HashMap<(u32, u32), String>,
+    /// Palette index per node, e.g. its component.
+    pub node_colors: HashMap<u32, usize>,
+    pub secondary: Option<Rc<SecondaryView>>,
+}
+
+/// Another graph the GUI can show instead of the input, such as a
+/// condensation DAG, with its own decorations.
+#[derive(Debug, PartialEq, Clone)]
+pub struct SecondaryView {
+    pub title: String,
+    pub graph: Graph,
+    pub overlay: Overlay,
+}
+
+fn contains_edge(edges: &HashSet<(u32, u32)>, start: u32, end: u32, directed: bool) -> bool {
+    edges.contains(&(start, end)) || (!directed && edges.contains(&(end, start)))
+}
+
+impl Overlay {
+    /// Highlights the nodes of a cycle and the edges closing it, in order.
+    pub fn highlight_cycle(&mut self, cycle: &[u32]) {
+        self.highlighted_nodes.extend(cycle.iter().copied());
+        let closing = cycle.first().copied();
+        let ends = cycle.iter().skip(1).copied().chain(closing);
+        self.highlighted_edges
+            .extend(cycle.iter().copied().zip(ends));
+    }
+    pub fn is_edge_highlighted(&self, start: u32, end: u32, directed: bool) -> bool {
+        contains_edge(&self.highlighted_edges, start, end, directed)
+    }
+    pub fn is_edge_marked(&self, start: u32, end: u32, directed: bool) -> bool {
+        contains_edge(&self.marked_edges, start, end, directed)
+    }
+    pub fn edge_label(&self, start: u32, end: u32, directed: bool) -> Option<&str> {
+        let label = match directed {
+            true => self.edge_labels.get(&(start, end)),
+            false => (self.edge_labels.get(&(start, end))).or(self.edge_labels.get(&(end, start))),
+        };
+        label.map(|label| label.as_str())
+    }
+}
+
+/// Implemented by algorithm runtimes so the GUI can display their state
+/// without knowing the concrete algorithm.
+pub trait Inspect {
+    fn variables(&self) -> Vec<Variable>;
+    fn overlay(&self) -> Overlay {
+        Overlay::default()
+    }
+}
+
+pub fn find_variable<'a>(variables: &'a [Variable], name: &str) -> Option<&'a Variable> {
+    variables.iter().find(|variable| variable.name == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scalar_change_is_detected() -> Result<(), String> {
+        let before = Variable::scalar("active_node", 1);
+        let after = Variable::scalar("active_node", 2);
+
+        assert_eq!(after.changed_entries(Some(&before)), vec![true]);
+        assert_eq!(before.changed_entries(Some(&before)), vec![false]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_map_changes_are_per_entry() -> Result<(), String> {
+        let mut distances: HashMap<u32, i32> = vec![(0, 0), (1, -1), (2, -1)].into_iter().collect();
+        let before = Variable::map("distances", &distances, |d| d.to_string());
+        distances.insert(2, 1);
+        let after = Variable::map("distances", &distances, |d| d.to_string());
+
+        assert_eq!(
+            after.changed_entries(Some(&before)),
+            vec![false, false, true]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_growth_is_highlighted() -> Result<(), String> {
+        let before = Variable::list("queue", VariableKind::Queue, vec![3]);
+        let after = Variable::list("queue", VariableKind::Queue, vec![3, 4]);
+
+        assert_eq!(after.changed_entries(Some(&before)), vec![false, true]);
+        assert_eq!(after.changed_entries(None), vec![true, true]);
+        Ok(())
+    }
+}
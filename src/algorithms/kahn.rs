@@ -0,0 +1,215 @@
+use crate::{
+    algorithms::algorithm::{Algorithm, SharedLog, Snapshot, record, record_last},
+    algorithms::inspect::{Inspect, Overlay, Variable, VariableKind},
+    graph::graph::Graph,
+};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Runtime {
+    pub active_node: Option<u32>,
+    pub in_degree: HashMap<u32, usize>,
+    pub queue: VecDeque<u32>,
+    pub order: SharedLog<u32>,
+    /// Nodes of a cycle, in edge order, when no ordering exists.
+    pub cycle: Vec<u32>,
+}
+/// Kahn's topological sort: repeatedly removes a node without incoming edges.
+#[derive(Debug, PartialEq)]
+pub struct Kahn {
+    pub graph: Graph,
+    pub runtime: Runtime,
+    pub history: Vec<Runtime>,
+}
+
+/// Sorted successors of `id`, self-loops included.
+pub fn successors(graph: &Graph, id: u32) -> Vec<u32> {
+    let mut ends: Vec<u32> = graph.nodes[&id]
+        .runtime
+        .edges
+        .keys()
+        .filter(|end| graph.nodes.contains_key(end))
+        .copied()
+        .collect();
+    ends.sort_unstable();
+    ends
+}
+
+/// A cycle among `remaining`, where every node has a predecessor in
+/// `remaining` (what Kahn's algorithm leaves behind). Walks predecessors
+/// until a node repeats.
+fn find_cycle(graph: &Graph, remaining: &HashSet<u32>) -> Vec<u32> {
+    let mut predecessor: HashMap<u32, u32> = HashMap::new();
+    for start in remaining.iter() {
+        for end in successors(graph, *start) {
+            if remaining.contains(&end) {
+                let entry = predecessor.entry(end).or_insert(*start);
+                *entry = (*entry).min(*start);
+            }
+        }
+    }
+    let Some(mut node) = remaining.iter().min().copied() else {
+        return Vec::new();
+    };
+    let mut walk = Vec::new();
+    let mut seen = HashMap::new();
+    while !seen.contains_key(&node) {
+        seen.insert(node, walk.len());
+        walk.push(node);
+        node = predecessor[&node];
+    }
+    let mut cycle = walk.split_off(seen[&node]);
+    // The walk went against the edges.
+    cycle.reverse();
+    cycle.rotate_right(1);
+    cycle
+}
+
+impl Kahn {
+    pub fn new(graph: Graph) -> Self {
+        let mut in_degree: HashMap<u32, usize> = graph.nodes.keys().map(|id| (*id, 0)).collect();
+        for node in graph.nodes.values() {
+            for end in node.runtime.edges.keys() {
+                if let Some(degree) = in_degree.get_mut(end) {
+                    *degree += 1;
+                }
+            }
+        }
+        Kahn {
+            graph,
+            runtime: Runtime {
+                active_node: None,
+                in_degree,
+                queue: VecDeque::new(),
+                order: SharedLog::new(),
+                cycle: Vec::new(),
+            },
+            history: Vec::new(),
+        }
+    }
+    fn record_step(&mut self) {
+        record(&mut self.history, &self.runtime);
+    }
+}
+impl Algorithm<Runtime> for Kahn {
+    fn get_snapshot(&self) -> Snapshot<Runtime> {
+        Snapshot {
+            data: self.runtime.clone(),
+            graph: self.graph.to_snapshot().unwrap(),
+        }
+    }
+    fn get_history(&self) -> &[Runtime] {
+        &self.history
+    }
+    fn run(&mut self) {
+        let mut sources: Vec<u32> = self
+            .runtime
+            .in_degree
+            .iter()
+            .filter(|(_, degree)| **degree == 0)
+            .map(|(id, _)| *id)
+            .collect();
+        sources.sort_unstable();
+        self.runtime.queue.extend(sources);
+        self.record_step();
+
+        while let Some(u) = self.runtime.queue.pop_front() {
+            self.runtime.active_node = Some(u);
+            self.runtime.order.push(u);
+            self.record_step();
+            for v in successors(&self.graph, u) {
+                let degree = self.runtime.in_degree.get_mut(&v).expect("It exists");
+                *degree -= 1;
+                if *degree == 0 {
+                    self.runtime.queue.push_back(v);
+                }
+                self.record_step();
+            }
+        }
+        self.runtime.active_node = None;
+        if self.runtime.order.len() < self.graph.nodes.len() {
+            let placed: HashSet<u32> = self.runtime.order.items().iter().copied().collect();
+            let remaining = self
+                .graph
+                .nodes
+                .keys()
+                .filter(|id| !placed.contains(id))
+                .copied()
+                .collect();
+            self.runtime.cycle = find_cycle(&self.graph, &remaining);
+        }
+        record_last(&mut self.history, &self.runtime);
+    }
+}
+impl Inspect for Runtime {
+    fn variables(&self) -> Vec<Variable> {
+        let active = self
+            .active_node
+            .map_or(String::from("-"), |id| id.to_string());
+        vec![
+            Variable::scalar("active_node", active),
+            Variable::map("in_degree", &self.in_degree, |degree| degree.to_string()),
+            Variable::list("queue", VariableKind::Queue, self.queue.iter()),
+            Variable::list("order", VariableKind::Array, self.order.items().iter()),
+            Variable::list("cycle", VariableKind::Array, self.cycle.iter()),
+        ]
+    }
+    fn overlay(&self) -> Overlay {
+        let placed: HashMap<u32, usize> = self
+            .order
+            .items()
+            .iter()
+            .enumerate()
+            .map(|(index, id)| (*id, index))
+            .collect();
+        let mut overlay = Overlay {
+            node_annotations: self
+                .in_degree
+                .iter()
+                .map(|(id, degree)| match placed.get(id) {
+                    Some(index) => (*id, format!("#{}", index)),
+                    None => (*id, format!("in={}", degree)),
+                })
+                .collect(),
+            ..Overlay::default()
+        };
+        overlay.highlight_cycle(&self.cycle);
+        overlay
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn setup_dag() -> Graph {
+        let edges = vec![(5, 2), (5, 0), (4, 0), (4, 1), (2, 3), (3, 1)];
+        Graph::from_list_of_edges(edges, false)
+    }
+
+    #[test]
+    fn test_kahn_orders_dag() -> Result<(), String> {
+        let mut my_kahn = Kahn::new(setup_dag());
+
+        my_kahn.run();
+
+        assert_eq!(*my_kahn.runtime.order.items(), vec![4, 5, 0, 2, 3, 1]);
+        assert!(my_kahn.runtime.cycle.is_empty());
+        assert_eq!(my_kahn.get_history()[0].queue, VecDeque::from([4, 5]));
+        Ok(())
+    }
+
+    #[test]
+    fn test_kahn_reports_cycle() -> Result<(), String> {
+        let edges = vec![(0, 1), (1, 2), (2, 3), (3, 1), (3, 4)];
+        let mut my_kahn = Kahn::new(Graph::from_list_of_edges(edges, false));
+
+        my_kahn.run();
+
+        assert_eq!(*my_kahn.runtime.order.items(), vec![0]);
+        assert_eq!(my_kahn.runtime.cycle, vec![1, 2, 3]);
+        let overlay = my_kahn.runtime.overlay();
+        assert!(overlay.highlighted_edges.contains(&(3, 1)));
+        assert!(!overlay.highlighted_nodes.contains(&4));
+        Ok(())
+    }
+}
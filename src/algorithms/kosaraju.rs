@@ -0,0 +1,239 @@
+use crate::{
+    algorithms::algorithm::{Algorithm, SharedLog, Snapshot, record, record_last},
+    algorithms::components::condensation,
+    algorithms::inspect::{Inspect, Overlay, SecondaryView, Variable, VariableKind},
+    algorithms::kahn::successors,
+    graph::graph::Graph,
+};
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Runtime {
+    /// 1 while ordering nodes on the graph, 2 while collecting components on
+    /// the reversed graph.
+    pub pass: u8,
+    pub active_node: Option<u32>,
+    /// Nodes whose recursive call is still open.
+    pub stack: Vec<u32>,
+    /// Nodes by increasing finishing time in the first pass.
+    pub order: SharedLog<u32>,
+    pub component: HashMap<u32, usize>,
+    pub reversed: Option<Rc<SecondaryView>>,
+    pub condensation: Option<Rc<SecondaryView>>,
+}
+/// Kosaraju's strongly connected components: a DFS records finishing times,
+/// then DFS on the reversed graph, by decreasing finishing time, reaches
+/// exactly one component per tree.
+#[derive(Debug, PartialEq)]
+pub struct Kosaraju {
+    pub graph: Graph,
+    pub runtime: Runtime,
+    pub history: Vec<Runtime>,
+}
+
+impl Kosaraju {
+    pub fn new(graph: Graph) -> Self {
+        Kosaraju {
+            graph,
+            runtime: Runtime {
+                pass: 1,
+                active_node: None,
+                stack: Vec::new(),
+                order: SharedLog::new(),
+                component: HashMap::new(),
+                reversed: None,
+                condensation: None,
+            },
+            history: Vec::new(),
+        }
+    }
+    fn record_step(&mut self) {
+        record(&mut self.history, &self.runtime);
+    }
+    /// Explicit-stack DFS from `root` over `graph`, skipping `seen` nodes.
+    /// Calls `finish` on every node when its call returns.
+    fn visit<F>(&mut self, graph: &Graph, root: u32, seen: &mut HashSet<u32>, mut finish: F)
+    where
+        F: FnMut(&mut Runtime, u32),
+    {
+        seen.insert(root);
+        self.runtime.active_node = Some(root);
+        self.runtime.stack.push(root);
+        self.record_step();
+        let mut pending = vec![successors(graph, root).into_iter()];
+        while let Some(next) = pending.last_mut().map(|ends| ends.next()) {
+            match next {
+                Some(v) if seen.insert(v) => {
+                    self.runtime.active_node = Some(v);
+                    self.runtime.stack.push(v);
+                    self.record_step();
+                    pending.push(successors(graph, v).into_iter());
+                }
+                Some(_) => {}
+                None => {
+                    pending.pop();
+                    let u = self.runtime.stack.pop().expect("Open call");
+                    finish(&mut self.runtime, u);
+                    self.runtime.active_node = self.runtime.stack.last().copied();
+                    self.record_step();
+                }
+            }
+        }
+    }
+}
+impl Algorithm<Runtime> for Kosaraju {
+    fn get_snapshot(&self) -> Snapshot<Runtime> {
+        Snapshot {
+            data: self.runtime.clone(),
+            graph: self.graph.to_snapshot().unwrap(),
+        }
+    }
+    fn get_history(&self) -> &[Runtime] {
+        &self.history
+    }
+    fn run(&mut self) {
+        let graph = self.graph.clone();
+        let mut roots: Vec<u32> = graph.nodes.keys().copied().collect();
+        roots.sort_unstable();
+        let mut seen = HashSet::new();
+        for root in roots {
+            if !seen.contains(&root) {
+                self.visit(&graph, root, &mut seen, |runtime, u| runtime.order.push(u));
+            }
+        }
+
+        let reversed = graph.reversed();
+        self.runtime.pass = 2;
+        self.runtime.reversed = Some(Rc::new(SecondaryView {
+            title: String::from("reversed graph"),
+            graph: reversed.clone(),
+            overlay: Overlay::default(),
+        }));
+        self.record_step();
+        let mut seen = HashSet::new();
+        let order = self.runtime.order.items().to_vec();
+        let mut next_id = 0;
+        for root in order.into_iter().rev() {
+            if !seen.contains(&root) {
+                let id = next_id;
+                next_id += 1;
+                self.visit(&reversed, root, &mut seen, |runtime, u| {
+                    runtime.component.insert(u, id);
+                });
+            }
+        }
+        let view = condensation(&self.graph, &self.runtime.component);
+        self.runtime.condensation = Some(Rc::new(view));
+        record_last(&mut self.history, &self.runtime);
+    }
+}
+impl Inspect for Runtime {
+    fn variables(&self) -> Vec<Variable> {
+        let active = self
+            .active_node
+            .map_or(String::from("-"), |id| id.to_string());
+        vec![
+            Variable::scalar("pass", self.pass),
+            Variable::scalar("active_node", active),
+            Variable::list("stack", VariableKind::Stack, self.stack.iter()),
+            Variable::list("order", VariableKind::Array, self.order.items().iter()),
+            Variable::map("component", &self.component, |id| id.to_string()),
+        ]
+    }
+    fn overlay(&self) -> Overlay {
+        // Pass 2 walks the reversed graph, so its progress is drawn there.
+        let secondary = match (&self.condensation, &self.reversed) {
+            (Some(view), _) => Some(Rc::clone(view)),
+            (None, Some(reversed)) => Some(Rc::new(SecondaryView {
+                title: reversed.title.clone(),
+                graph: reversed.graph.clone(),
+                overlay: Overlay {
+                    highlighted_nodes: self.stack.iter().copied().collect(),
+                    highlighted_edges: self
+                        .stack
+                        .windows(2)
+                        .map(|pair| (pair[0], pair[1]))
+                        .collect(),
+                    node_annotations: self
+                        .active_node
+                        .map(|id| (id, String::from("active")))
+                        .into_iter()
+                        .collect(),
+                    node_colors: self.component.clone(),
+                    ..Overlay::default()
+                },
+            })),
+            (None, None) => None,
+        };
+        let finished: HashMap<u32, usize> = self
+            .order
+            .items()
+            .iter()
+            .enumerate()
+            .map(|(index, id)| (*id, index))
+            .collect();
+        Overlay {
+            node_annotations: finished
+                .iter()
+                .map(|(id, index)| (*id, format!("f={}", index)))
+                .collect(),
+            highlighted_nodes: self.stack.iter().copied().collect(),
+            node_colors: self.component.clone(),
+            secondary,
+            ..Overlay::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn setup_components() -> Graph {
+        // {0, 1, 2} -> {3, 4} -> {5}
+        let edges = vec![(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 3), (4, 5)];
+        Graph::from_list_of_edges(edges, false)
+    }
+
+    #[test]
+    fn test_kosaraju_finds_components() -> Result<(), String> {
+        let mut my_kosaraju = Kosaraju::new(setup_components());
+
+        my_kosaraju.run();
+
+        let component = &my_kosaraju.runtime.component;
+        assert_eq!(*my_kosaraju.runtime.order.items(), vec![5, 4, 3, 2, 1, 0]);
+        assert_eq!(component[&0], 0, "Source components come first");
+        assert_eq!(component[&0], component[&1]);
+        assert_eq!(component[&3], component[&4]);
+        assert_eq!(component[&5], 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_kosaraju_second_pass_shows_reversed_graph() -> Result<(), String> {
+        let mut my_kosaraju = Kosaraju::new(setup_components());
+
+        my_kosaraju.run();
+
+        let history = my_kosaraju.get_history();
+        let second = history.iter().find(|step| step.pass == 2).unwrap();
+        let view = second.overlay().secondary.expect("Reversed view");
+        assert_eq!(view.title, "reversed graph");
+        assert!(view.graph.get_edge(5, 4).is_some());
+        let walking = history
+            .iter()
+            .find(|step| step.pass == 2 && step.stack.len() > 1)
+            .unwrap();
+        let view = walking.overlay().secondary.expect("Reversed view");
+        assert_eq!(view.overlay.highlighted_nodes.len(), walking.stack.len());
+        assert!(
+            view.overlay
+                .highlighted_edges
+                .contains(&(walking.stack[0], walking.stack[1]))
+        );
+        let last = history.last().unwrap().overlay().secondary.unwrap();
+        assert_eq!(last.title, "condensation");
+        Ok(())
+    }
+}
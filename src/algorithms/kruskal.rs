@@ -0,0 +1,319 @@
+use crate::{
+    algorithms::algorithm::{
+        Algorithm, MAX_RECORDED_STEPS, SharedLog, Snapshot, record, record_last,
+    },
+    algorithms::inspect::{Inspect, Overlay, SecondaryView, Variable, VariableKind},
+    graph::graph::Graph,
+    layouts::hierarchical::HierarchicalLayout,
+    layouts::layout::Layout,
+};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Edges of the graph taken as undirected, as `(u, v, weight)` with `u < v`.
+/// Opposite directed edges collapse into the lighter one; self-loops are dropped.
+pub fn undirected_edges(graph: &Graph) -> Vec<(u32, u32, u32)> {
+    let mut lightest: HashMap<(u32, u32), u32> = HashMap::new();
+    for node in graph.nodes.values() {
+        for edge in node.runtime.edges.values() {
+            let (start, end) = (edge.runtime.node_start, edge.runtime.node_end);
+            if start == end || !graph.nodes.contains_key(&end) {
+                continue;
+            }
+            let weight = lightest
+                .entry((start.min(end), start.max(end)))
+                .or_insert(edge.runtime.weight);
+            *weight = (*weight).min(edge.runtime.weight);
+        }
+    }
+    let mut edges: Vec<(u32, u32, u32)> = lightest
+        .into_iter()
+        .map(|((u, v), weight)| (u, v, weight))
+        .collect();
+    edges.sort_unstable();
+    edges
+}
+
+/// Union-find forest with union by rank and path compression.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct DisjointSet {
+    pub parent: HashMap<u32, u32>,
+    pub rank: HashMap<u32, u32>,
+}
+
+impl DisjointSet {
+    pub fn new(ids: impl IntoIterator<Item = u32>) -> Self {
+        let mut set = DisjointSet::default();
+        for id in ids {
+            set.parent.insert(id, id);
+            set.rank.insert(id, 0);
+        }
+        set
+    }
+    /// Root of the tree holding `id`, without touching the forest.
+    pub fn root(&self, mut id: u32) -> u32 {
+        while self.parent[&id] != id {
+            id = self.parent[&id];
+        }
+        id
+    }
+    /// Root of the tree holding `id`; every node on the way is relinked to it.
+    pub fn find(&mut self, id: u32) -> u32 {
+        let root = self.root(id);
+        let mut node = id;
+        while node != root {
+            node = self.parent.insert(node, root).expect("Known node");
+        }
+        root
+    }
+    /// Merges the trees of `u` and `v`. Returns `false` if they were one tree.
+    pub fn union(&mut self, u: u32, v: u32) -> bool {
+        let (u, v) = (self.find(u), self.find(v));
+        if u == v {
+            return false;
+        }
+        let (low, high) = match self.rank[&u] < self.rank[&v] {
+            true => (u, v),
+            false => (v, u),
+        };
+        self.parent.insert(low, high);
+        if self.rank[&low] == self.rank[&high] {
+            *self.rank.get_mut(&high).expect("Known node") += 1;
+        }
+        true
+    }
+    /// The forest as a graph with an edge from every node to its parent,
+    /// laid out with roots on top.
+    pub fn to_view(&self) -> SecondaryView {
+        let mut downward = Graph::new();
+        for id in self.parent.keys() {
+            downward.add_new_node_to_graph(*id, 0.0, 0.0);
+        }
+        for (id, parent) in self.parent.iter() {
+            if id != parent {
+                downward.add_directed_edge(*parent, *id);
+            }
+        }
+        HierarchicalLayout::default().apply(&mut downward);
+        let overlay = Overlay {
+            node_annotations: self
+                .rank
+                .iter()
+                .map(|(id, rank)| (*id, format!("r={}", rank)))
+                .collect(),
+            ..Overlay::default()
+        };
+        SecondaryView {
+            title: String::from("union-find forest"),
+            graph: downward.reversed(),
+            overlay,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Runtime {
+    /// Edges by increasing weight, as `(u, v, weight)`.
+    pub edges: Rc<[(u32, u32, u32)]>,
+    /// Index of the edge being decided.
+    pub cursor: usize,
+    pub accepted: SharedLog<(u32, u32)>,
+    pub rejected: SharedLog<(u32, u32)>,
+    pub sets: DisjointSet,
+    pub total: u64,
+    /// `sets` drawn as a forest, built once per recorded step.
+    pub forest: Option<Rc<SecondaryView>>,
+}
+/// Kruskal's minimum spanning forest: takes edges by increasing weight and
+/// keeps those joining two different union-find trees.
+#[derive(Debug, PartialEq)]
+pub struct Kruskal {
+    pub graph: Graph,
+    pub runtime: Runtime,
+    pub history: Vec<Runtime>,
+}
+
+impl Kruskal {
+    pub fn new(graph: Graph) -> Self {
+        let mut edges = undirected_edges(&graph);
+        edges.sort_by_key(|(u, v, weight)| (*weight, *u, *v));
+        let sets = DisjointSet::new(graph.nodes.keys().copied());
+        Kruskal {
+            graph,
+            runtime: Runtime {
+                edges: Rc::from(edges),
+                cursor: 0,
+                accepted: SharedLog::new(),
+                rejected: SharedLog::new(),
+                sets,
+                total: 0,
+                forest: None,
+            },
+            history: Vec::new(),
+        }
+    }
+    fn sync_forest(&mut self) {
+        self.runtime.forest = Some(Rc::new(self.runtime.sets.to_view()));
+    }
+    fn record_step(&mut self) {
+        if self.history.len() < MAX_RECORDED_STEPS {
+            self.sync_forest();
+            record(&mut self.history, &self.runtime);
+        }
+    }
+}
+impl Algorithm<Runtime> for Kruskal {
+    fn get_snapshot(&self) -> Snapshot<Runtime> {
+        Snapshot {
+            data: self.runtime.clone(),
+            graph: self.graph.to_snapshot().unwrap(),
+        }
+    }
+    fn get_history(&self) -> &[Runtime] {
+        &self.history
+    }
+    fn run(&mut self) {
+        self.record_step();
+        let tree_size = self.graph.nodes.len().saturating_sub(1);
+        while let Some((u, v, weight)) = self.runtime.edges.get(self.runtime.cursor).copied() {
+            if self.runtime.accepted.len() == tree_size {
+                break;
+            }
+            if self.runtime.sets.union(u, v) {
+                self.runtime.accepted.push((u, v));
+                self.runtime.total += weight as u64;
+            } else {
+                self.runtime.rejected.push((u, v));
+            }
+            self.runtime.cursor += 1;
+            self.record_step();
+        }
+        self.sync_forest();
+        record_last(&mut self.history, &self.runtime);
+    }
+}
+impl Inspect for Runtime {
+    fn variables(&self) -> Vec<Variable> {
+        let edges = self
+            .edges
+            .iter()
+            .enumerate()
+            .map(|(index, (u, v, weight))| {
+                let marker = if index == self.cursor { "> " } else { "" };
+                format!("{}{}-{} ({})", marker, u, v, weight)
+            });
+        let format = |edges: &[(u32, u32)]| -> Vec<String> {
+            edges.iter().map(|(u, v)| format!("{}-{}", u, v)).collect()
+        };
+        vec![
+            Variable::scalar("cursor", self.cursor),
+            Variable::list("edges", VariableKind::Array, edges),
+            Variable::list(
+                "accepted",
+                VariableKind::Array,
+                format(&self.accepted.items()),
+            ),
+            Variable::list(
+                "rejected",
+                VariableKind::Array,
+                format(&self.rejected.items()),
+            ),
+            Variable::map("parent", &self.sets.parent, |id| id.to_string()),
+            Variable::map("rank", &self.sets.rank, |rank| rank.to_string()),
+            Variable::scalar("total", self.total),
+        ]
+    }
+    fn overlay(&self) -> Overlay {
+        let mut roots: Vec<u32> = self
+            .sets
+            .parent
+            .iter()
+            .filter(|(id, parent)| id == parent)
+            .map(|(id, _)| *id)
+            .collect();
+        roots.sort_unstable();
+        let color: HashMap<u32, usize> = roots
+            .iter()
+            .enumerate()
+            .map(|(index, root)| (*root, index))
+            .collect();
+        // Accepted edges are `(min, max)`; highlight both orientations so a
+        // directed edge stored the other way round still shows up.
+        let mut overlay = Overlay {
+            highlighted_edges: self
+                .accepted
+                .items()
+                .iter()
+                .flat_map(|(u, v)| [(*u, *v), (*v, *u)])
+                .collect(),
+            node_colors: self
+                .sets
+                .parent
+                .keys()
+                .map(|id| (*id, color[&self.sets.root(*id)]))
+                .collect(),
+            secondary: self.forest.clone(),
+            ..Overlay::default()
+        };
+        // Once the tree is complete the remaining edges are never looked at.
+        let complete = self.accepted.len() + 1 >= self.sets.parent.len();
+        if let Some((u, v, _)) = self.edges.get(self.cursor)
+            && !complete
+        {
+            overlay.highlighted_nodes.extend([*u, *v]);
+        }
+        overlay
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn setup_weighted() -> Graph {
+        let edges = vec![
+            (0, 1, 4),
+            (0, 2, 1),
+            (1, 2, 2),
+            (1, 3, 5),
+            (2, 3, 8),
+            (3, 4, 3),
+        ];
+        Graph::from_list_of_weighted_edges(edges, true)
+    }
+
+    #[test]
+    fn test_kruskal_builds_minimum_tree() -> Result<(), String> {
+        let mut my_kruskal = Kruskal::new(setup_weighted());
+
+        my_kruskal.run();
+
+        assert_eq!(my_kruskal.runtime.total, 11);
+        assert_eq!(
+            *my_kruskal.runtime.accepted.items(),
+            vec![(0, 2), (1, 2), (3, 4), (1, 3)]
+        );
+        assert_eq!(*my_kruskal.runtime.rejected.items(), vec![(0, 1)]);
+        let overlay = my_kruskal.runtime.overlay();
+        assert!(overlay.is_edge_highlighted(3, 1, true));
+        assert!(overlay.node_colors.values().all(|color| *color == 0));
+        let forest = overlay.secondary.expect("Forest built with the step");
+        assert_eq!(forest.graph.nodes.len(), 5);
+        Ok(())
+    }
+
+    #[test]
+    fn test_disjoint_set_union_by_rank() -> Result<(), String> {
+        let mut sets = DisjointSet::new(0..4);
+
+        assert!(sets.union(0, 1));
+        assert!(sets.union(2, 3));
+        assert!(sets.union(1, 3));
+        assert!(!sets.union(0, 2));
+
+        let root = sets.find(3);
+        assert_eq!(sets.rank[&root], 2);
+        let view = sets.to_view();
+        assert_eq!(view.graph.get_edge(3, root).is_some(), 3 != root);
+        Ok(())
+    }
+}
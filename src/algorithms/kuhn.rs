@@ -0,0 +1,160 @@
+use crate::{
+    algorithms::algorithm::{Algorithm, Snapshot, record, record_last},
+    algorithms::inspect::{Inspect, Overlay, Variable, VariableKind},
+    algorithms::matching::{Bipartition, flip, format_matching},
+    graph::graph::Graph,
+};
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Runtime {
+    pub bipartition: Rc<Bipartition>,
+    /// Left node the current search started from.
+    pub active_node: Option<u32>,
+    /// Right nodes already tried by the current search.
+    pub visited: HashSet<u32>,
+    /// Alternating path being explored: left, right, left, ...
+    pub path: Vec<u32>,
+    /// Partner of every matched node, stored for both ends.
+    pub mate: HashMap<u32, u32>,
+    pub size: usize,
+}
+/// Kuhn's maximum bipartite matching: one augmenting path DFS from every left
+/// node, over unmatched edges out of the left and matched edges back.
+#[derive(Debug, PartialEq)]
+pub struct Kuhn {
+    pub graph: Graph,
+    pub runtime: Runtime,
+    pub history: Vec<Runtime>,
+}
+
+impl Kuhn {
+    /// `None` when the graph is not bipartite.
+    pub fn new(graph: Graph) -> Option<Self> {
+        let bipartition = Bipartition::new(&graph)?;
+        Some(Kuhn {
+            graph,
+            runtime: Runtime {
+                bipartition: Rc::new(bipartition),
+                active_node: None,
+                visited: HashSet::new(),
+                path: Vec::new(),
+                mate: HashMap::new(),
+                size: 0,
+            },
+            history: Vec::new(),
+        })
+    }
+    fn record_step(&mut self) {
+        record(&mut self.history, &self.runtime);
+    }
+    /// Iterative `try_kuhn(root)`. On success `path` ends at a free right node.
+    fn augment_from(&mut self, root: u32) -> bool {
+        let bipartition = Rc::clone(&self.runtime.bipartition);
+        let mut next: Vec<usize> = vec![0];
+        self.runtime.path = vec![root];
+        self.runtime.visited.clear();
+        while let Some(index) = next.last_mut() {
+            let u = self.runtime.path[self.runtime.path.len() - 1];
+            let Some(v) = bipartition.neighbors(u).get(*index).copied() else {
+                // Dead end: drop `u` and the right node that led to it.
+                next.pop();
+                self.runtime.path.pop();
+                self.runtime.path.pop();
+                continue;
+            };
+            *index += 1;
+            if !self.runtime.visited.insert(v) {
+                continue;
+            }
+            self.runtime.path.push(v);
+            match self.runtime.mate.get(&v) {
+                None => return true,
+                Some(w) => {
+                    self.runtime.path.push(*w);
+                    next.push(0);
+                    self.record_step();
+                }
+            }
+        }
+        false
+    }
+}
+impl Algorithm<Runtime> for Kuhn {
+    fn get_snapshot(&self) -> Snapshot<Runtime> {
+        Snapshot {
+            data: self.runtime.clone(),
+            graph: self.graph.to_snapshot().unwrap(),
+        }
+    }
+    fn get_history(&self) -> &[Runtime] {
+        &self.history
+    }
+    fn run(&mut self) {
+        let left = self.runtime.bipartition.left.clone();
+        for root in left {
+            self.runtime.active_node = Some(root);
+            if self.augment_from(root) {
+                self.record_step();
+                flip(&mut self.runtime.mate, &self.runtime.path);
+                self.runtime.size += 1;
+            }
+            self.runtime.path.clear();
+            self.record_step();
+        }
+        self.runtime.active_node = None;
+        self.runtime.visited.clear();
+        record_last(&mut self.history, &self.runtime);
+    }
+}
+impl Inspect for Runtime {
+    fn variables(&self) -> Vec<Variable> {
+        let active = self
+            .active_node
+            .map_or(String::from("-"), |id| id.to_string());
+        let mut visited: Vec<u32> = self.visited.iter().copied().collect();
+        visited.sort_unstable();
+        vec![
+            Variable::scalar("active_node", active),
+            Variable::list("visited", VariableKind::Array, visited.iter()),
+            Variable::list("path", VariableKind::Stack, self.path.iter()),
+            Variable::list(
+                "matching",
+                VariableKind::Array,
+                format_matching(&self.bipartition, &self.mate),
+            ),
+            Variable::scalar("size", self.size),
+        ]
+    }
+    fn overlay(&self) -> Overlay {
+        self.bipartition.overlay(&self.mate, &self.path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kuhn_finds_maximum_matching() -> Result<(), String> {
+        // Greedy would match 0-3 and leave 1 alone; the path 1-3-0-4 fixes it.
+        let edges = vec![(0, 3), (0, 4), (1, 3), (2, 4), (2, 5)];
+        let mut my_matching =
+            Kuhn::new(Graph::from_list_of_edges(edges, true)).ok_or("Bipartite")?;
+
+        my_matching.run();
+
+        assert_eq!(my_matching.runtime.size, 3);
+        let pairs = format_matching(&my_matching.runtime.bipartition, &my_matching.runtime.mate);
+        assert_eq!(pairs, vec!["0-4", "1-3", "2-5"]);
+        let paths: Vec<&Vec<u32>> = my_matching
+            .get_history()
+            .iter()
+            .map(|step| &step.path)
+            .filter(|path| path.len() == 4)
+            .collect();
+        assert_eq!(paths[paths.len() - 1], &vec![1, 3, 0, 4]);
+        Ok(())
+    }
+}
@@ -0,0 +1,119 @@
+use crate::{algorithms::inspect::Overlay, graph::graph::Graph, layouts::tree::TreeLayout};
+use std::collections::{HashMap, HashSet};
+
+/// The BFS spanning tree of the component holding `root`, edge direction
+/// ignored. Edges outside the tree are never used by LCA queries.
+#[derive(Debug, PartialEq, Clone)]
+pub struct RootedTree {
+    pub root: u32,
+    pub parent: HashMap<u32, u32>,
+    pub depth: HashMap<u32, u32>,
+    /// Children of every node, sorted by id.
+    pub children: HashMap<u32, Vec<u32>>,
+}
+
+impl RootedTree {
+    pub fn new(graph: &Graph, root: u32) -> Option<Self> {
+        if !graph.nodes.contains_key(&root) {
+            return None;
+        }
+        let (children, _) = TreeLayout::new(root).spanning_forest(graph);
+        let mut tree = RootedTree {
+            root,
+            parent: HashMap::new(),
+            depth: HashMap::from([(root, 0)]),
+            children: HashMap::new(),
+        };
+        let mut stack = vec![root];
+        while let Some(u) = stack.pop() {
+            let mut below = children.get(&u).cloned().unwrap_or_default();
+            below.sort_unstable();
+            for v in below.iter() {
+                tree.parent.insert(*v, u);
+                tree.depth.insert(*v, tree.depth[&u] + 1);
+                stack.push(*v);
+            }
+            tree.children.insert(u, below);
+        }
+        Some(tree)
+    }
+    pub fn contains(&self, id: u32) -> bool {
+        self.depth.contains_key(&id)
+    }
+    /// Tree edges from `node` up to its ancestor `ancestor`, in both
+    /// orientations so the overlay matches however the edge is stored.
+    pub fn path_up(&self, mut node: u32, ancestor: u32) -> Vec<(u32, u32)> {
+        let mut edges = Vec::new();
+        while node != ancestor {
+            let Some(parent) = self.parent.get(&node).copied() else {
+                break;
+            };
+            edges.extend([(node, parent), (parent, node)]);
+            node = parent;
+        }
+        edges
+    }
+}
+
+/// A pending or answered query `u`, `v`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Query {
+    pub u: u32,
+    pub v: u32,
+    /// Where each side of the query currently stands.
+    pub at: (u32, u32),
+    /// Edges covered by the last jump.
+    pub jump: Vec<(u32, u32)>,
+    pub lca: Option<u32>,
+}
+
+impl Query {
+    pub fn new(u: u32, v: u32) -> Self {
+        Query {
+            u,
+            v,
+            at: (u, v),
+            jump: Vec::new(),
+            lca: None,
+        }
+    }
+    /// The query nodes in the first two palette colors and the answer
+    /// highlighted, on top of `overlay`.
+    pub fn decorate(&self, overlay: &mut Overlay) {
+        overlay.node_colors = HashMap::from([(self.u, 0), (self.v, 1)]);
+        overlay.highlighted_nodes.extend([self.at.0, self.at.1]);
+        overlay.marked_edges = self.jump.iter().copied().collect();
+        if let Some(lca) = self.lca {
+            overlay.highlighted_nodes = HashSet::from([lca]);
+            overlay.node_colors.insert(lca, 2);
+            overlay.node_annotations.insert(lca, String::from("LCA"));
+        }
+    }
+    pub fn describe(&self) -> String {
+        match self.lca {
+            Some(lca) => format!("lca({}, {}) = {}", self.u, self.v, lca),
+            None => format!(
+                "lca({}, {}) at {}, {}",
+                self.u, self.v, self.at.0, self.at.1
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rooted_tree_ignores_direction() -> Result<(), String> {
+        let edges = vec![(1, 0), (1, 2), (3, 2), (2, 4)];
+        let graph = Graph::from_list_of_edges(edges, false);
+
+        let tree = RootedTree::new(&graph, 2).ok_or("Root exists")?;
+
+        assert_eq!(tree.children[&2], vec![1, 3, 4]);
+        assert_eq!((tree.parent[&0], tree.depth[&0]), (1, 2));
+        assert_eq!(tree.path_up(0, 2).len(), 4);
+        Ok(())
+    }
+}
@@ -0,0 +1,284 @@
+use crate::{
+    algorithms::algorithm::{Algorithm, SharedLog, Snapshot, make_room, record, record_last},
+    algorithms::inspect::{Inspect, Overlay, Variable, VariableKind},
+    algorithms::lca::{Query, RootedTree},
+    graph::graph::Graph,
+};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Runtime {
+    pub tree: Rc<RootedTree>,
+    /// `up[k][v]` is the ancestor `2^k` levels above `v`, or the root. Rows
+    /// are shared between steps once filled.
+    pub up: Vec<Rc<HashMap<u32, u32>>>,
+    /// Table cell filled by the last step.
+    pub cell: Option<(usize, u32)>,
+    pub query: Option<Query>,
+    pub jumps: SharedLog<String>,
+}
+/// Lowest common ancestor by binary lifting: fills `up[k][v]` row by row,
+/// then lifts the deeper query node to the same depth and both nodes together
+/// by decreasing powers of two.
+#[derive(Debug, PartialEq)]
+pub struct BinaryLifting {
+    pub graph: Graph,
+    pub query: Option<(u32, u32)>,
+    pub runtime: Runtime,
+    pub history: Vec<Runtime>,
+}
+
+impl BinaryLifting {
+    /// `None` when the root is missing or a query node is outside its tree.
+    pub fn new(graph: Graph, root: u32, query: Option<(u32, u32)>) -> Option<Self> {
+        let tree = RootedTree::new(&graph, root)?;
+        if query.is_some_and(|(u, v)| !tree.contains(u) || !tree.contains(v)) {
+            return None;
+        }
+        Some(BinaryLifting {
+            graph,
+            query,
+            runtime: Runtime {
+                tree: Rc::new(tree),
+                up: Vec::new(),
+                cell: None,
+                query: None,
+                jumps: SharedLog::new(),
+            },
+            history: Vec::new(),
+        })
+    }
+    fn record_step(&mut self) {
+        record(&mut self.history, &self.runtime);
+    }
+    fn fill_table(&mut self) {
+        let tree = Rc::clone(&self.runtime.tree);
+        let mut order: Vec<u32> = tree.depth.keys().copied().collect();
+        order.sort_unstable_by_key(|id| (tree.depth[id], *id));
+        let height = order.last().map_or(0, |id| tree.depth[id]);
+        let rows = (u32::BITS - height.leading_zeros()).max(1) as usize;
+        let first = order
+            .iter()
+            .map(|id| (*id, tree.parent.get(id).copied().unwrap_or(tree.root)))
+            .collect();
+        self.runtime.up.push(Rc::new(first));
+        self.record_step();
+        for k in 1..rows {
+            self.runtime.up.push(Rc::default());
+            for v in order.iter() {
+                let half = self.runtime.up[k - 1][v];
+                let up = self.runtime.up[k - 1][&half];
+                Rc::make_mut(&mut self.runtime.up[k]).insert(*v, up);
+                self.runtime.cell = Some((k, *v));
+                self.record_step();
+            }
+        }
+        self.runtime.cell = None;
+    }
+    /// Moves one side of the query `2^k` levels up.
+    fn jump(&mut self, second: bool, k: usize) {
+        let query = self.runtime.query.as_mut().expect("Query started");
+        let from = match second {
+            false => query.at.0,
+            true => query.at.1,
+        };
+        let to = self.runtime.up[k][&from];
+        match second {
+            false => query.at.0 = to,
+            true => query.at.1 = to,
+        }
+        query.jump.extend(self.runtime.tree.path_up(from, to));
+        self.runtime
+            .jumps
+            .push(format!("{} -> {} (2^{})", from, to, k));
+    }
+    fn answer(&mut self, (u, v): (u32, u32)) {
+        let tree = Rc::clone(&self.runtime.tree);
+        self.runtime.query = Some(Query::new(u, v));
+        self.record_step();
+        // Lift the deeper side until both stand on the same level.
+        let deeper_is_v = tree.depth[&v] > tree.depth[&u];
+        let diff = tree.depth[&u].abs_diff(tree.depth[&v]);
+        for k in 0..self.runtime.up.len() {
+            if diff >> k & 1 == 1 {
+                self.runtime.query.as_mut().unwrap().jump.clear();
+                self.jump(deeper_is_v, k);
+                self.record_step();
+            }
+        }
+        for k in (0..self.runtime.up.len()).rev() {
+            let at = self.runtime.query.as_ref().unwrap().at;
+            if at.0 != at.1 && self.runtime.up[k][&at.0] != self.runtime.up[k][&at.1] {
+                self.runtime.query.as_mut().unwrap().jump.clear();
+                self.jump(false, k);
+                self.jump(true, k);
+                self.record_step();
+            }
+        }
+        let query = self.runtime.query.as_mut().unwrap();
+        query.jump.clear();
+        query.lca = Some(match query.at {
+            (a, b) if a == b => a,
+            (a, _) => self.runtime.up[0][&a],
+        });
+        self.record_step();
+    }
+    /// Answers another query against the table built by `run`, appending
+    /// only the query steps to the history. Returns the index of the first of
+    /// them, or `None` when a node is outside the tree.
+    pub fn ask(&mut self, (u, v): (u32, u32)) -> Option<usize> {
+        if !self.runtime.tree.contains(u) || !self.runtime.tree.contains(v) {
+            return None;
+        }
+        if self.runtime.up.is_empty() {
+            self.fill_table();
+        }
+        make_room(&mut self.history);
+        let first = self.history.len();
+        self.query = Some((u, v));
+        self.runtime.jumps = SharedLog::new();
+        self.answer((u, v));
+        record_last(&mut self.history, &self.runtime);
+        Some(first)
+    }
+}
+impl Algorithm<Runtime> for BinaryLifting {
+    fn get_snapshot(&self) -> Snapshot<Runtime> {
+        Snapshot {
+            data: self.runtime.clone(),
+            graph: self.graph.to_snapshot().unwrap(),
+        }
+    }
+    fn get_history(&self) -> &[Runtime] {
+        &self.history
+    }
+    fn run(&mut self) {
+        self.fill_table();
+        if let Some(query) = self.query {
+            self.answer(query);
+        }
+        record_last(&mut self.history, &self.runtime);
+    }
+}
+impl Inspect for Runtime {
+    fn variables(&self) -> Vec<Variable> {
+        let query = self
+            .query
+            .as_ref()
+            .map_or(String::from("-"), |query| query.describe());
+        let mut variables = vec![Variable::map("depth", &self.tree.depth, |depth| {
+            depth.to_string()
+        })];
+        for (k, row) in self.up.iter().enumerate() {
+            let name = format!("up[{}]", k);
+            variables.push(Variable::map(&name, row, |id| id.to_string()));
+        }
+        variables.push(Variable::scalar("query", query));
+        variables.push(Variable::list(
+            "jumps",
+            VariableKind::Array,
+            self.jumps.items().iter(),
+        ));
+        variables
+    }
+    fn overlay(&self) -> Overlay {
+        let mut overlay = Overlay {
+            node_annotations: self
+                .tree
+                .depth
+                .iter()
+                .map(|(id, depth)| (*id, format!("d={}", depth)))
+                .collect(),
+            ..Overlay::default()
+        };
+        if let Some((k, v)) = self.cell {
+            let up = self.up[k][&v];
+            overlay.highlighted_nodes.extend([v, up]);
+            overlay.marked_edges = self.tree.path_up(v, up).into_iter().collect();
+            overlay
+                .node_annotations
+                .insert(v, format!("up[{}]={}", k, up));
+        }
+        if let Some(query) = self.query.as_ref() {
+            query.decorate(&mut overlay);
+        }
+        overlay
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn setup_tree() -> Graph {
+        let edges = vec![
+            (0, 1),
+            (0, 2),
+            (1, 3),
+            (1, 4),
+            (3, 7),
+            (3, 8),
+            (4, 9),
+            (4, 10),
+            (2, 5),
+            (2, 6),
+            (5, 11),
+            (6, 12),
+            (6, 13),
+            (6, 14),
+        ];
+        Graph::from_list_of_edges(edges, false)
+    }
+    fn lca(u: u32, v: u32) -> Result<u32, String> {
+        let mut my_lca = BinaryLifting::new(setup_tree(), 0, Some((u, v))).ok_or("Valid query")?;
+        my_lca.run();
+        my_lca
+            .runtime
+            .query
+            .and_then(|query| query.lca)
+            .ok_or(String::from("Answered"))
+    }
+
+    #[test]
+    fn test_binary_lifting_table() -> Result<(), String> {
+        let mut my_lca = BinaryLifting::new(setup_tree(), 0, None).ok_or("Root exists")?;
+
+        my_lca.run();
+
+        let up = &my_lca.runtime.up;
+        assert_eq!(up.len(), 2);
+        assert_eq!((up[0][&7], up[1][&7], up[1][&1]), (3, 1, 0));
+        assert!(my_lca.runtime.query.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_binary_lifting_queries() -> Result<(), String> {
+        assert_eq!(lca(7, 10)?, 1);
+        assert_eq!(lca(11, 14)?, 2);
+        assert_eq!(lca(9, 4)?, 4);
+        assert_eq!(lca(8, 8)?, 8);
+        assert_eq!(lca(12, 7)?, 0);
+        assert!(BinaryLifting::new(setup_tree(), 0, Some((1, 99))).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_binary_lifting_asks_against_built_table() -> Result<(), String> {
+        let mut my_lca = BinaryLifting::new(setup_tree(), 0, None).ok_or("Root exists")?;
+        my_lca.run();
+        let built = my_lca.get_history().len();
+
+        let first = my_lca.ask((7, 10)).ok_or("Nodes in tree")?;
+
+        assert_eq!(first, built, "Preprocessing is not rerun");
+        let history = my_lca.get_history();
+        assert!(history[first..].iter().all(|step| step.cell.is_none()));
+        let answer = history.last().unwrap().query.as_ref().and_then(|q| q.lca);
+        assert_eq!(answer, Some(1));
+        let first = my_lca.ask((12, 7)).ok_or("Nodes in tree")?;
+        assert_eq!(my_lca.get_history()[first].jumps.len(), 0);
+        assert!(my_lca.ask((7, 99)).is_none());
+        Ok(())
+    }
+}
@@ -0,0 +1,243 @@
+use crate::{
+    algorithms::algorithm::{Algorithm, SharedLog, Snapshot, make_room, record, record_last},
+    algorithms::inspect::{Inspect, Overlay, Variable, VariableKind},
+    algorithms::lca::{Query, RootedTree},
+    graph::graph::Graph,
+};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Runtime {
+    pub tree: Rc<RootedTree>,
+    /// Nodes in the order a DFS enters and returns to them.
+    pub tour: SharedLog<u32>,
+    /// First index of every node in `tour`.
+    pub first: HashMap<u32, usize>,
+    /// `sparse[k][i]` is the shallowest node of `tour[i..i + 2^k]`.
+    pub sparse: Vec<Rc<[u32]>>,
+    /// Tour range the query reduces to.
+    pub range: Option<(usize, usize)>,
+    pub query: Option<Query>,
+}
+/// Lowest common ancestor as a range minimum: the LCA of `u` and `v` is the
+/// shallowest node of the Euler tour between their first visits, answered
+/// with two overlapping blocks of a sparse table.
+#[derive(Debug, PartialEq)]
+pub struct EulerTourLca {
+    pub graph: Graph,
+    pub query: Option<(u32, u32)>,
+    pub runtime: Runtime,
+    pub history: Vec<Runtime>,
+}
+
+impl Runtime {
+    fn shallower(&self, a: u32, b: u32) -> u32 {
+        match self.tree.depth[&a] <= self.tree.depth[&b] {
+            true => a,
+            false => b,
+        }
+    }
+}
+
+impl EulerTourLca {
+    /// `None` when the root is missing or a query node is outside its tree.
+    pub fn new(graph: Graph, root: u32, query: Option<(u32, u32)>) -> Option<Self> {
+        let tree = RootedTree::new(&graph, root)?;
+        if query.is_some_and(|(u, v)| !tree.contains(u) || !tree.contains(v)) {
+            return None;
+        }
+        Some(EulerTourLca {
+            graph,
+            query,
+            runtime: Runtime {
+                tree: Rc::new(tree),
+                tour: SharedLog::new(),
+                first: HashMap::new(),
+                sparse: Vec::new(),
+                range: None,
+                query: None,
+            },
+            history: Vec::new(),
+        })
+    }
+    fn record_step(&mut self) {
+        record(&mut self.history, &self.runtime);
+    }
+    fn visit(&mut self, node: u32) {
+        let index = self.runtime.tour.len();
+        self.runtime.first.entry(node).or_insert(index);
+        self.runtime.tour.push(node);
+        self.record_step();
+    }
+    fn build_tour(&mut self) {
+        let tree = Rc::clone(&self.runtime.tree);
+        let mut stack = vec![(tree.root, 0)];
+        self.visit(tree.root);
+        while let Some((u, next)) = stack.last_mut() {
+            let u = *u;
+            match tree.children[&u].get(*next) {
+                Some(child) => {
+                    *next += 1;
+                    stack.push((*child, 0));
+                    self.visit(*child);
+                }
+                None => {
+                    stack.pop();
+                    if let Some((parent, _)) = stack.last() {
+                        self.visit(*parent);
+                    }
+                }
+            }
+        }
+    }
+    fn build_sparse(&mut self) {
+        let tour: Rc<[u32]> = Rc::from(&*self.runtime.tour.items());
+        self.runtime.sparse.push(tour);
+        self.record_step();
+        let mut width = 2;
+        while width <= self.runtime.tour.len() {
+            let below = &self.runtime.sparse[self.runtime.sparse.len() - 1];
+            let row: Rc<[u32]> = (0..=self.runtime.tour.len() - width)
+                .map(|i| self.runtime.shallower(below[i], below[i + width / 2]))
+                .collect();
+            self.runtime.sparse.push(row);
+            self.record_step();
+            width *= 2;
+        }
+    }
+    fn answer(&mut self, (u, v): (u32, u32)) {
+        let mut query = Query::new(u, v);
+        let (l, r) = (self.runtime.first[&u], self.runtime.first[&v]);
+        let (l, r) = (l.min(r), l.max(r));
+        self.runtime.query = Some(query.clone());
+        self.runtime.range = Some((l, r));
+        self.record_step();
+        let k = (usize::BITS - 1 - (r - l + 1).leading_zeros()) as usize;
+        let row = &self.runtime.sparse[k];
+        let (a, b) = (row[l], row[r + 1 - (1 << k)]);
+        query.at = (a, b);
+        self.runtime.query = Some(query.clone());
+        self.record_step();
+        query.lca = Some(self.runtime.shallower(a, b));
+        self.runtime.query = Some(query);
+        self.record_step();
+    }
+    /// Answers another query against the tour and table built by `run`,
+    /// appending only the query steps to the history. Returns the index of
+    /// the first of them, or `None` when a node is outside the tree.
+    pub fn ask(&mut self, (u, v): (u32, u32)) -> Option<usize> {
+        if !self.runtime.tree.contains(u) || !self.runtime.tree.contains(v) {
+            return None;
+        }
+        if self.runtime.sparse.is_empty() {
+            self.build_tour();
+            self.build_sparse();
+        }
+        make_room(&mut self.history);
+        let first = self.history.len();
+        self.query = Some((u, v));
+        self.answer((u, v));
+        record_last(&mut self.history, &self.runtime);
+        Some(first)
+    }
+}
+impl Algorithm<Runtime> for EulerTourLca {
+    fn get_snapshot(&self) -> Snapshot<Runtime> {
+        Snapshot {
+            data: self.runtime.clone(),
+            graph: self.graph.to_snapshot().unwrap(),
+        }
+    }
+    fn get_history(&self) -> &[Runtime] {
+        &self.history
+    }
+    fn run(&mut self) {
+        self.build_tour();
+        self.build_sparse();
+        if let Some(query) = self.query {
+            self.answer(query);
+        }
+        record_last(&mut self.history, &self.runtime);
+    }
+}
+impl Inspect for Runtime {
+    fn variables(&self) -> Vec<Variable> {
+        let tour = self.tour.items();
+        let depths = tour.iter().map(|id| self.tree.depth[id]);
+        let range = self
+            .range
+            .map_or(String::from("-"), |(l, r)| format!("[{}, {}]", l, r));
+        let query = self
+            .query
+            .as_ref()
+            .map_or(String::from("-"), |query| query.describe());
+        let mut variables = vec![
+            Variable::list("tour", VariableKind::Array, tour.iter()),
+            Variable::list("depth", VariableKind::Array, depths),
+            Variable::map("first", &self.first, |index| index.to_string()),
+        ];
+        for (k, row) in self.sparse.iter().enumerate() {
+            let name = format!("sparse[{}]", k);
+            variables.push(Variable::list(&name, VariableKind::Array, row.iter()));
+        }
+        variables.push(Variable::scalar("range", range));
+        variables.push(Variable::scalar("query", query));
+        variables
+    }
+    fn overlay(&self) -> Overlay {
+        let mut overlay = Overlay {
+            node_annotations: self
+                .first
+                .iter()
+                .map(|(id, index)| (*id, format!("first={}", index)))
+                .collect(),
+            ..Overlay::default()
+        };
+        let tour = self.tour.items();
+        if let [.., previous, last] = tour[..]
+            && self.sparse.is_empty()
+        {
+            let (child, parent) = match self.tree.parent.get(&last) == Some(&previous) {
+                true => (last, previous),
+                false => (previous, last),
+            };
+            overlay.highlighted_nodes.insert(last);
+            overlay.marked_edges = self.tree.path_up(child, parent).into_iter().collect();
+        }
+        if let Some((l, r)) = self.range {
+            overlay.highlighted_nodes = tour[l..=r].iter().copied().collect();
+        }
+        if let Some(query) = self.query.as_ref() {
+            query.decorate(&mut overlay);
+        }
+        overlay
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn setup_tree() -> Graph {
+        let edges = vec![(0, 1), (0, 2), (1, 3), (1, 4), (2, 5), (4, 6)];
+        Graph::from_list_of_edges(edges, false)
+    }
+
+    #[test]
+    fn test_euler_tour_and_sparse_table() -> Result<(), String> {
+        let mut my_lca = EulerTourLca::new(setup_tree(), 0, Some((6, 3))).ok_or("Valid query")?;
+
+        my_lca.run();
+
+        let runtime = &my_lca.runtime;
+        assert_eq!(
+            *runtime.tour.items(),
+            vec![0, 1, 3, 1, 4, 6, 4, 1, 0, 2, 5, 2, 0]
+        );
+        assert_eq!(runtime.sparse.len(), 4);
+        assert_eq!(runtime.sparse[2][1], 1);
+        assert_eq!(runtime.range, Some((2, 5)));
+        assert_eq!(runtime.query.as_ref().and_then(|query| query.lca), Some(1));
+        Ok(())
+    }
+}
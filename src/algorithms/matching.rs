@@ -0,0 +1,130 @@
+use crate::{algorithms::inspect::Overlay, graph::graph::Graph, layouts::bipartite::two_coloring};
+use std::collections::HashMap;
+
+/// The two sides of a bipartite graph, edge direction ignored, with the
+/// adjacency augmenting paths need.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Bipartition {
+    /// Left side, sorted; augmenting paths start here.
+    pub left: Vec<u32>,
+    /// Right-side neighbors of every left node, sorted.
+    pub neighbors: HashMap<u32, Vec<u32>>,
+    /// 0 for left nodes, 1 for right nodes.
+    pub side: HashMap<u32, usize>,
+}
+
+impl Bipartition {
+    /// `None` when the graph is not bipartite.
+    pub fn new(graph: &Graph) -> Option<Self> {
+        let (side, proper) = two_coloring(graph);
+        if !proper {
+            return None;
+        }
+        let mut neighbors: HashMap<u32, Vec<u32>> = HashMap::new();
+        for node in graph.nodes.values() {
+            for end in node.runtime.edges.keys() {
+                let (u, v) = match side[&node.id] {
+                    false => (node.id, *end),
+                    true => (*end, node.id),
+                };
+                neighbors.entry(u).or_default().push(v);
+            }
+        }
+        for list in neighbors.values_mut() {
+            list.sort_unstable();
+            list.dedup();
+        }
+        let mut left: Vec<u32> = side
+            .iter()
+            .filter(|(_, right)| !**right)
+            .map(|(id, _)| *id)
+            .collect();
+        left.sort_unstable();
+        Some(Bipartition {
+            left,
+            neighbors,
+            side: side
+                .into_iter()
+                .map(|(id, right)| (id, right as usize))
+                .collect(),
+        })
+    }
+    pub fn neighbors(&self, u: u32) -> &[u32] {
+        self.neighbors.get(&u).map_or(&[], |list| list.as_slice())
+    }
+    /// Matched edges highlighted, the alternating `path` dashed and both sides
+    /// colored.
+    /// Edges go in both orientations, since direction is ignored and either
+    /// one may be the stored edge.
+    pub fn overlay(&self, mate: &HashMap<u32, u32>, path: &[u32]) -> Overlay {
+        Overlay {
+            highlighted_nodes: path.iter().copied().collect(),
+            highlighted_edges: mate.iter().map(|(u, v)| (*u, *v)).collect(),
+            marked_edges: path
+                .windows(2)
+                .flat_map(|pair| [(pair[0], pair[1]), (pair[1], pair[0])])
+                .collect(),
+            node_colors: self.side.clone(),
+            ..Overlay::default()
+        }
+    }
+}
+
+/// Flips the alternating `path` (left, right, left, ..., right): its odd
+/// edges become matched, which grows the matching by one.
+pub fn flip(mate: &mut HashMap<u32, u32>, path: &[u32]) {
+    for pair in path.chunks(2) {
+        mate.insert(pair[0], pair[1]);
+        mate.insert(pair[1], pair[0]);
+    }
+}
+
+/// Matched pairs as `left-right`, sorted.
+pub fn format_matching(bipartition: &Bipartition, mate: &HashMap<u32, u32>) -> Vec<String> {
+    let mut pairs: Vec<(u32, u32)> = mate
+        .iter()
+        .filter(|(u, _)| bipartition.side[u] == 0)
+        .map(|(u, v)| (*u, *v))
+        .collect();
+    pairs.sort_unstable();
+    pairs
+        .into_iter()
+        .map(|(u, v)| format!("{}-{}", u, v))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bipartition_ignores_direction() -> Result<(), String> {
+        let edges = vec![(0, 3), (4, 1), (1, 3)];
+        let graph = Graph::from_list_of_edges(edges, false);
+
+        let bipartition = Bipartition::new(&graph).ok_or("Bipartite")?;
+
+        assert_eq!(bipartition.left, vec![0, 1]);
+        assert_eq!(bipartition.neighbors(1), &[3, 4]);
+        let triangle = Graph::from_list_of_edges(vec![(0, 1), (1, 2), (2, 0)], true);
+        assert!(Bipartition::new(&triangle).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_bipartition_overlay_on_directed_graph() -> Result<(), String> {
+        let edges = vec![(0, 3), (4, 1), (1, 3)];
+        let graph = Graph::from_list_of_edges(edges, false);
+        let bipartition = Bipartition::new(&graph).ok_or("Bipartite")?;
+        let mate = HashMap::from([(1, 4), (4, 1)]);
+
+        let overlay = bipartition.overlay(&mate, &[0, 3, 1, 4]);
+
+        assert!(
+            overlay.is_edge_highlighted(4, 1, true),
+            "Stored right to left"
+        );
+        assert!(overlay.is_edge_marked(1, 3, true), "Back step of the path");
+        Ok(())
+    }
+}
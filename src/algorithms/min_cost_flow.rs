@@ -0,0 +1,405 @@
+use crate::{
+    algorithms::algorithm::{Algorithm, Snapshot, record, record_last},
+    algorithms::inspect::{Inspect, Overlay, SecondaryView, Variable, VariableKind},
+    edge::edge_model::Edge,
+    graph::graph::Graph,
+};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::rc::Rc;
+
+/// Distances from the source and the arc reaching every node.
+type Paths = (HashMap<u32, i64>, HashMap<u32, usize>);
+
+/// How the cheapest augmenting path is found.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PathSearch {
+    /// Bellman–Ford with a queue, directly on the possibly negative costs.
+    Spfa,
+    /// Dijkstra on costs reduced by node potentials, which keep them
+    /// non-negative. Negative input costs get initial potentials from SPFA.
+    Dijkstra,
+}
+
+/// One direction of a residual arc. Arcs come in pairs: `i ^ 1` is the
+/// reverse of `i`, with no capacity of its own and the opposite cost.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Arc {
+    pub from: u32,
+    pub to: u32,
+    pub capacity: i64,
+    pub cost: i64,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Runtime {
+    /// Flow per arc; the reverse arc holds the negated value.
+    pub flow: Vec<i64>,
+    pub potential: HashMap<u32, i64>,
+    /// Cost of the cheapest residual path from the source, from the last search.
+    pub distance: HashMap<u32, i64>,
+    /// Arcs of the current augmenting path.
+    pub path: Vec<usize>,
+    pub bottleneck: i64,
+    pub total_flow: i64,
+    pub total_cost: i64,
+    pub error: Option<String>,
+    arcs: Rc<Vec<Arc>>,
+    /// The input nodes without edges, for drawing the residual graph.
+    nodes: Rc<Graph>,
+}
+/// Min-cost max-flow by successive shortest paths: keeps augmenting along
+/// the cheapest residual path, with edge weights as capacities and edge
+/// costs as prices per unit.
+#[derive(Debug, PartialEq)]
+pub struct MinCostFlow {
+    pub graph: Graph,
+    pub source: u32,
+    pub sink: u32,
+    pub search: PathSearch,
+    pub runtime: Runtime,
+    pub history: Vec<Runtime>,
+    adjacency: HashMap<u32, Vec<usize>>,
+}
+
+impl Runtime {
+    fn residual(&self, arc: usize) -> i64 {
+        self.arcs[arc].capacity - self.flow[arc]
+    }
+    /// Original arcs, i.e. the even ones, as `(from, to)` with their label.
+    fn labels(&self) -> HashMap<(u32, u32), String> {
+        self.arcs
+            .iter()
+            .enumerate()
+            .step_by(2)
+            .map(|(index, arc)| {
+                let label = format!("{}/{} @{}", self.flow[index], arc.capacity, arc.cost);
+                ((arc.from, arc.to), label)
+            })
+            .collect()
+    }
+    fn path_nodes(&self) -> Vec<u32> {
+        let first = self.path.first().map(|arc| self.arcs[*arc].from);
+        first
+            .into_iter()
+            .chain(self.path.iter().map(|arc| self.arcs[*arc].to))
+            .collect()
+    }
+    /// Arcs with residual capacity, labeled `residual @cost`. Opposite
+    /// arcs between the same nodes share one edge and one label.
+    fn residual_view(&self) -> SecondaryView {
+        let mut graph = (*self.nodes).clone();
+        let mut labels: HashMap<(u32, u32), Vec<String>> = HashMap::new();
+        for (index, arc) in self.arcs.iter().enumerate() {
+            let residual = self.residual(index);
+            if residual > 0 {
+                graph.insert_edge(Edge::new(arc.from, arc.to, residual as u32));
+                labels
+                    .entry((arc.from, arc.to))
+                    .or_default()
+                    .push(format!("{} @{}", residual, arc.cost));
+            }
+        }
+        let nodes = self.path_nodes();
+        let overlay = Overlay {
+            edge_labels: labels
+                .into_iter()
+                .map(|(arc, labels)| (arc, labels.join(", ")))
+                .collect(),
+            highlighted_nodes: nodes.iter().copied().collect(),
+            highlighted_edges: nodes.windows(2).map(|pair| (pair[0], pair[1])).collect(),
+            ..Overlay::default()
+        };
+        SecondaryView {
+            title: String::from("residual graph"),
+            graph,
+            overlay,
+        }
+    }
+}
+
+impl MinCostFlow {
+    pub fn new(graph: Graph, source: u32, sink: u32, search: PathSearch) -> Option<Self> {
+        if source == sink || !graph.nodes.contains_key(&source) || !graph.nodes.contains_key(&sink)
+        {
+            return None;
+        }
+        let mut edges: Vec<&Edge> = graph
+            .nodes
+            .values()
+            .flat_map(|node| node.runtime.edges.values())
+            .filter(|edge| edge.runtime.node_start != edge.runtime.node_end)
+            .filter(|edge| graph.nodes.contains_key(&edge.runtime.node_end))
+            .collect();
+        edges.sort_by_key(|edge| (edge.runtime.node_start, edge.runtime.node_end));
+        let mut arcs = Vec::with_capacity(edges.len() * 2);
+        let mut adjacency: HashMap<u32, Vec<usize>> = HashMap::new();
+        for edge in edges {
+            let (from, to) = (edge.runtime.node_start, edge.runtime.node_end);
+            let cost = edge.runtime.cost as i64;
+            adjacency.entry(from).or_default().push(arcs.len());
+            arcs.push(Arc {
+                from,
+                to,
+                capacity: edge.runtime.weight as i64,
+                cost,
+            });
+            adjacency.entry(to).or_default().push(arcs.len());
+            arcs.push(Arc {
+                from: to,
+                to: from,
+                capacity: 0,
+                cost: -cost,
+            });
+        }
+        let mut nodes = graph.clone();
+        for node in nodes.nodes.values_mut() {
+            node.runtime.edges.clear();
+        }
+        Some(MinCostFlow {
+            runtime: Runtime {
+                flow: vec![0; arcs.len()],
+                potential: graph.nodes.keys().map(|id| (*id, 0)).collect(),
+                distance: HashMap::new(),
+                path: Vec::new(),
+                bottleneck: 0,
+                total_flow: 0,
+                total_cost: 0,
+                error: None,
+                arcs: Rc::new(arcs),
+                nodes: Rc::new(nodes),
+            },
+            graph,
+            source,
+            sink,
+            search,
+            history: Vec::new(),
+            adjacency,
+        })
+    }
+    fn record_step(&mut self) {
+        record(&mut self.history, &self.runtime);
+    }
+    fn arcs_from(&self, u: u32) -> &[usize] {
+        self.adjacency.get(&u).map_or(&[], |arcs| arcs.as_slice())
+    }
+    fn reduced_cost(&self, arc: usize) -> i64 {
+        let Arc { from, to, cost, .. } = self.runtime.arcs[arc];
+        cost + self.runtime.potential[&from] - self.runtime.potential[&to]
+    }
+    /// Cheapest residual paths from the source, by reduced cost. Fails on a
+    /// negative cycle.
+    fn spfa(&self) -> Result<Paths, String> {
+        let mut distance = HashMap::from([(self.source, 0)]);
+        let mut via = HashMap::new();
+        let mut relaxations: HashMap<u32, usize> = HashMap::new();
+        let mut queue = VecDeque::from([self.source]);
+        let mut queued = HashMap::from([(self.source, true)]);
+        while let Some(u) = queue.pop_front() {
+            queued.insert(u, false);
+            for arc in self.arcs_from(u) {
+                let to = self.runtime.arcs[*arc].to;
+                let candidate = distance[&u] + self.reduced_cost(*arc);
+                if self.runtime.residual(*arc) == 0
+                    || distance.get(&to).is_some_and(|known| *known <= candidate)
+                {
+                    continue;
+                }
+                distance.insert(to, candidate);
+                via.insert(to, *arc);
+                let count = relaxations.entry(to).or_insert(0);
+                *count += 1;
+                if *count > self.graph.nodes.len() {
+                    return Err(String::from("Negative-cost cycle in the residual graph"));
+                }
+                if !queued.get(&to).copied().unwrap_or(false) {
+                    queued.insert(to, true);
+                    queue.push_back(to);
+                }
+            }
+        }
+        Ok((distance, via))
+    }
+    /// Same as `spfa`, for non-negative reduced costs.
+    fn dijkstra(&self) -> Paths {
+        let mut distance = HashMap::from([(self.source, 0)]);
+        let mut via = HashMap::new();
+        let mut heap = BinaryHeap::from([Reverse((0, self.source))]);
+        while let Some(Reverse((known, u))) = heap.pop() {
+            if known > distance[&u] {
+                continue;
+            }
+            for arc in self.arcs_from(u) {
+                let to = self.runtime.arcs[*arc].to;
+                let candidate = known + self.reduced_cost(*arc);
+                if self.runtime.residual(*arc) == 0
+                    || distance.get(&to).is_some_and(|known| *known <= candidate)
+                {
+                    continue;
+                }
+                distance.insert(to, candidate);
+                via.insert(to, *arc);
+                heap.push(Reverse((candidate, to)));
+            }
+        }
+        (distance, via)
+    }
+    /// Finds the next cheapest path and stores it. Returns `false` when the
+    /// sink is unreachable or the search failed.
+    fn find_path(&mut self) -> bool {
+        let found = match self.search {
+            PathSearch::Spfa => self.spfa(),
+            PathSearch::Dijkstra => Ok(self.dijkstra()),
+        };
+        let (distance, via) = match found {
+            Ok(found) => found,
+            Err(error) => {
+                self.runtime.error = Some(error);
+                return false;
+            }
+        };
+        // Reduced distances turn back into real costs with the potentials.
+        let source_potential = self.runtime.potential[&self.source];
+        self.runtime.distance = distance
+            .iter()
+            .map(|(id, reduced)| (*id, reduced - source_potential + self.runtime.potential[id]))
+            .collect();
+        if self.search == PathSearch::Dijkstra {
+            for (id, reduced) in distance.iter() {
+                *self.runtime.potential.get_mut(id).expect("Known node") += reduced;
+            }
+        }
+        self.runtime.path.clear();
+        if !via.contains_key(&self.sink) {
+            return false;
+        }
+        let mut node = self.sink;
+        while let Some(arc) = via.get(&node).filter(|_| node != self.source) {
+            self.runtime.path.push(*arc);
+            node = self.runtime.arcs[*arc].from;
+        }
+        self.runtime.path.reverse();
+        true
+    }
+}
+impl Algorithm<Runtime> for MinCostFlow {
+    fn get_snapshot(&self) -> Snapshot<Runtime> {
+        Snapshot {
+            data: self.runtime.clone(),
+            graph: self.graph.to_snapshot().unwrap(),
+        }
+    }
+    fn get_history(&self) -> &[Runtime] {
+        &self.history
+    }
+    fn run(&mut self) {
+        let negative = self.runtime.arcs.iter().step_by(2).any(|arc| arc.cost < 0);
+        if self.search == PathSearch::Dijkstra && negative {
+            match self.spfa() {
+                Ok((distance, _)) => self.runtime.potential.extend(distance),
+                Err(error) => self.runtime.error = Some(error),
+            }
+        }
+        self.record_step();
+        while self.runtime.error.is_none() && self.find_path() {
+            let path = &self.runtime.path;
+            let bottleneck = path.iter().map(|arc| self.runtime.residual(*arc)).min();
+            self.runtime.bottleneck = bottleneck.expect("Source and sink differ");
+            self.record_step();
+            for arc in self.runtime.path.clone() {
+                self.runtime.flow[arc] += self.runtime.bottleneck;
+                self.runtime.flow[arc ^ 1] -= self.runtime.bottleneck;
+                self.runtime.total_cost += self.runtime.bottleneck * self.runtime.arcs[arc].cost;
+            }
+            self.runtime.total_flow += self.runtime.bottleneck;
+            self.record_step();
+        }
+        self.runtime.bottleneck = 0;
+        record_last(&mut self.history, &self.runtime);
+    }
+}
+impl Inspect for Runtime {
+    fn variables(&self) -> Vec<Variable> {
+        let path = self.path.iter().map(|arc| {
+            let Arc { from, to, cost, .. } = self.arcs[*arc];
+            format!("{}->{} @{}", from, to, cost)
+        });
+        vec![
+            Variable::list("path", VariableKind::Array, path),
+            Variable::scalar("bottleneck", self.bottleneck),
+            Variable::map("potential", &self.potential, |value| value.to_string()),
+            Variable::map("distance", &self.distance, |value| value.to_string()),
+            Variable::scalar("total_flow", self.total_flow),
+            Variable::scalar("total_cost", self.total_cost),
+            Variable::scalar("error", self.error.as_deref().unwrap_or("-")),
+        ]
+    }
+    fn overlay(&self) -> Overlay {
+        let nodes = self.path_nodes();
+        Overlay {
+            node_annotations: self
+                .potential
+                .iter()
+                .map(|(id, potential)| (*id, format!("pi={}", potential)))
+                .collect(),
+            highlighted_nodes: nodes.iter().copied().collect(),
+            highlighted_edges: nodes.windows(2).map(|pair| (pair[0], pair[1])).collect(),
+            edge_labels: self.labels(),
+            secondary: Some(Rc::new(self.residual_view())),
+            ..Overlay::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn setup_network() -> Graph {
+        // Two routes from 0 to 3: through 1 (cost 1 + 1) and through 2
+        // (cost 2 + 3), plus a shortcut 1 -> 2 (cost 1).
+        let edges = vec![(0, 1, 2), (0, 2, 2), (1, 3, 1), (2, 3, 3), (1, 2, 1)];
+        let costs = [
+            ((0, 1), 1),
+            ((0, 2), 2),
+            ((1, 3), 1),
+            ((2, 3), 3),
+            ((1, 2), 1),
+        ];
+        let mut graph = Graph::from_list_of_weighted_edges(edges, false);
+        for ((start, end), cost) in costs {
+            graph.get_edge_mut(start, end).unwrap().runtime.cost = cost;
+        }
+        graph
+    }
+
+    #[test]
+    fn test_min_cost_flow_both_searches_agree() -> Result<(), String> {
+        for search in [PathSearch::Spfa, PathSearch::Dijkstra] {
+            let mut my_flow = MinCostFlow::new(setup_network(), 0, 3, search).ok_or("Ends")?;
+
+            my_flow.run();
+
+            assert_eq!(my_flow.runtime.total_flow, 4);
+            // 1 unit on 0-1-3 (2), 1 on 0-1-2-3 (5), 2 on 0-2-3 (10).
+            assert_eq!(my_flow.runtime.total_cost, 17);
+            assert!(my_flow.runtime.error.is_none());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_min_cost_flow_potentials_and_negative_costs() -> Result<(), String> {
+        let mut graph = setup_network();
+        graph.get_edge_mut(1, 2).unwrap().runtime.cost = -1;
+        let mut my_flow = MinCostFlow::new(graph, 0, 3, PathSearch::Dijkstra).ok_or("Ends")?;
+
+        my_flow.run();
+
+        assert_eq!(my_flow.get_history()[0].potential[&2], 0);
+        assert_eq!(my_flow.runtime.total_flow, 4);
+        assert_eq!(my_flow.runtime.total_cost, 15);
+        let overlay = my_flow.runtime.overlay();
+        assert_eq!(overlay.edge_label(1, 2, true), Some("1/1 @-1"));
+        Ok(())
+    }
+}
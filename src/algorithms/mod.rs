@@ -1,3 +1,29 @@
 pub mod algorithm;
+pub mod astar;
 pub mod bfs;
-pub mod dfs;
\ No newline at end of file
+pub mod bipartite_check;
+pub mod bridges;
+pub mod chromatic;
+pub mod coloring;
+pub mod components;
+pub mod cycle_detection;
+pub mod dfs;
+pub mod dinic;
+pub mod edmonds_karp;
+pub mod euler;
+pub mod flow;
+pub mod hopcroft_karp;
+pub mod inspect;
+pub mod kahn;
+pub mod kosaraju;
+pub mod kruskal;
+pub mod kuhn;
+pub mod lca;
+pub mod lca_lifting;
+pub mod lca_sparse_table;
+pub mod matching;
+pub mod min_cost_flow;
+pub mod prim;
+pub mod tarjan;
+pub mod topological_dfs;
+pub mod zero_one_bfs;
\ No newline at end of file
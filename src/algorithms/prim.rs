@@ -0,0 +1,247 @@
+use crate::{
+    algorithms::algorithm::{Algorithm, MAX_RECORDED_STEPS, Snapshot, record, record_last},
+    algorithms::inspect::{Inspect, Overlay, Variable, VariableKind},
+    algorithms::kruskal::undirected_edges,
+    graph::graph::Graph,
+};
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, BinaryHeap, HashMap, HashSet};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Runtime {
+    pub active_node: Option<u32>,
+    /// Cheapest known edge weight linking a node to the tree.
+    pub key: HashMap<u32, u32>,
+    pub parent: HashMap<u32, u32>,
+    /// Heap entries as `(key, node)`, smallest first. Outdated entries stay
+    /// until popped and are then skipped.
+    pub queue: Vec<(u32, u32)>,
+    pub in_tree: HashSet<u32>,
+    /// Edges from the tree to the rest of the graph, as `(inside, outside)`.
+    pub cut: BTreeSet<(u32, u32)>,
+    /// Crossing edge just taken into the tree.
+    pub picked: Option<(u32, u32)>,
+    pub total: u64,
+}
+/// Prim's minimum spanning tree from `source`: grows a single tree by always
+/// taking the cheapest edge that crosses the cut.
+#[derive(Debug)]
+pub struct Prim {
+    pub graph: Graph,
+    pub source: u32,
+    pub runtime: Runtime,
+    pub history: Vec<Runtime>,
+    heap: BinaryHeap<Reverse<(u32, u32)>>,
+    neighbors: HashMap<u32, Vec<(u32, u32)>>,
+}
+
+impl Prim {
+    pub fn new(graph: Graph, source: u32) -> Option<Self> {
+        if !graph.nodes.contains_key(&source) {
+            return None;
+        }
+        let mut neighbors: HashMap<u32, Vec<(u32, u32)>> = HashMap::new();
+        for (u, v, weight) in undirected_edges(&graph) {
+            neighbors.entry(u).or_default().push((v, weight));
+            neighbors.entry(v).or_default().push((u, weight));
+        }
+        for list in neighbors.values_mut() {
+            list.sort_unstable();
+        }
+        Some(Prim {
+            graph,
+            source,
+            runtime: Runtime {
+                active_node: None,
+                key: HashMap::new(),
+                parent: HashMap::new(),
+                queue: Vec::new(),
+                in_tree: HashSet::new(),
+                cut: BTreeSet::new(),
+                picked: None,
+                total: 0,
+            },
+            history: Vec::new(),
+            heap: BinaryHeap::new(),
+            neighbors,
+        })
+    }
+    fn sync_queue(&mut self) {
+        let mut queue: Vec<(u32, u32)> = self.heap.iter().map(|Reverse(entry)| *entry).collect();
+        queue.sort_unstable();
+        self.runtime.queue = queue;
+    }
+    fn record_step(&mut self) {
+        if self.history.len() < MAX_RECORDED_STEPS {
+            self.sync_queue();
+            record(&mut self.history, &self.runtime);
+        }
+    }
+    fn neighbors(&self, u: u32) -> &[(u32, u32)] {
+        self.neighbors.get(&u).map_or(&[], |list| list.as_slice())
+    }
+    /// Moves the edges around `u`, which just joined the tree, across the cut.
+    fn update_cut(&mut self, u: u32) {
+        for (v, _) in self.neighbors.get(&u).into_iter().flatten() {
+            match self.runtime.in_tree.contains(v) {
+                true => self.runtime.cut.remove(&(*v, u)),
+                false => self.runtime.cut.insert((u, *v)),
+            };
+        }
+    }
+}
+impl Algorithm<Runtime> for Prim {
+    fn get_snapshot(&self) -> Snapshot<Runtime> {
+        Snapshot {
+            data: self.runtime.clone(),
+            graph: self.graph.to_snapshot().unwrap(),
+        }
+    }
+    fn get_history(&self) -> &[Runtime] {
+        &self.history
+    }
+    fn run(&mut self) {
+        self.runtime.key.insert(self.source, 0);
+        self.heap.push(Reverse((0, self.source)));
+        self.record_step();
+
+        while let Some(Reverse((key, u))) = self.heap.pop() {
+            if self.runtime.in_tree.contains(&u) || key > self.runtime.key[&u] {
+                continue;
+            }
+            self.runtime.active_node = Some(u);
+            self.runtime.in_tree.insert(u);
+            self.runtime.picked = self.runtime.parent.get(&u).map(|parent| (*parent, u));
+            self.runtime.total += key as u64;
+            self.update_cut(u);
+            self.record_step();
+            for (v, weight) in self.neighbors(u).to_vec() {
+                if self.runtime.in_tree.contains(&v)
+                    || self.runtime.key.get(&v).is_some_and(|key| *key <= weight)
+                {
+                    continue;
+                }
+                self.runtime.key.insert(v, weight);
+                self.runtime.parent.insert(v, u);
+                self.heap.push(Reverse((weight, v)));
+                self.record_step();
+            }
+        }
+        self.runtime.active_node = None;
+        self.runtime.picked = None;
+        self.sync_queue();
+        record_last(&mut self.history, &self.runtime);
+    }
+}
+impl Inspect for Runtime {
+    fn variables(&self) -> Vec<Variable> {
+        let active = self
+            .active_node
+            .map_or(String::from("-"), |id| id.to_string());
+        let queue = self
+            .queue
+            .iter()
+            .map(|(key, node)| format!("{}:{}", node, key));
+        let cut = self.cut.iter().map(|(u, v)| format!("{}-{}", u, v));
+        let mut in_tree: Vec<u32> = self.in_tree.iter().copied().collect();
+        in_tree.sort_unstable();
+        vec![
+            Variable::scalar("active_node", active),
+            Variable::list("queue", VariableKind::Queue, queue),
+            Variable::map("key", &self.key, |key| key.to_string()),
+            Variable::map("parent", &self.parent, |id| id.to_string()),
+            Variable::list("in_tree", VariableKind::Array, in_tree),
+            Variable::list("cut", VariableKind::Array, cut),
+            Variable::scalar("total", self.total),
+        ]
+    }
+    fn overlay(&self) -> Overlay {
+        let mut overlay = Overlay {
+            node_annotations: self
+                .key
+                .iter()
+                .map(|(id, key)| (*id, format!("k={}", key)))
+                .collect(),
+            highlighted_nodes: self.active_node.into_iter().collect(),
+            // Both orientations, so directed edges stored either way match.
+            highlighted_edges: self
+                .in_tree
+                .iter()
+                .filter_map(|id| self.parent.get(id).map(|parent| (*parent, *id)))
+                .flat_map(|(u, v)| [(u, v), (v, u)])
+                .collect(),
+            marked_edges: self
+                .cut
+                .iter()
+                .flat_map(|(u, v)| [(*u, *v), (*v, *u)])
+                .collect(),
+            node_colors: self.in_tree.iter().map(|id| (*id, 0)).collect(),
+            ..Overlay::default()
+        };
+        // The crossing edge just taken stands out from the rest of the tree.
+        if let Some((parent, u)) = self.picked {
+            let label = format!("picked {}", self.key[&u]);
+            overlay.highlighted_nodes.extend([parent, u]);
+            overlay.edge_labels.insert((parent, u), label.clone());
+            overlay.edge_labels.insert((u, parent), label);
+        }
+        overlay
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn setup_weighted() -> Graph {
+        let edges = vec![
+            (0, 1, 4),
+            (0, 2, 1),
+            (1, 2, 2),
+            (1, 3, 5),
+            (2, 3, 8),
+            (3, 4, 3),
+        ];
+        Graph::from_list_of_weighted_edges(edges, true)
+    }
+
+    #[test]
+    fn test_prim_builds_minimum_tree() -> Result<(), String> {
+        let mut my_prim = Prim::new(setup_weighted(), 0).ok_or("Source exists")?;
+
+        my_prim.run();
+
+        assert_eq!(my_prim.runtime.total, 11);
+        let parent = &my_prim.runtime.parent;
+        assert_eq!(
+            (parent[&1], parent[&2], parent[&3], parent[&4]),
+            (2, 0, 1, 3)
+        );
+        assert!(my_prim.runtime.cut.is_empty());
+        assert!(Prim::new(setup_weighted(), 9).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_prim_cut_after_first_pick() -> Result<(), String> {
+        let mut my_prim = Prim::new(setup_weighted(), 0).ok_or("Source exists")?;
+
+        my_prim.run();
+
+        let first = my_prim
+            .get_history()
+            .iter()
+            .find(|step| step.active_node == Some(2))
+            .unwrap();
+        assert_eq!(first.picked, Some((0, 2)));
+        assert_eq!(
+            first.cut.iter().copied().collect::<Vec<_>>(),
+            vec![(0, 1), (2, 1), (2, 3)]
+        );
+        let overlay = first.overlay();
+        assert!(overlay.is_edge_highlighted(2, 0, true));
+        assert_eq!(overlay.edge_label(2, 0, true), Some("picked 1"));
+        assert!(overlay.highlighted_nodes.contains(&0));
+        assert_eq!(first.queue, vec![(4, 1)]);
+        Ok(())
+    }
+}
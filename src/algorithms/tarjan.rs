@@ -0,0 +1,199 @@
+use crate::{
+    algorithms::algorithm::{Algorithm, Snapshot, record, record_last},
+    algorithms::components::condensation,
+    algorithms::inspect::{Inspect, Overlay, SecondaryView, Variable, VariableKind},
+    algorithms::kahn::successors,
+    graph::graph::Graph,
+};
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Runtime {
+    pub active_node: Option<u32>,
+    pub timer: u32,
+    pub disc: HashMap<u32, u32>,
+    pub low: HashMap<u32, u32>,
+    /// Visited nodes not yet assigned to a component.
+    pub stack: Vec<u32>,
+    pub component: HashMap<u32, usize>,
+    /// Components closed so far, which is also the id of the next one.
+    pub components: usize,
+    pub condensation: Option<Rc<SecondaryView>>,
+}
+/// Tarjan's strongly connected components: a node whose `low` equals its
+/// `disc` when its call returns closes a component, which is popped from the
+/// stack.
+#[derive(Debug, PartialEq)]
+pub struct Tarjan {
+    pub graph: Graph,
+    pub runtime: Runtime,
+    pub history: Vec<Runtime>,
+}
+
+impl Tarjan {
+    pub fn new(graph: Graph) -> Self {
+        Tarjan {
+            graph,
+            runtime: Runtime {
+                active_node: None,
+                timer: 0,
+                disc: HashMap::new(),
+                low: HashMap::new(),
+                stack: Vec::new(),
+                component: HashMap::new(),
+                components: 0,
+                condensation: None,
+            },
+            history: Vec::new(),
+        }
+    }
+    fn record_step(&mut self) {
+        record(&mut self.history, &self.runtime);
+    }
+    fn enter(&mut self, u: u32) {
+        self.runtime.active_node = Some(u);
+        self.runtime.disc.insert(u, self.runtime.timer);
+        self.runtime.low.insert(u, self.runtime.timer);
+        self.runtime.timer += 1;
+        self.runtime.stack.push(u);
+        self.record_step();
+    }
+    fn lower(&mut self, u: u32, value: u32) {
+        let low = self.runtime.low.get_mut(&u).expect("Visited");
+        if value < *low {
+            *low = value;
+            self.record_step();
+        }
+    }
+    fn visit(&mut self, root: u32) {
+        let mut on_stack: HashSet<u32> = self.runtime.stack.iter().copied().collect();
+        let mut calls = vec![root];
+        let mut pending = vec![successors(&self.graph, root).into_iter()];
+        self.enter(root);
+        on_stack.insert(root);
+        while let Some(next) = pending.last_mut().map(|ends| ends.next()) {
+            let u = *calls.last().expect("Open call");
+            match next {
+                Some(v) if !self.runtime.disc.contains_key(&v) => {
+                    self.enter(v);
+                    on_stack.insert(v);
+                    calls.push(v);
+                    pending.push(successors(&self.graph, v).into_iter());
+                }
+                Some(v) if on_stack.contains(&v) => self.lower(u, self.runtime.disc[&v]),
+                Some(_) => {}
+                None => {
+                    pending.pop();
+                    calls.pop();
+                    if self.runtime.low[&u] == self.runtime.disc[&u] {
+                        let id = self.runtime.components;
+                        self.runtime.components += 1;
+                        while let Some(member) = self.runtime.stack.pop() {
+                            on_stack.remove(&member);
+                            self.runtime.component.insert(member, id);
+                            if member == u {
+                                break;
+                            }
+                        }
+                    }
+                    self.runtime.active_node = calls.last().copied();
+                    self.record_step();
+                    if let Some(parent) = calls.last() {
+                        self.lower(*parent, self.runtime.low[&u]);
+                    }
+                }
+            }
+        }
+    }
+}
+impl Algorithm<Runtime> for Tarjan {
+    fn get_snapshot(&self) -> Snapshot<Runtime> {
+        Snapshot {
+            data: self.runtime.clone(),
+            graph: self.graph.to_snapshot().unwrap(),
+        }
+    }
+    fn get_history(&self) -> &[Runtime] {
+        &self.history
+    }
+    fn run(&mut self) {
+        let mut roots: Vec<u32> = self.graph.nodes.keys().copied().collect();
+        roots.sort_unstable();
+        for root in roots {
+            if !self.runtime.disc.contains_key(&root) {
+                self.visit(root);
+            }
+        }
+        let view = condensation(&self.graph, &self.runtime.component);
+        self.runtime.condensation = Some(Rc::new(view));
+        record_last(&mut self.history, &self.runtime);
+    }
+}
+impl Inspect for Runtime {
+    fn variables(&self) -> Vec<Variable> {
+        let active = self
+            .active_node
+            .map_or(String::from("-"), |id| id.to_string());
+        vec![
+            Variable::scalar("active_node", active),
+            Variable::map("disc", &self.disc, |time| time.to_string()),
+            Variable::map("low", &self.low, |time| time.to_string()),
+            Variable::list("stack", VariableKind::Stack, self.stack.iter()),
+            Variable::map("component", &self.component, |id| id.to_string()),
+        ]
+    }
+    fn overlay(&self) -> Overlay {
+        Overlay {
+            node_annotations: self
+                .disc
+                .iter()
+                .map(|(id, disc)| (*id, format!("{}/{}", disc, self.low[id])))
+                .collect(),
+            highlighted_nodes: self.stack.iter().copied().collect(),
+            node_colors: self.component.clone(),
+            secondary: self.condensation.clone(),
+            ..Overlay::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn setup_components() -> Graph {
+        // {0, 1, 2} -> {3, 4} -> {5}
+        let edges = vec![(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 3), (4, 5)];
+        Graph::from_list_of_edges(edges, false)
+    }
+
+    #[test]
+    fn test_tarjan_finds_components() -> Result<(), String> {
+        let mut my_tarjan = Tarjan::new(setup_components());
+
+        my_tarjan.run();
+
+        let component = &my_tarjan.runtime.component;
+        assert_eq!(component[&5], 0, "Sink components close first");
+        assert_eq!(component[&3], component[&4]);
+        assert_eq!(component[&0], component[&2]);
+        assert_ne!(component[&0], component[&3]);
+        assert_eq!(my_tarjan.runtime.low[&2], 0);
+        assert!(my_tarjan.runtime.stack.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_tarjan_final_step_has_condensation() -> Result<(), String> {
+        let mut my_tarjan = Tarjan::new(setup_components());
+
+        my_tarjan.run();
+
+        let overlay = my_tarjan.get_history().last().unwrap().overlay();
+        let view = overlay.secondary.expect("Condensation view");
+        assert_eq!(view.graph.nodes.len(), 3);
+        assert!(view.graph.get_edge(2, 1).is_some());
+        assert!(view.graph.get_edge(1, 0).is_some());
+        Ok(())
+    }
+}
@@ -0,0 +1,183 @@
+use crate::{
+    algorithms::algorithm::{Algorithm, SharedLog, Snapshot, record, record_last},
+    algorithms::inspect::{Inspect, Overlay, Variable, VariableKind},
+    algorithms::kahn::successors,
+    graph::graph::Graph,
+};
+use std::collections::HashMap;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Runtime {
+    pub active_node: Option<u32>,
+    pub timer: u32,
+    pub entry: HashMap<u32, u32>,
+    pub exit: HashMap<u32, u32>,
+    /// Nodes whose recursive call is still open.
+    pub stack: Vec<u32>,
+    /// Nodes by increasing exit time; reversed, the topological order so far.
+    pub finished: SharedLog<u32>,
+    /// Nodes of a cycle, in edge order, when no ordering exists.
+    pub cycle: Vec<u32>,
+}
+/// Topological sort by DFS finishing times: a node is finished when its call
+/// returns, and the order is the finished nodes reversed. A back edge means there is a cycle.
+#[derive(Debug, PartialEq)]
+pub struct TopologicalDFS {
+    pub graph: Graph,
+    pub runtime: Runtime,
+    pub history: Vec<Runtime>,
+}
+
+impl Runtime {
+    /// Nodes by decreasing exit time.
+    pub fn order(&self) -> Vec<u32> {
+        self.finished.items().iter().rev().copied().collect()
+    }
+}
+impl TopologicalDFS {
+    pub fn new(graph: Graph) -> Self {
+        TopologicalDFS {
+            graph,
+            runtime: Runtime {
+                active_node: None,
+                timer: 0,
+                entry: HashMap::new(),
+                exit: HashMap::new(),
+                stack: Vec::new(),
+                finished: SharedLog::new(),
+                cycle: Vec::new(),
+            },
+            history: Vec::new(),
+        }
+    }
+    fn record_step(&mut self) {
+        record(&mut self.history, &self.runtime);
+    }
+    fn enter(&mut self, u: u32) {
+        self.runtime.active_node = Some(u);
+        self.runtime.entry.insert(u, self.runtime.timer);
+        self.runtime.timer += 1;
+        self.runtime.stack.push(u);
+        self.record_step();
+    }
+    fn leave(&mut self, u: u32) {
+        self.runtime.exit.insert(u, self.runtime.timer);
+        self.runtime.timer += 1;
+        self.runtime.stack.pop();
+        self.runtime.finished.push(u);
+        self.runtime.active_node = self.runtime.stack.last().copied();
+        self.record_step();
+    }
+    /// Explicit-stack DFS from `root`. Returns `false` once a cycle is found.
+    fn visit(&mut self, root: u32) -> bool {
+        self.enter(root);
+        let mut pending = vec![successors(&self.graph, root).into_iter()];
+        while let Some(next) = pending.last_mut().map(|ends| ends.next()) {
+            let u = *self.runtime.stack.last().expect("Open call");
+            match next {
+                None => {
+                    pending.pop();
+                    self.leave(u);
+                }
+                Some(v) if !self.runtime.entry.contains_key(&v) => {
+                    self.enter(v);
+                    pending.push(successors(&self.graph, v).into_iter());
+                }
+                Some(v) if !self.runtime.exit.contains_key(&v) => {
+                    let start = self.runtime.stack.iter().position(|id| *id == v);
+                    self.runtime.cycle = self.runtime.stack[start.expect("On stack")..].to_vec();
+                    self.record_step();
+                    return false;
+                }
+                Some(_) => {}
+            }
+        }
+        true
+    }
+}
+impl Algorithm<Runtime> for TopologicalDFS {
+    fn get_snapshot(&self) -> Snapshot<Runtime> {
+        Snapshot {
+            data: self.runtime.clone(),
+            graph: self.graph.to_snapshot().unwrap(),
+        }
+    }
+    fn get_history(&self) -> &[Runtime] {
+        &self.history
+    }
+    fn run(&mut self) {
+        let mut roots: Vec<u32> = self.graph.nodes.keys().copied().collect();
+        roots.sort_unstable();
+        for root in roots {
+            if !self.runtime.entry.contains_key(&root) && !self.visit(root) {
+                break;
+            }
+        }
+        record_last(&mut self.history, &self.runtime);
+    }
+}
+impl Inspect for Runtime {
+    fn variables(&self) -> Vec<Variable> {
+        let active = self
+            .active_node
+            .map_or(String::from("-"), |id| id.to_string());
+        vec![
+            Variable::scalar("active_node", active),
+            Variable::list("stack", VariableKind::Stack, self.stack.iter()),
+            Variable::map("entry", &self.entry, |time| time.to_string()),
+            Variable::map("exit", &self.exit, |time| time.to_string()),
+            Variable::list("order", VariableKind::Array, self.order()),
+            Variable::list("cycle", VariableKind::Array, self.cycle.iter()),
+        ]
+    }
+    fn overlay(&self) -> Overlay {
+        let mut overlay = Overlay {
+            node_annotations: self
+                .entry
+                .iter()
+                .map(|(id, entry)| match self.exit.get(id) {
+                    Some(exit) => (*id, format!("{}/{}", entry, exit)),
+                    None => (*id, format!("{}/", entry)),
+                })
+                .collect(),
+            ..Overlay::default()
+        };
+        overlay.highlight_cycle(&self.cycle);
+        overlay
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn setup_dag() -> Graph {
+        let edges = vec![(5, 2), (5, 0), (4, 0), (4, 1), (2, 3), (3, 1)];
+        Graph::from_list_of_edges(edges, false)
+    }
+
+    #[test]
+    fn test_topological_dfs_orders_by_exit_time() -> Result<(), String> {
+        let mut my_sort = TopologicalDFS::new(setup_dag());
+
+        my_sort.run();
+
+        assert_eq!(my_sort.runtime.order(), vec![5, 4, 2, 3, 1, 0]);
+        assert_eq!(my_sort.runtime.entry[&0], 0);
+        assert_eq!(my_sort.runtime.exit[&0], 1);
+        assert!(my_sort.runtime.stack.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_topological_dfs_stops_at_back_edge() -> Result<(), String> {
+        let edges = vec![(0, 1), (1, 2), (2, 3), (3, 1), (3, 4)];
+        let mut my_sort = TopologicalDFS::new(Graph::from_list_of_edges(edges, false));
+
+        my_sort.run();
+
+        assert_eq!(my_sort.runtime.cycle, vec![1, 2, 3]);
+        let overlay = my_sort.get_history().last().unwrap().overlay();
+        assert!(overlay.highlighted_edges.contains(&(3, 1)));
+        Ok(())
+    }
+}
@@ -0,0 +1,181 @@
+use crate::{
+    algorithms::algorithm::{Algorithm, SharedLog, Snapshot, record, record_last},
+    algorithms::inspect::{Inspect, Overlay, Variable, VariableKind},
+    graph::graph::Graph,
+};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Runtime {
+    pub active_node: Option<u32>,
+    pub deque: VecDeque<u32>,
+    pub distance: HashMap<u32, u64>,
+    pub parent: HashMap<u32, u32>,
+    /// Nodes whose distance is final.
+    pub done: HashSet<u32>,
+    /// Last relaxation as `(from, to, pushed to the front)`.
+    pub pushed: Option<(u32, u32, bool)>,
+    pub decisions: SharedLog<String>,
+}
+/// 0-1 BFS from `source`: shortest paths when every weight is 0 or 1. A node
+/// reached over a 0 edge goes to the front of the deque, over a 1 edge to
+/// the back, so the deque stays sorted by distance.
+#[derive(Debug, PartialEq)]
+pub struct ZeroOneBFS {
+    pub graph: Graph,
+    pub source: u32,
+    pub runtime: Runtime,
+    pub history: Vec<Runtime>,
+}
+
+impl ZeroOneBFS {
+    /// `None` when the source is missing or some weight is neither 0 nor 1.
+    pub fn new(graph: Graph, source: u32) -> Option<Self> {
+        let binary = graph
+            .nodes
+            .values()
+            .flat_map(|node| node.runtime.edges.values())
+            .all(|edge| edge.runtime.weight <= 1);
+        if !binary || !graph.nodes.contains_key(&source) {
+            return None;
+        }
+        Some(ZeroOneBFS {
+            graph,
+            source,
+            runtime: Runtime {
+                active_node: None,
+                deque: VecDeque::new(),
+                distance: HashMap::new(),
+                parent: HashMap::new(),
+                done: HashSet::new(),
+                pushed: None,
+                decisions: SharedLog::new(),
+            },
+            history: Vec::new(),
+        })
+    }
+    fn record_step(&mut self) {
+        record(&mut self.history, &self.runtime);
+    }
+}
+impl Algorithm<Runtime> for ZeroOneBFS {
+    fn get_snapshot(&self) -> Snapshot<Runtime> {
+        Snapshot {
+            data: self.runtime.clone(),
+            graph: self.graph.to_snapshot().unwrap(),
+        }
+    }
+    fn get_history(&self) -> &[Runtime] {
+        &self.history
+    }
+    fn run(&mut self) {
+        self.runtime.distance.insert(self.source, 0);
+        self.runtime.deque.push_back(self.source);
+        self.record_step();
+        while let Some(u) = self.runtime.deque.pop_front() {
+            // A node pushed twice is expanded only the first time.
+            if !self.runtime.done.insert(u) {
+                continue;
+            }
+            self.runtime.active_node = Some(u);
+            self.runtime.pushed = None;
+            self.record_step();
+            let mut edges: Vec<(u32, u32)> = self.graph.nodes[&u]
+                .runtime
+                .edges
+                .values()
+                .map(|edge| (edge.runtime.node_end, edge.runtime.weight))
+                .filter(|(v, _)| self.graph.nodes.contains_key(v))
+                .collect();
+            edges.sort_unstable();
+            for (v, weight) in edges {
+                let distance = self.runtime.distance[&u] + weight as u64;
+                if self
+                    .runtime
+                    .distance
+                    .get(&v)
+                    .is_some_and(|known| *known <= distance)
+                {
+                    continue;
+                }
+                self.runtime.distance.insert(v, distance);
+                self.runtime.parent.insert(v, u);
+                let front = weight == 0;
+                match front {
+                    true => self.runtime.deque.push_front(v),
+                    false => self.runtime.deque.push_back(v),
+                }
+                let side = if front { "front" } else { "back" };
+                self.runtime.decisions.push(format!(
+                    "{}->{} w={}: {} to {}",
+                    u, v, weight, side, distance
+                ));
+                self.runtime.pushed = Some((u, v, front));
+                self.record_step();
+            }
+        }
+        self.runtime.active_node = None;
+        self.runtime.pushed = None;
+        record_last(&mut self.history, &self.runtime);
+    }
+}
+impl Inspect for Runtime {
+    fn variables(&self) -> Vec<Variable> {
+        let active = self
+            .active_node
+            .map_or(String::from("-"), |id| id.to_string());
+        vec![
+            Variable::scalar("active_node", active),
+            Variable::list("deque", VariableKind::Queue, self.deque.iter()),
+            Variable::map("distance", &self.distance, |distance| distance.to_string()),
+            Variable::map("parent", &self.parent, |id| id.to_string()),
+            Variable::list(
+                "decisions",
+                VariableKind::Array,
+                self.decisions.items().iter(),
+            ),
+        ]
+    }
+    fn overlay(&self) -> Overlay {
+        let mut overlay = Overlay {
+            node_annotations: self
+                .distance
+                .iter()
+                .map(|(id, distance)| (*id, format!("d={}", distance)))
+                .collect(),
+            highlighted_nodes: self.active_node.into_iter().collect(),
+            highlighted_edges: self.parent.iter().map(|(v, u)| (*u, *v)).collect(),
+            ..Overlay::default()
+        };
+        // Front pushes in the first palette color, back pushes in the second.
+        if let Some((u, v, front)) = self.pushed {
+            overlay.marked_edges.insert((u, v));
+            overlay.node_colors.insert(v, !front as usize);
+        }
+        overlay
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zero_one_bfs_prefers_zero_edges() -> Result<(), String> {
+        // The direct edge 0-3 costs 1, the detour 0-1-2-3 costs 0.
+        let edges = vec![(0, 3, 1), (0, 1, 0), (1, 2, 0), (2, 3, 0), (3, 4, 1)];
+        let graph = Graph::from_list_of_weighted_edges(edges, false);
+        let mut my_bfs = ZeroOneBFS::new(graph, 0).ok_or("Valid graph")?;
+
+        my_bfs.run();
+
+        let distance = &my_bfs.runtime.distance;
+        assert_eq!((distance[&3], distance[&4]), (0, 1));
+        assert_eq!(my_bfs.runtime.parent[&3], 2);
+        let first = &my_bfs.runtime.decisions.items()[..2];
+        assert_eq!(first, ["0->1 w=0: front to 0", "0->3 w=1: back to 1"]);
+        let heavy = Graph::from_list_of_weighted_edges(vec![(0, 1, 2)], false);
+        assert!(ZeroOneBFS::new(heavy, 0).is_none());
+        Ok(())
+    }
+}
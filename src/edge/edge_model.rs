@@ -3,6 +3,14 @@ pub struct GUIModel {
     pub line_type: u32,
     pub thickness: u32,
 }
+impl Default for GUIModel {
+    fn default() -> GUIModel {
+        GUIModel {
+            line_type: 1,
+            thickness: 2,
+        }
+    }
+}
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
 pub struct EdgeSnapshot {
@@ -10,6 +18,8 @@ pub struct EdgeSnapshot {
     pub node_start: u32,
     pub node_end: u32,
     pub weight: u32,
+    pub cost: i32,
+    pub directed: bool,
 }
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
@@ -17,6 +27,10 @@ pub struct Runtime {
     pub node_start: u32,
     pub node_end: u32,
     pub weight: u32,
+    /// Price per unit of flow, used by min-cost flow.
+    pub cost: i32,
+    /// `false` when the edge is one half of an undirected pair.
+    pub directed: bool,
 }
 #[derive(Debug, Hash, Clone, Copy)]
 pub struct Edge {
@@ -38,19 +52,25 @@ impl Edge {
                 node_start,
                 node_end,
                 weight,
+                cost: 0,
+                directed: true,
             },
-            gui_model: GUIModel {
-                line_type: 1,
-                thickness: 1,
-            },
+            gui_model: GUIModel::default(),
         }
+    }
+    pub fn new_undirected(node_start: u32, node_end: u32, weight: u32) -> Self {
+        let mut edge = Edge::new(node_start, node_end, weight);
+        edge.runtime.directed = false;
+        edge
     }
 	pub fn to_snapshot(&self) -> EdgeSnapshot {
 		EdgeSnapshot {
 			id: self.id,
 			node_start: self.runtime.node_start,
 			node_end: self.runtime.node_end,
-			weight: self.runtime.weight
+			weight: self.runtime.weight,
+			cost: self.runtime.cost,
+			directed: self.runtime.directed,
 		}
 	}
 }
@@ -0,0 +1,90 @@
+use crate::graph::graph::Graph;
+use rand::{Rng, SeedableRng, rngs::StdRng};
+
+/// Settings shared by every generator. The same options always produce the
+/// same graph.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct GeneratorOptions {
+    pub seed: u64,
+    /// Inclusive range edge weights are drawn from; every weight is 1 when unset.
+    pub weights: Option<(u32, u32)>,
+    pub undirected: bool,
+}
+
+impl Default for GeneratorOptions {
+    fn default() -> Self {
+        GeneratorOptions {
+            seed: 0,
+            weights: None,
+            undirected: true,
+        }
+    }
+}
+
+impl GeneratorOptions {
+    pub fn with_seed(seed: u64) -> Self {
+        GeneratorOptions {
+            seed,
+            ..Self::default()
+        }
+    }
+    pub fn rng(&self) -> StdRng {
+        StdRng::seed_from_u64(self.seed)
+    }
+}
+
+/// Graph with nodes `0..count`, isolated ones included, and the given edges
+/// weighted according to `options`.
+pub fn build(
+    count: u32,
+    edges: Vec<(u32, u32)>,
+    options: &GeneratorOptions,
+    rng: &mut StdRng,
+) -> Graph {
+    let weighted = edges
+        .into_iter()
+        .map(|(u, v)| {
+            let weight = match options.weights {
+                Some((low, high)) => rng.random_range(low.min(high)..=high.max(low)),
+                None => 1,
+            };
+            (u, v, weight)
+        })
+        .collect();
+    let mut graph = Graph::from_list_of_weighted_edges(weighted, options.undirected);
+    for id in 0..count {
+        graph.add_new_node_to_graph(id, 0.0, 0.0);
+    }
+    graph
+}
+
+/// Number of edges, counting an undirected pair once.
+pub fn edge_count(graph: &Graph) -> usize {
+    graph
+        .nodes
+        .values()
+        .flat_map(|node| node.runtime.edges.values())
+        .filter(|edge| edge.runtime.directed || edge.runtime.node_start < edge.runtime.node_end)
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_keeps_isolated_nodes_and_weights() -> Result<(), String> {
+        let options = GeneratorOptions {
+            weights: Some((5, 9)),
+            ..GeneratorOptions::with_seed(3)
+        };
+        let my_graph = build(4, vec![(0, 1), (1, 2)], &options, &mut options.rng());
+
+        assert_eq!(my_graph.nodes.len(), 4);
+        assert_eq!(edge_count(&my_graph), 2);
+        let weight = my_graph.get_edge(0, 1).unwrap().runtime.weight;
+        assert!((5..=9).contains(&weight));
+        assert_eq!(my_graph.get_edge(1, 0).unwrap().runtime.weight, weight);
+        Ok(())
+    }
+}
@@ -0,0 +1,303 @@
+use crate::generators::generator::{GeneratorOptions, build};
+use crate::graph::graph::Graph;
+use rand::{Rng, seq::SliceRandom};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+
+/// Pair key that ignores orientation for undirected graphs.
+fn key(u: u32, v: u32, undirected: bool) -> (u32, u32) {
+    if undirected {
+        (u.min(v), u.max(v))
+    } else {
+        (u, v)
+    }
+}
+
+fn max_edges(count: u32, undirected: bool) -> usize {
+    let count = count as usize;
+    let ordered = count * count.saturating_sub(1);
+    if undirected { ordered / 2 } else { ordered }
+}
+
+/// Erdős–Rényi G(n, p): every possible edge exists with probability `p`.
+pub fn gnp(count: u32, p: f64, options: &GeneratorOptions) -> Graph {
+    let mut rng = options.rng();
+    let p = p.clamp(0.0, 1.0);
+    let mut edges = Vec::new();
+    for u in 0..count {
+        for v in 0..count {
+            if u != v && (!options.undirected || u < v) && rng.random_bool(p) {
+                edges.push((u, v));
+            }
+        }
+    }
+    build(count, edges, options, &mut rng)
+}
+
+/// Erdős–Rényi G(n, m): `m` distinct edges chosen uniformly, at most all of them.
+pub fn gnm(count: u32, m: usize, options: &GeneratorOptions) -> Graph {
+    let mut rng = options.rng();
+    let m = m.min(max_edges(count, options.undirected));
+    let edges = if m * 2 > max_edges(count, options.undirected) {
+        // Dense: shuffling all pairs beats retrying collisions.
+        let mut all: Vec<(u32, u32)> = (0..count)
+            .flat_map(|u| (0..count).map(move |v| (u, v)))
+            .filter(|(u, v)| u != v && (!options.undirected || u < v))
+            .collect();
+        all.shuffle(&mut rng);
+        all.truncate(m);
+        all
+    } else {
+        let mut chosen = HashSet::new();
+        let mut edges = Vec::with_capacity(m);
+        while edges.len() < m {
+            let (u, v) = (rng.random_range(0..count), rng.random_range(0..count));
+            if u != v && chosen.insert(key(u, v, options.undirected)) {
+                edges.push((u, v));
+            }
+        }
+        edges
+    };
+    build(count, edges, options, &mut rng)
+}
+
+/// Edges of a uniformly random labeled tree, decoded from a random Prüfer sequence.
+fn prufer_tree(count: u32, rng: &mut impl Rng) -> Vec<(u32, u32)> {
+    if count < 2 {
+        return Vec::new();
+    }
+    let sequence: Vec<u32> = (0..count - 2).map(|_| rng.random_range(0..count)).collect();
+    let mut degree = vec![1; count as usize];
+    for node in sequence.iter() {
+        degree[*node as usize] += 1;
+    }
+    let mut leaves: BinaryHeap<Reverse<u32>> = (0..count)
+        .filter(|node| degree[*node as usize] == 1)
+        .map(Reverse)
+        .collect();
+    let mut edges = Vec::with_capacity(count as usize - 1);
+    for node in sequence {
+        let Reverse(leaf) = leaves.pop().unwrap();
+        edges.push((node, leaf));
+        degree[node as usize] -= 1;
+        if degree[node as usize] == 1 {
+            leaves.push(Reverse(node));
+        }
+    }
+    let Reverse(u) = leaves.pop().unwrap();
+    let Reverse(v) = leaves.pop().unwrap();
+    edges.push((u, v));
+    edges
+}
+
+/// Uniformly random labeled tree on `count` nodes.
+pub fn random_tree(count: u32, options: &GeneratorOptions) -> Graph {
+    let mut rng = options.rng();
+    let edges = prufer_tree(count, &mut rng);
+    build(count, edges, options, &mut rng)
+}
+
+/// Random DAG: nodes get a random topological order and every forward pair
+/// is an edge with probability `p`. Always directed.
+pub fn random_dag(count: u32, p: f64, options: &GeneratorOptions) -> Graph {
+    let mut rng = options.rng();
+    let mut order: Vec<u32> = (0..count).collect();
+    order.shuffle(&mut rng);
+    let mut edges = Vec::new();
+    for i in 0..order.len() {
+        for j in (i + 1)..order.len() {
+            if rng.random_bool(p.clamp(0.0, 1.0)) {
+                edges.push((order[i], order[j]));
+            }
+        }
+    }
+    let options = GeneratorOptions {
+        undirected: false,
+        ..options.clone()
+    };
+    build(count, edges, &options, &mut rng)
+}
+
+/// Connected graph with `m` edges (at least `count - 1`): a random spanning
+/// tree plus random extra edges.
+pub fn random_connected(count: u32, m: usize, options: &GeneratorOptions) -> Graph {
+    let mut rng = options.rng();
+    let mut edges = prufer_tree(count, &mut rng);
+    let mut chosen: HashSet<(u32, u32)> = edges
+        .iter()
+        .map(|(u, v)| key(*u, *v, options.undirected))
+        .collect();
+    let m = m.clamp(edges.len(), max_edges(count, options.undirected));
+    while edges.len() < m {
+        let (u, v) = (rng.random_range(0..count), rng.random_range(0..count));
+        if u != v && chosen.insert(key(u, v, options.undirected)) {
+            edges.push((u, v));
+        }
+    }
+    build(count, edges, options, &mut rng)
+}
+
+/// Random bipartite graph: nodes `0..left` on one side, `left..left + right`
+/// on the other, each cross pair an edge with probability `p`.
+pub fn random_bipartite(left: u32, right: u32, p: f64, options: &GeneratorOptions) -> Graph {
+    let mut rng = options.rng();
+    let mut edges = Vec::new();
+    for u in 0..left {
+        for v in left..left + right {
+            if rng.random_bool(p.clamp(0.0, 1.0)) {
+                edges.push((u, v));
+            }
+        }
+    }
+    build(left + right, edges, options, &mut rng)
+}
+
+/// Barabási–Albert preferential attachment: starts from a clique on `m + 1`
+/// nodes, then every new node links to `m` distinct nodes picked with
+/// probability proportional to their degree.
+pub fn barabasi_albert(count: u32, m: u32, options: &GeneratorOptions) -> Graph {
+    let mut rng = options.rng();
+    let m = m.max(1);
+    let seed_size = (m + 1).min(count);
+    let mut edges = Vec::new();
+    // Every node appears once per incident edge, so a uniform pick from this
+    // list is a degree-proportional pick.
+    let mut endpoints = Vec::new();
+    for u in 0..seed_size {
+        for v in (u + 1)..seed_size {
+            edges.push((u, v));
+            endpoints.extend([u, v]);
+        }
+    }
+    for node in seed_size..count {
+        let mut targets = HashSet::new();
+        while targets.len() < m as usize {
+            targets.insert(endpoints[rng.random_range(0..endpoints.len())]);
+        }
+        let mut targets: Vec<u32> = targets.into_iter().collect();
+        targets.sort_unstable();
+        for target in targets {
+            edges.push((node, target));
+            endpoints.extend([node, target]);
+        }
+    }
+    build(count, edges, options, &mut rng)
+}
+
+/// Watts–Strogatz small world: a ring where every node links to its `k / 2`
+/// nearest neighbors on each side, then each edge is rewired to a random
+/// endpoint with probability `beta`.
+pub fn watts_strogatz(count: u32, k: u32, beta: f64, options: &GeneratorOptions) -> Graph {
+    let mut rng = options.rng();
+    let half = (k / 2).min(count.saturating_sub(1) / 2);
+    let mut chosen = HashSet::new();
+    let mut ring = Vec::new();
+    for u in 0..count {
+        for offset in 1..=half {
+            let v = (u + offset) % count;
+            chosen.insert(key(u, v, true));
+            ring.push((u, v));
+        }
+    }
+    let mut edges = Vec::with_capacity(ring.len());
+    for (u, v) in ring {
+        if !rng.random_bool(beta.clamp(0.0, 1.0)) {
+            edges.push((u, v));
+            continue;
+        }
+        // Give up on rewiring a node that is already linked to everything.
+        let rewired = (0..count).find_map(|_| {
+            let w = rng.random_range(0..count);
+            (w != u && !chosen.contains(&key(u, w, true))).then_some(w)
+        });
+        match rewired {
+            Some(w) => {
+                chosen.remove(&key(u, v, true));
+                chosen.insert(key(u, w, true));
+                edges.push((u, w));
+            }
+            None => edges.push((u, v)),
+        }
+    }
+    build(count, edges, options, &mut rng)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generators::generator::edge_count;
+    use crate::layouts::bipartite::two_coloring;
+    use crate::layouts::hierarchical::feedback_arcs;
+    use crate::layouts::layout::{sorted_ids, undirected_neighbors};
+
+    fn is_connected(graph: &Graph) -> bool {
+        let neighbors = undirected_neighbors(graph);
+        let mut seen = HashSet::from([0]);
+        let mut stack = vec![0];
+        while let Some(node) = stack.pop() {
+            for next in neighbors[&node].iter() {
+                if seen.insert(*next) {
+                    stack.push(*next);
+                }
+            }
+        }
+        seen.len() == graph.nodes.len()
+    }
+
+    #[test]
+    fn test_generators_are_reproducible() -> Result<(), String> {
+        let options = GeneratorOptions {
+            weights: Some((1, 100)),
+            ..GeneratorOptions::with_seed(42)
+        };
+
+        assert_eq!(gnp(30, 0.2, &options), gnp(30, 0.2, &options));
+        assert_eq!(
+            watts_strogatz(30, 4, 0.3, &options),
+            watts_strogatz(30, 4, 0.3, &options)
+        );
+        assert_ne!(
+            gnm(30, 40, &options),
+            gnm(30, 40, &GeneratorOptions::with_seed(43))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_random_structures() -> Result<(), String> {
+        let options = GeneratorOptions::with_seed(7);
+
+        let tree = random_tree(50, &options);
+        assert_eq!(edge_count(&tree), 49);
+        assert!(is_connected(&tree));
+
+        let connected = random_connected(40, 60, &options);
+        assert_eq!(edge_count(&connected), 60);
+        assert!(is_connected(&connected));
+
+        assert_eq!(edge_count(&gnm(10, 100, &options)), 45);
+        assert_eq!(edge_count(&barabasi_albert(100, 2, &options)), 1 + 98 * 2);
+        assert_eq!(edge_count(&watts_strogatz(20, 4, 0.5, &options)), 40);
+        assert!(two_coloring(&random_bipartite(10, 12, 0.5, &options)).1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_random_dag_is_acyclic() -> Result<(), String> {
+        let dag = random_dag(30, 0.3, &GeneratorOptions::with_seed(1));
+        let mut edges = Vec::new();
+        for node in dag.nodes.values() {
+            assert!(
+                node.runtime
+                    .edges
+                    .values()
+                    .all(|edge| edge.runtime.directed)
+            );
+            edges.extend(node.runtime.edges.keys().map(|end| (node.id, *end)));
+        }
+        edges.sort_unstable();
+
+        assert!(feedback_arcs(&sorted_ids(&dag), &edges).is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+use crate::generators::generator::{GeneratorOptions, build};
+use crate::graph::graph::Graph;
+
+/// `rows × columns` lattice, node `r * columns + c` at row `r`, column `c`.
+pub fn grid(rows: u32, columns: u32, options: &GeneratorOptions) -> Graph {
+    let mut edges = Vec::new();
+    for row in 0..rows {
+        for column in 0..columns {
+            let id = row * columns + column;
+            if column + 1 < columns {
+                edges.push((id, id + 1));
+            }
+            if row + 1 < rows {
+                edges.push((id, id + columns));
+            }
+        }
+    }
+    build(rows * columns, edges, options, &mut options.rng())
+}
+
+pub fn complete(count: u32, options: &GeneratorOptions) -> Graph {
+    let mut edges = Vec::new();
+    for u in 0..count {
+        for v in 0..count {
+            if u < v || (u != v && !options.undirected) {
+                edges.push((u, v));
+            }
+        }
+    }
+    build(count, edges, options, &mut options.rng())
+}
+
+/// `0 -> 1 -> ... -> count - 1 -> 0`. With two nodes that is `0 -> 1 -> 0`,
+/// a single edge when undirected.
+pub fn cycle(count: u32, options: &GeneratorOptions) -> Graph {
+    let edges = match count {
+        0 | 1 => Vec::new(),
+        2 if options.undirected => vec![(0, 1)],
+        _ => (0..count).map(|u| (u, (u + 1) % count)).collect(),
+    };
+    build(count, edges, options, &mut options.rng())
+}
+
+/// Node 0 connected to every other node.
+pub fn star(count: u32, options: &GeneratorOptions) -> Graph {
+    let edges = (1..count).map(|leaf| (0, leaf)).collect();
+    build(count, edges, options, &mut options.rng())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generators::generator::edge_count;
+
+    #[test]
+    fn test_structured_edge_counts() -> Result<(), String> {
+        let options = GeneratorOptions::default();
+
+        assert_eq!(edge_count(&grid(3, 4, &options)), 17);
+        assert_eq!(edge_count(&complete(5, &options)), 10);
+        assert_eq!(edge_count(&cycle(6, &options)), 6);
+        assert_eq!(edge_count(&star(6, &options)), 5);
+        let directed = GeneratorOptions {
+            undirected: false,
+            ..GeneratorOptions::default()
+        };
+        assert_eq!(edge_count(&complete(5, &directed)), 20);
+        assert_eq!(edge_count(&cycle(2, &options)), 1);
+        assert_eq!(edge_count(&cycle(2, &directed)), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_grid_neighbors() -> Result<(), String> {
+        let my_graph = grid(3, 3, &GeneratorOptions::default());
+
+        let mut center: Vec<u32> = my_graph.nodes[&4].runtime.edges.keys().copied().collect();
+        center.sort_unstable();
+        assert_eq!(center, vec![1, 3, 5, 7]);
+        Ok(())
+    }
+}
@@ -1,10 +1,10 @@
 use crate::{
     edge::edge_model::{Edge, EdgeSnapshot},
-    node::node_model::{Node, NodeSnapshot},
+    node::node_model::{DEFAULT_RADIUS, Node, NodeSnapshot},
 };
 use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Default, Clone)]
 pub struct Graph {
     pub nodes: HashMap<u32, Node>,
 }
@@ -86,6 +86,11 @@ impl Graph {
     }
     pub fn from_list_of_weighted_edges(edges: Vec<(u32, u32, u32)>, is_undirected: bool) -> Self {
         let mut current_graph = Self::new();
+        let new_edge = if is_undirected {
+            Edge::new_undirected
+        } else {
+            Edge::new
+        };
         for (u, v, w) in edges {
             if !current_graph.nodes.contains_key(&u) {
                 current_graph.nodes.insert(u, Node::new_node_from_id(u));
@@ -100,7 +105,7 @@ impl Graph {
                 .unwrap()
                 .runtime
                 .edges
-                .insert(v, Edge::new(u, v, w));
+                .insert(v, new_edge(u, v, w));
 
             if is_undirected {
                 current_graph
@@ -109,7 +114,7 @@ impl Graph {
                     .unwrap()
                     .runtime
                     .edges
-                    .insert(u, Edge::new(v, u, w));
+                    .insert(u, new_edge(v, u, w));
             }
         }
         return current_graph;
@@ -129,15 +134,70 @@ impl Graph {
     pub fn add_undirected_edge(&mut self, u: u32, v: u32) {
         self.add_directed_edge(u, v);
         self.add_directed_edge(v, u);
+        for (start, end) in [(u, v), (v, u)] {
+            if let Some(edge) = self
+                .nodes
+                .get_mut(&start)
+                .and_then(|node| node.runtime.edges.get_mut(&end))
+            {
+                edge.runtime.directed = false;
+            }
+        }
     }
     pub fn add_new_node_to_graph(&mut self, id: u32, position_x: f64, position_y: f64) {
         self.nodes
             .entry(id)
-            .or_insert(Node::new(id, position_x, position_y, 1.0));
+            .or_insert(Node::new(id, position_x, position_y, DEFAULT_RADIUS));
     }
 
+    /// Removes the node together with every edge pointing to it.
     pub fn remove_node_from_graph(&mut self, id: u32) {
         self.nodes.remove_entry(&id);
+        for node in self.nodes.values_mut() {
+            node.runtime.edges.remove(&id);
+        }
+    }
+
+    pub fn get_edge(&self, start: u32, end: u32) -> Option<&Edge> {
+        self.nodes.get(&start)?.runtime.edges.get(&end)
+    }
+    pub fn get_edge_mut(&mut self, start: u32, end: u32) -> Option<&mut Edge> {
+        self.nodes.get_mut(&start)?.runtime.edges.get_mut(&end)
+    }
+    /// Inserts (or replaces) `edge` when both of its endpoints exist.
+    pub fn insert_edge(&mut self, edge: Edge) {
+        if !self.nodes.contains_key(&edge.runtime.node_end) {
+            return;
+        }
+        if let Some(node) = self.nodes.get_mut(&edge.runtime.node_start) {
+            node.runtime.edges.insert(edge.runtime.node_end, edge);
+        }
+    }
+    pub fn remove_edge(&mut self, start: u32, end: u32) -> Option<Edge> {
+        self.nodes.get_mut(&start)?.runtime.edges.remove(&end)
+    }
+    /// Same nodes and positions with every edge turned around.
+    pub fn reversed(&self) -> Graph {
+        let mut reversed = self.clone();
+        for node in reversed.nodes.values_mut() {
+            node.runtime.edges.clear();
+        }
+        for edge in self.nodes.values().flat_map(|node| node.runtime.edges.values()) {
+            let mut turned = *edge;
+            turned.runtime.node_start = edge.runtime.node_end;
+            turned.runtime.node_end = edge.runtime.node_start;
+            reversed.insert_edge(turned);
+        }
+        reversed
+    }
+    /// Edges of other nodes that end at `id`.
+    pub fn incoming_edges(&self, id: u32) -> Vec<Edge> {
+        self.nodes
+            .values()
+            .filter(|node| node.id != id)
+            .filter_map(|node| node.runtime.edges.get(&id))
+            .copied()
+            .collect()
     }
 }
 #[cfg(test)]
@@ -221,6 +281,33 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_graph_undirected_edges_are_marked() -> Result<(), String> {
+        let mut my_graph = setup_undirected_right_chain();
+        my_graph.add_new_node_to_graph(7, 0.0, 0.0);
+        my_graph.add_undirected_edge(6, 7);
+        let snapshot = my_graph.to_snapshot()?;
+
+        assert!(
+            snapshot.edges.iter().all(|edge| !edge.directed),
+            "Undirected edges should not be drawn with arrows"
+        );
+        assert!(setup_left_chain().to_snapshot()?.edges[0].directed);
+        Ok(())
+    }
+
+    #[test]
+    fn test_reversed_graph_turns_edges() -> Result<(), String> {
+        let my_graph = Graph::from_list_of_weighted_edges(vec![(0, 1, 4), (1, 2, 7)], false);
+        let reversed = my_graph.reversed();
+
+        assert_eq!(reversed.get_edge(1, 0).map(|edge| edge.runtime.weight), Some(4));
+        assert_eq!(reversed.get_edge(2, 1).map(|edge| edge.runtime.node_end), Some(1));
+        assert!(reversed.get_edge(0, 1).is_none());
+        assert_eq!(reversed.reversed(), my_graph);
+        Ok(())
+    }
+
     #[test]
     fn test_graph_snapshot() -> Result<(), String> {
         let my_graph = setup_left_chain();
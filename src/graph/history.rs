@@ -0,0 +1,490 @@
+use crate::{edge::edge_model::Edge, graph::graph::Graph, node::node_model::Node};
+use ordered_float::OrderedFloat;
+
+/// A reversible mutation of a `Graph`. Each variant carries everything it
+/// needs to be undone, so commands that delete data capture it up front.
+#[derive(Debug, PartialEq, Clone)]
+pub enum GraphCommand {
+    AddNode {
+        id: u32,
+        position_x: f64,
+        position_y: f64,
+    },
+    RemoveNode {
+        node: Node,
+        incoming: Vec<Edge>,
+    },
+    AddEdge {
+        edge: Edge,
+        replaced: Vec<Edge>,
+    },
+    RemoveEdge {
+        edge: Edge,
+    },
+    MoveNode {
+        id: u32,
+        from: (f64, f64),
+        to: (f64, f64),
+    },
+    /// Pinned nodes keep their position when a layout runs.
+    SetPinned {
+        id: u32,
+        from: bool,
+        to: bool,
+    },
+    ChangeWeight {
+        start: u32,
+        end: u32,
+        from: u32,
+        to: u32,
+    },
+    ChangeCost {
+        start: u32,
+        end: u32,
+        from: i32,
+        to: i32,
+    },
+    /// Turns `start -> end` into an undirected pair or back into a single
+    /// directed edge, remembering what was stored in the reverse slot.
+    ToggleDirection {
+        start: u32,
+        end: u32,
+        was_directed: bool,
+        reverse: Option<Edge>,
+    },
+}
+
+impl GraphCommand {
+    pub fn add_node(
+        graph: &Graph,
+        id: u32,
+        position_x: f64,
+        position_y: f64,
+    ) -> Result<Self, String> {
+        if graph.nodes.contains_key(&id) {
+            return Err(format!("Node {} already exists", id));
+        }
+        Ok(GraphCommand::AddNode {
+            id,
+            position_x,
+            position_y,
+        })
+    }
+    pub fn remove_node(graph: &Graph, id: u32) -> Result<Self, String> {
+        let node = graph
+            .nodes
+            .get(&id)
+            .ok_or(format!("Node {} does not exist", id))?;
+        Ok(GraphCommand::RemoveNode {
+            node: node.clone(),
+            incoming: graph.incoming_edges(id),
+        })
+    }
+    pub fn add_edge(
+        graph: &Graph,
+        start: u32,
+        end: u32,
+        weight: u32,
+        directed: bool,
+    ) -> Result<Self, String> {
+        for id in [start, end] {
+            if !graph.nodes.contains_key(&id) {
+                return Err(format!("Node {} does not exist", id));
+            }
+        }
+        let edge = if directed {
+            Edge::new(start, end, weight)
+        } else {
+            Edge::new_undirected(start, end, weight)
+        };
+        // A directed edge over an undirected pair replaces both halves.
+        let splits_pair = start != end
+            && graph
+                .get_edge(start, end)
+                .is_some_and(|edge| !edge.runtime.directed);
+        let mut slots = vec![(start, end)];
+        if !directed || splits_pair {
+            slots.push((end, start));
+        }
+        Ok(GraphCommand::AddEdge {
+            edge,
+            replaced: slots
+                .into_iter()
+                .filter_map(|(u, v)| graph.get_edge(u, v).copied())
+                .collect(),
+        })
+    }
+    pub fn remove_edge(graph: &Graph, start: u32, end: u32) -> Result<Self, String> {
+        let edge = graph
+            .get_edge(start, end)
+            .ok_or(format!("Edge {} -> {} does not exist", start, end))?;
+        Ok(GraphCommand::RemoveEdge { edge: *edge })
+    }
+    pub fn move_node(
+        graph: &Graph,
+        id: u32,
+        position_x: f64,
+        position_y: f64,
+    ) -> Result<Self, String> {
+        let node = graph
+            .nodes
+            .get(&id)
+            .ok_or(format!("Node {} does not exist", id))?;
+        Ok(GraphCommand::MoveNode {
+            id,
+            from: (
+                node.gui_model.position_x.into_inner(),
+                node.gui_model.position_y.into_inner(),
+            ),
+            to: (position_x, position_y),
+        })
+    }
+    pub fn set_pinned(graph: &Graph, id: u32, pinned: bool) -> Result<Self, String> {
+        let node = graph
+            .nodes
+            .get(&id)
+            .ok_or(format!("Node {} does not exist", id))?;
+        Ok(GraphCommand::SetPinned {
+            id,
+            from: node.gui_model.pinned,
+            to: pinned,
+        })
+    }
+    pub fn change_weight(graph: &Graph, start: u32, end: u32, weight: u32) -> Result<Self, String> {
+        let edge = graph
+            .get_edge(start, end)
+            .ok_or(format!("Edge {} -> {} does not exist", start, end))?;
+        Ok(GraphCommand::ChangeWeight {
+            start,
+            end,
+            from: edge.runtime.weight,
+            to: weight,
+        })
+    }
+    pub fn change_cost(graph: &Graph, start: u32, end: u32, cost: i32) -> Result<Self, String> {
+        let edge = graph
+            .get_edge(start, end)
+            .ok_or(format!("Edge {} -> {} does not exist", start, end))?;
+        Ok(GraphCommand::ChangeCost {
+            start,
+            end,
+            from: edge.runtime.cost,
+            to: cost,
+        })
+    }
+    pub fn toggle_direction(graph: &Graph, start: u32, end: u32) -> Result<Self, String> {
+        let edge = graph
+            .get_edge(start, end)
+            .ok_or(format!("Edge {} -> {} does not exist", start, end))?;
+        Ok(GraphCommand::ToggleDirection {
+            start,
+            end,
+            was_directed: edge.runtime.directed,
+            reverse: graph.get_edge(end, start).copied(),
+        })
+    }
+
+    pub fn apply(&self, graph: &mut Graph) {
+        match self {
+            GraphCommand::AddNode {
+                id,
+                position_x,
+                position_y,
+            } => graph.add_new_node_to_graph(*id, *position_x, *position_y),
+            GraphCommand::RemoveNode { node, .. } => graph.remove_node_from_graph(node.id),
+            GraphCommand::AddEdge { edge, replaced } => {
+                let (start, end) = (edge.runtime.node_start, edge.runtime.node_end);
+                let reverse_replaced = replaced
+                    .iter()
+                    .any(|old| (old.runtime.node_start, old.runtime.node_end) == (end, start));
+                if edge.runtime.directed && reverse_replaced {
+                    graph.remove_edge(end, start);
+                }
+                graph.insert_edge(*edge);
+                if !edge.runtime.directed {
+                    let mut reverse = *edge;
+                    reverse.runtime.node_start = edge.runtime.node_end;
+                    reverse.runtime.node_end = edge.runtime.node_start;
+                    graph.insert_edge(reverse);
+                }
+            }
+            GraphCommand::RemoveEdge { edge } => {
+                graph.remove_edge(edge.runtime.node_start, edge.runtime.node_end);
+                if !edge.runtime.directed {
+                    graph.remove_edge(edge.runtime.node_end, edge.runtime.node_start);
+                }
+            }
+            GraphCommand::MoveNode { id, to, .. } => set_position(graph, *id, *to),
+            GraphCommand::SetPinned { id, to, .. } => set_pinned(graph, *id, *to),
+            GraphCommand::ChangeWeight { start, end, to, .. } => {
+                set_weight(graph, *start, *end, *to)
+            }
+            GraphCommand::ChangeCost { start, end, to, .. } => set_cost(graph, *start, *end, *to),
+            GraphCommand::ToggleDirection {
+                start,
+                end,
+                was_directed,
+                ..
+            } => {
+                if let Some(edge) = graph.get_edge_mut(*start, *end) {
+                    edge.runtime.directed = !was_directed;
+                }
+                if *was_directed {
+                    let edge = graph.get_edge(*start, *end);
+                    let weight = edge.map_or(1, |e| e.runtime.weight);
+                    let mut reverse = Edge::new_undirected(*end, *start, weight);
+                    reverse.runtime.cost = edge.map_or(0, |e| e.runtime.cost);
+                    graph.insert_edge(reverse);
+                } else {
+                    graph.remove_edge(*end, *start);
+                }
+            }
+        }
+    }
+
+    pub fn revert(&self, graph: &mut Graph) {
+        match self {
+            GraphCommand::AddNode { id, .. } => graph.remove_node_from_graph(*id),
+            GraphCommand::RemoveNode { node, incoming } => {
+                graph.nodes.insert(node.id, node.clone());
+                for edge in incoming {
+                    graph.insert_edge(*edge);
+                }
+            }
+            GraphCommand::AddEdge { edge, replaced } => {
+                GraphCommand::RemoveEdge { edge: *edge }.apply(graph);
+                for replaced in replaced {
+                    graph.insert_edge(*replaced);
+                }
+            }
+            GraphCommand::RemoveEdge { edge } => {
+                GraphCommand::AddEdge {
+                    edge: *edge,
+                    replaced: Vec::new(),
+                }
+                .apply(graph);
+            }
+            GraphCommand::MoveNode { id, from, .. } => set_position(graph, *id, *from),
+            GraphCommand::SetPinned { id, from, .. } => set_pinned(graph, *id, *from),
+            GraphCommand::ChangeWeight {
+                start, end, from, ..
+            } => set_weight(graph, *start, *end, *from),
+            GraphCommand::ChangeCost {
+                start, end, from, ..
+            } => set_cost(graph, *start, *end, *from),
+            GraphCommand::ToggleDirection {
+                start,
+                end,
+                was_directed,
+                reverse,
+            } => {
+                if let Some(edge) = graph.get_edge_mut(*start, *end) {
+                    edge.runtime.directed = *was_directed;
+                }
+                graph.remove_edge(*end, *start);
+                if let Some(reverse) = reverse {
+                    graph.insert_edge(*reverse);
+                }
+            }
+        }
+    }
+}
+
+fn set_position(graph: &mut Graph, id: u32, (position_x, position_y): (f64, f64)) {
+    if let Some(node) = graph.nodes.get_mut(&id) {
+        node.gui_model.position_x = OrderedFloat(position_x);
+        node.gui_model.position_y = OrderedFloat(position_y);
+    }
+}
+fn set_pinned(graph: &mut Graph, id: u32, pinned: bool) {
+    if let Some(node) = graph.nodes.get_mut(&id) {
+        node.gui_model.pinned = pinned;
+    }
+}
+fn set_weight(graph: &mut Graph, start: u32, end: u32, weight: u32) {
+    let directed = match graph.get_edge_mut(start, end) {
+        Some(edge) => {
+            edge.runtime.weight = weight;
+            edge.runtime.directed
+        }
+        None => return,
+    };
+    if !directed && let Some(reverse) = graph.get_edge_mut(end, start) {
+        reverse.runtime.weight = weight;
+    }
+}
+fn set_cost(graph: &mut Graph, start: u32, end: u32, cost: i32) {
+    let directed = match graph.get_edge_mut(start, end) {
+        Some(edge) => {
+            edge.runtime.cost = cost;
+            edge.runtime.directed
+        }
+        None => return,
+    };
+    if !directed && let Some(reverse) = graph.get_edge_mut(end, start) {
+        reverse.runtime.cost = cost;
+    }
+}
+
+/// Undo/redo stacks of applied commands. Commands executed between
+/// `begin_group` and `end_group` (e.g. every step of a drag) are undone as
+/// a single entry.
+#[derive(Debug, Default, Clone)]
+pub struct History {
+    undo_stack: Vec<Vec<GraphCommand>>,
+    redo_stack: Vec<Vec<GraphCommand>>,
+    open_group: Option<Vec<GraphCommand>>,
+}
+
+impl History {
+    pub fn new() -> Self {
+        History::default()
+    }
+    pub fn execute(&mut self, graph: &mut Graph, command: GraphCommand) {
+        command.apply(graph);
+        match self.open_group.as_mut() {
+            Some(group) => group.push(command),
+            None => self.undo_stack.push(vec![command]),
+        }
+        self.redo_stack.clear();
+    }
+    pub fn begin_group(&mut self) {
+        self.end_group();
+        self.open_group = Some(Vec::new());
+    }
+    pub fn end_group(&mut self) {
+        if let Some(group) = self.open_group.take()
+            && !group.is_empty()
+        {
+            self.undo_stack.push(group);
+        }
+    }
+    pub fn is_grouping(&self) -> bool {
+        self.open_group.is_some()
+    }
+    pub fn undo(&mut self, graph: &mut Graph) -> bool {
+        self.end_group();
+        let Some(entry) = self.undo_stack.pop() else {
+            return false;
+        };
+        for command in entry.iter().rev() {
+            command.revert(graph);
+        }
+        self.redo_stack.push(entry);
+        true
+    }
+    pub fn redo(&mut self, graph: &mut Graph) -> bool {
+        self.end_group();
+        let Some(entry) = self.redo_stack.pop() else {
+            return false;
+        };
+        for command in entry.iter() {
+            command.apply(graph);
+        }
+        self.undo_stack.push(entry);
+        true
+    }
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty() || self.open_group.as_ref().is_some_and(|g| !g.is_empty())
+    }
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn setup_triangle() -> Graph {
+        let edges = vec![(0, 1, 4), (1, 2, 5), (2, 0, 6)];
+        Graph::from_list_of_weighted_edges(edges, true)
+    }
+
+    #[test]
+    fn test_history_undo_redo_remove_node() -> Result<(), String> {
+        let mut my_graph = setup_triangle();
+        let original = my_graph.clone();
+        let mut history = History::new();
+
+        let command = GraphCommand::remove_node(&my_graph, 1)?;
+        history.execute(&mut my_graph, command);
+        assert!(my_graph.incoming_edges(1).is_empty());
+        assert!(my_graph.to_snapshot().is_ok(), "No dangling edges left");
+
+        assert!(history.undo(&mut my_graph));
+        assert_eq!(my_graph, original);
+
+        assert!(history.redo(&mut my_graph));
+        assert!(!my_graph.nodes.contains_key(&1));
+        Ok(())
+    }
+
+    #[test]
+    fn test_history_every_command_is_reversible() -> Result<(), String> {
+        let mut my_graph = setup_triangle();
+        let original = my_graph.clone();
+        let mut history = History::new();
+
+        let command = GraphCommand::add_node(&my_graph, 3, 10.0, 20.0)?;
+        history.execute(&mut my_graph, command);
+        let command = GraphCommand::add_edge(&my_graph, 3, 0, 7, false)?;
+        history.execute(&mut my_graph, command);
+        let command = GraphCommand::change_weight(&my_graph, 0, 3, 9)?;
+        history.execute(&mut my_graph, command);
+        assert_eq!(my_graph.get_edge(3, 0).unwrap().runtime.weight, 9);
+        let command = GraphCommand::change_cost(&my_graph, 3, 0, -2)?;
+        history.execute(&mut my_graph, command);
+        assert_eq!(my_graph.get_edge(0, 3).unwrap().runtime.cost, -2);
+        let command = GraphCommand::toggle_direction(&my_graph, 0, 1)?;
+        history.execute(&mut my_graph, command);
+        assert!(my_graph.get_edge(1, 0).is_none());
+        let command = GraphCommand::remove_edge(&my_graph, 1, 2)?;
+        history.execute(&mut my_graph, command);
+
+        while history.undo(&mut my_graph) {}
+        assert_eq!(my_graph, original);
+        assert!(history.can_redo());
+        Ok(())
+    }
+
+    #[test]
+    fn test_history_directed_edge_over_undirected_pair() -> Result<(), String> {
+        let mut my_graph = setup_triangle();
+        let original = my_graph.clone();
+        let mut history = History::new();
+
+        let command = GraphCommand::add_edge(&my_graph, 0, 1, 3, true)?;
+        history.execute(&mut my_graph, command);
+        assert!(my_graph.get_edge(0, 1).unwrap().runtime.directed);
+        assert!(
+            my_graph.get_edge(1, 0).is_none(),
+            "No half pair left behind"
+        );
+
+        history.undo(&mut my_graph);
+        assert_eq!(my_graph, original);
+        Ok(())
+    }
+
+    #[test]
+    fn test_history_groups_drag_moves() -> Result<(), String> {
+        let mut my_graph = setup_triangle();
+        let mut history = History::new();
+
+        history.begin_group();
+        let command = GraphCommand::set_pinned(&my_graph, 2, true)?;
+        history.execute(&mut my_graph, command);
+        for step in 1..=5 {
+            let command = GraphCommand::move_node(&my_graph, 2, step as f64, 0.0)?;
+            history.execute(&mut my_graph, command);
+        }
+        history.end_group();
+        assert_eq!(my_graph.nodes[&2].gui_model.position_x, OrderedFloat(5.0));
+
+        history.undo(&mut my_graph);
+        assert_eq!(my_graph.nodes[&2].gui_model.position_x, OrderedFloat(0.0));
+        assert!(!my_graph.nodes[&2].gui_model.pinned);
+        assert!(!history.can_undo(), "The drag should be a single entry");
+        Ok(())
+    }
+}
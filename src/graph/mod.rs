@@ -1 +1,3 @@
-pub mod graph;
\ No newline at end of file
+pub mod graph;
+pub mod history;
+pub mod parse;
\ No newline at end of file
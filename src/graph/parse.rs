@@ -0,0 +1,119 @@
+use crate::{edge::edge_model::Edge, graph::graph::Graph};
+
+/// A graph read from a competitive programming style edge list.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ParsedGraph {
+    pub graph: Graph,
+    /// The header named two sides, `n1 n2 m`; right nodes follow the left ones.
+    pub bipartite: bool,
+}
+
+/// Largest node count a header may ask for; a pasted list is never bigger,
+/// and a typo should not allocate billions of nodes.
+pub const MAX_PARSED_NODES: u32 = 100_000;
+
+/// Parses the Codeforces input format with undirected, 1-indexed nodes: the
+/// header `n m` followed by `m` edges `u v`, or `u v w` when every edge has a
+/// weight. The header `n1 n2 m` reads a bipartite graph whose `v` counts from
+/// 1 on the right side. Only the number of tokens matters, not the line
+/// breaks, so the list can be pasted on one line. An edge listed twice is
+/// rejected rather than letting the last weight win.
+pub fn parse_edge_list(input: &str) -> Result<ParsedGraph, String> {
+    let tokens = input
+        .split_whitespace()
+        .map(|token| {
+            token
+                .parse::<u32>()
+                .map_err(|_| format!("Invalid number: {}", token))
+        })
+        .collect::<Result<Vec<u32>, String>>()?;
+    // The header size is the one that leaves 2 or 3 numbers per edge.
+    let fits = |header: usize| {
+        tokens.get(header - 1).is_some_and(|m| {
+            let (rest, m) = ((tokens.len() - header) as u64, *m as u64);
+            rest == 2 * m || rest == 3 * m
+        })
+    };
+    let (offset, n, m) = match tokens[..] {
+        [n, m, ..] if fits(2) => (None, n, m),
+        [n1, n2, m, ..] if fits(3) => {
+            let n = n1
+                .checked_add(n2)
+                .ok_or(format!("Too many nodes: {} + {}", n1, n2))?;
+            (Some(n1), n, m)
+        }
+        [] => return Err(String::from("Empty input")),
+        _ => return Err(String::from("Expected a header `n m` and m edges")),
+    };
+    if n > MAX_PARSED_NODES {
+        return Err(format!(
+            "Too many nodes: {} (at most {})",
+            n, MAX_PARSED_NODES
+        ));
+    }
+    let width = match m {
+        0 => 2,
+        m => (tokens.len() - offset.map_or(2, |_| 3)) / m as usize,
+    };
+    let mut graph = Graph::new();
+    for id in 1..=n {
+        graph.add_new_node_to_graph(id, 0.0, 0.0);
+    }
+    for edge in tokens[tokens.len() - m as usize * width..].chunks(width) {
+        let (u, v) = (edge[0], edge[1] + offset.unwrap_or(0));
+        let weight = edge.get(2).copied().unwrap_or(1);
+        if !(1..=n).contains(&u) || !(1..=n).contains(&v) || offset.is_some_and(|n1| u > n1) {
+            return Err(format!("Edge out of range: {} {}", edge[0], edge[1]));
+        }
+        if graph.get_edge(u, v).is_some() {
+            return Err(format!("Duplicate edge: {} {}", edge[0], edge[1]));
+        }
+        graph.insert_edge(Edge::new_undirected(u, v, weight));
+        graph.insert_edge(Edge::new_undirected(v, u, weight));
+    }
+    Ok(ParsedGraph {
+        graph,
+        bipartite: offset.is_some(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_edge_list() -> Result<(), String> {
+        let parsed = parse_edge_list("4 3\n1 2 1\n2 3 7\n\n3 1 1\n")?;
+
+        assert!(!parsed.bipartite);
+        assert_eq!(parsed.graph.nodes.len(), 4);
+        assert_eq!(parsed.graph.get_edge(3, 2).unwrap().runtime.weight, 7);
+        assert!(!parsed.graph.get_edge(1, 2).unwrap().runtime.directed);
+        assert!(parse_edge_list("3 2\n1 2\n").is_err());
+        assert!(parse_edge_list("3 2 1 2 2 3").is_ok());
+        assert!(parse_edge_list("2 1\n1 3\n").is_err());
+        assert!(
+            parse_edge_list("3 2\n1 2 4\n2 1 5\n").is_err(),
+            "Parallel edges"
+        );
+        assert!(parse_edge_list("4000000000 0").is_err());
+        assert!(
+            parse_edge_list("4294967295 1 0").is_err(),
+            "n1 + n2 overflows"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_bipartite_edge_list() -> Result<(), String> {
+        let parsed = parse_edge_list("2 3 3\n1 1\n1 3\n2 2\n")?;
+
+        assert!(parsed.bipartite);
+        assert_eq!(parsed.graph.nodes.len(), 5);
+        assert!(parsed.graph.get_edge(1, 3).is_some());
+        assert!(parsed.graph.get_edge(1, 5).is_some());
+        assert!(parsed.graph.get_edge(2, 4).is_some());
+        assert!(parse_edge_list("2 3 1\n3 1\n").is_err());
+        Ok(())
+    }
+}
@@ -1,88 +1,450 @@
-use iced::{Color, Point, widget::canvas};
-use ordered_float::OrderedFloat;
+use crate::edge::edge_model::GUIModel as EdgeModel;
+use crate::layouts::layout::GOLDEN_ANGLE;
+use crate::node::node_model::GUIModel as NodeModel;
+use iced::{Color, Pixels, Point, Vector, alignment, widget::canvas};
 
-pub trait GUIFriendly {
-    fn update_gui(&self, frame: &mut canvas::Frame);
-}
+const MIN_ZOOM: f32 = 0.05;
+const MAX_ZOOM: f32 = 20.0;
+const LABEL_SIZE: f32 = 14.0;
+const MIN_LABEL_SIZE: f32 = 6.0;
+const EDGE_LABEL_OFFSET: f32 = 10.0;
+const LABEL_COLOR: Color = Color::BLACK;
+const ANNOTATION_COLOR: Color = Color::from_rgb(0.7, 0.9, 1.0);
+const EDGE_LABEL_COLOR: Color = Color::from_rgb(1.0, 0.85, 0.4);
+const HIGHLIGHT_COLOR: Color = Color::from_rgb(1.0, 0.35, 0.2);
+const HIGHLIGHT_WIDTH: f32 = 3.0;
+const MARK_COLOR: Color = Color::from_rgb(1.0, 0.85, 0.4);
+/// Light fills that keep the black node labels readable.
+const PALETTE: [Color; 8] = [
+    Color::from_rgb(0.55, 0.8, 1.0),
+    Color::from_rgb(1.0, 0.75, 0.5),
+    Color::from_rgb(0.6, 0.9, 0.6),
+    Color::from_rgb(1.0, 0.6, 0.65),
+    Color::from_rgb(0.8, 0.7, 1.0),
+    Color::from_rgb(1.0, 0.95, 0.55),
+    Color::from_rgb(0.6, 0.95, 0.9),
+    Color::from_rgb(0.95, 0.7, 0.9),
+];
+const ARROW_SIZE: f32 = 10.0;
+const CURVE_BEND: f32 = 0.15;
+/// How far above the node a self-loop's control point sits, in radii.
+const LOOP_HEIGHT: f32 = 3.0;
+
+/// Values of `EdgeModel::line_type`; anything else is drawn solid.
+pub const LINE_SOLID: u32 = 1;
+pub const LINE_DASHED: u32 = 2;
+pub const LINE_DOTTED: u32 = 3;
 
-#[derive(Debug, Hash, Clone, Copy)]
-pub struct EdgeModel {
-    pub line_type: u32,
-    pub thickness: u32,
+/// Maps graph coordinates to canvas pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Camera {
+    pub offset: Vector,
+    pub zoom: f32,
 }
-impl Default for EdgeModel {
-    fn default() -> EdgeModel {
-        EdgeModel {
-            line_type: 0,
-            thickness: 10,
+impl Default for Camera {
+    fn default() -> Camera {
+        Camera {
+            offset: Vector::new(0.0, 0.0),
+            zoom: 1.0,
         }
     }
 }
+impl Camera {
+    pub fn to_screen(&self, point: Point) -> Point {
+        Point::new(
+            point.x * self.zoom + self.offset.x,
+            point.y * self.zoom + self.offset.y,
+        )
+    }
+    pub fn to_world(&self, point: Point) -> Point {
+        Point::new(
+            (point.x - self.offset.x) / self.zoom,
+            (point.y - self.offset.y) / self.zoom,
+        )
+    }
+    /// Zooms by `factor` keeping the graph point under `anchor` in place.
+    pub fn zoom_at(&mut self, anchor: Point, factor: f32) {
+        let world = self.to_world(anchor);
+        self.zoom = (self.zoom * factor).clamp(MIN_ZOOM, MAX_ZOOM);
+        self.offset = Vector::new(
+            anchor.x - world.x * self.zoom,
+            anchor.y - world.y * self.zoom,
+        );
+    }
+    /// Font size for a label of `size` at the current zoom, or `None` when
+    /// it would be too small to read.
+    pub fn label_size(&self, size: f32) -> Option<f32> {
+        let scaled = size * self.zoom;
+        (scaled >= MIN_LABEL_SIZE).then_some(scaled)
+    }
+}
 
-#[derive(PartialEq, Debug, Clone)]
-pub struct NodeModel {
-    pub position_x: OrderedFloat<f64>,
-    pub position_y: OrderedFloat<f64>,
-    pub radius: OrderedFloat<f64>,
+pub fn node_to_point(node: &NodeModel) -> Point {
+    Point::new(
+        node.position_x.into_inner() as f32,
+        node.position_y.into_inner() as f32,
+    )
 }
-impl Default for NodeModel {
-    fn default() -> NodeModel {
-        NodeModel {
-            position_x: OrderedFloat(0.0),
-            position_y: OrderedFloat(0.0),
-            radius: OrderedFloat(1.0),
-        }
+fn draw_label(
+    frame: &mut canvas::Frame,
+    content: String,
+    position: Point,
+    size: f32,
+    color: Color,
+    horizontal_alignment: alignment::Horizontal,
+) {
+    frame.fill_text(canvas::Text {
+        content,
+        position,
+        color,
+        size: Pixels(size),
+        horizontal_alignment,
+        vertical_alignment: alignment::Vertical::Center,
+        ..Default::default()
+    });
+}
+/// Fill for color class `index`, e.g. a component id. Past the fixed palette,
+/// hues are spread by the golden angle so neighboring indices stay apart.
+pub fn palette_color(index: usize) -> Color {
+    if let Some(color) = PALETTE.get(index) {
+        return *color;
     }
+    let hue = (index as f64 * GOLDEN_ANGLE.to_degrees() % 360.0 / 60.0) as f32;
+    let (saturation, value) = (0.45, 1.0);
+    let chroma = value * saturation;
+    let x = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+    let (r, g, b) = match hue as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = value - chroma;
+    Color::from_rgb(r + m, g + m, b + m)
 }
 
-#[derive(Clone)]
-pub enum FinalGUIModel {
-    Node(NodeModel),
-    Edge(EdgeModel),
+/// How a node should be drawn, decided by the algorithm overlay.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NodeStyle {
+    pub fill: Color,
+    /// Draws a colored ring, e.g. for nodes of a cycle.
+    pub highlighted: bool,
 }
-impl FinalGUIModel {
-    pub fn as_node(&self) -> Option<&NodeModel> {
-        if let FinalGUIModel::Node(n) = self {
-            Some(n)
-        } else {
-            None
+impl Default for NodeStyle {
+    fn default() -> Self {
+        NodeStyle {
+            fill: Color::WHITE,
+            highlighted: false,
         }
     }
+}
+
+/// Draws the node circle with its id (or label) centered inside and an
+/// optional annotation to its right.
+pub fn draw_node(
+    frame: &mut canvas::Frame,
+    camera: &Camera,
+    id: u32,
+    node: &NodeModel,
+    annotation: Option<&str>,
+    style: NodeStyle,
+) {
+    let center = camera.to_screen(node_to_point(node));
+    let radius = node.radius.into_inner() as f32 * camera.zoom;
+    let node_circle = canvas::Path::circle(center, radius);
+    frame.fill(&node_circle, style.fill);
+    if style.highlighted {
+        frame.stroke(
+            &node_circle,
+            canvas::Stroke::default()
+                .with_color(HIGHLIGHT_COLOR)
+                .with_width(HIGHLIGHT_WIDTH * camera.zoom),
+        );
+    }
 
-    pub fn as_edge(&self) -> Option<&EdgeModel> {
-        if let FinalGUIModel::Edge(e) = self {
-            Some(e)
-        } else {
-            None
+    if let Some(size) = camera.label_size(LABEL_SIZE) {
+        let label = node.label.clone().unwrap_or_else(|| id.to_string());
+        draw_label(
+            frame,
+            label,
+            center,
+            size.min(radius * 1.2),
+            LABEL_COLOR,
+            alignment::Horizontal::Center,
+        );
+        if let Some(annotation) = annotation {
+            let position = Point::new(center.x + radius + size * 0.3, center.y - radius);
+            draw_label(
+                frame,
+                String::from(annotation),
+                position,
+                size,
+                ANNOTATION_COLOR,
+                alignment::Horizontal::Left,
+            );
         }
     }
 }
+/// How an edge should be drawn, decided from the rest of the graph.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct EdgeShape {
+    pub directed: bool,
+    /// Bends the edge to its left so that `u -> v` and `v -> u` don't overlap.
+    pub curved: bool,
+    /// Set by the algorithm overlay, e.g. for the edges of a cycle.
+    pub highlighted: bool,
+    /// Dashed and tinted unless also highlighted, e.g. for frontier edges.
+    pub marked: bool,
+    /// Starts and ends at the same node; drawn as a loop above it.
+    pub self_loop: bool,
+}
 
-fn node_to_point(node: &NodeModel) -> Point {
-    Point::new(
-        node.position_x.into_inner() as f32,
-        node.position_y.into_inner() as f32,
-    )
+/// Screen-space outline of an edge, already trimmed at both node circles.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EdgeGeometry {
+    pub from: Point,
+    pub control: Option<Point>,
+    pub to: Point,
+    /// Unit direction of the edge where it reaches `to`.
+    pub tangent: Vector,
+    pub middle: Point,
+    pub normal: Vector,
+}
+
+fn length(vector: Vector) -> f32 {
+    (vector.x * vector.x + vector.y * vector.y).sqrt()
 }
-pub fn draw_node(frame: &mut canvas::Frame, node: &NodeModel) {
-    let node_circle = canvas::Path::circle(node_to_point(node), node.radius.into_inner() as f32);
-    frame.fill(&node_circle, Color::WHITE);
+fn unit(vector: Vector) -> Vector {
+    vector * (1.0 / length(vector))
 }
-pub fn draw_edge(frame: &mut canvas::Frame, edge: &EdgeModel, node_start:  &NodeModel, node_end: &NodeModel) {
-    let line = canvas::Path::line(node_to_point(&node_start), node_to_point(&node_end));
+
+pub fn edge_geometry(
+    start: Point,
+    end: Point,
+    start_radius: f32,
+    end_radius: f32,
+    curved: bool,
+) -> Option<EdgeGeometry> {
+    let direction = end - start;
+    let distance = length(direction);
+    if distance <= start_radius + end_radius {
+        return None;
+    }
+    let normal = Vector::new(-direction.y / distance, direction.x / distance);
+    let center = Point::new((start.x + end.x) / 2.0, (start.y + end.y) / 2.0);
+    if !curved {
+        let tangent = unit(direction);
+        return Some(EdgeGeometry {
+            from: start + tangent * start_radius,
+            control: None,
+            to: end - tangent * end_radius,
+            tangent,
+            middle: center,
+            normal,
+        });
+    }
+    let control = center + normal * (distance * CURVE_BEND);
+    let tangent = unit(end - control);
+    Some(EdgeGeometry {
+        from: start + unit(control - start) * start_radius,
+        control: Some(control),
+        to: end - tangent * end_radius,
+        tangent,
+        // Midpoint of the quadratic curve through `control`.
+        middle: center + normal * (distance * CURVE_BEND / 2.0),
+        normal,
+    })
+}
+
+/// A loop leaving and re-entering the top of the node circle at `center`.
+pub fn loop_geometry(center: Point, radius: f32) -> EdgeGeometry {
+    let (side, rise) = (radius * 0.5, radius * 0.866);
+    let from = Point::new(center.x - side, center.y - rise);
+    let to = Point::new(center.x + side, center.y - rise);
+    let control = Point::new(center.x, center.y - radius * LOOP_HEIGHT);
+    EdgeGeometry {
+        from,
+        control: Some(control),
+        to,
+        tangent: unit(to - control),
+        // Apex of the quadratic curve.
+        middle: Point::new(center.x, (center.y - rise + control.y) / 2.0),
+        normal: Vector::new(0.0, -1.0),
+    }
+}
+
+fn line_dash(line_type: u32, zoom: f32) -> Vec<f32> {
+    match line_type {
+        LINE_DASHED => vec![8.0 * zoom, 6.0 * zoom],
+        LINE_DOTTED => vec![2.0 * zoom, 4.0 * zoom],
+        _ => Vec::new(),
+    }
+}
+
+/// Draws the edge between the two node circles, with an arrowhead when it
+/// is directed and its label (usually the weight) pushed off the line.
+pub fn draw_edge(
+    frame: &mut canvas::Frame,
+    camera: &Camera,
+    edge: &EdgeModel,
+    node_start: &NodeModel,
+    node_end: &NodeModel,
+    shape: EdgeShape,
+    label: Option<&str>,
+) {
+    let start_radius = node_start.radius.into_inner() as f32 * camera.zoom;
+    let geometry = match shape.self_loop {
+        true => Some(loop_geometry(
+            camera.to_screen(node_to_point(node_start)),
+            start_radius,
+        )),
+        false => edge_geometry(
+            camera.to_screen(node_to_point(node_start)),
+            camera.to_screen(node_to_point(node_end)),
+            start_radius,
+            node_end.radius.into_inner() as f32 * camera.zoom,
+            shape.curved,
+        ),
+    };
+    let Some(geometry) = geometry else {
+        return;
+    };
+    let (color, thickness, line_type) = match (shape.highlighted, shape.marked) {
+        (true, _) => (HIGHLIGHT_COLOR, edge.thickness as f32 + 1.0, edge.line_type),
+        (false, true) => (MARK_COLOR, edge.thickness as f32, LINE_DASHED),
+        (false, false) => (Color::WHITE, edge.thickness as f32, edge.line_type),
+    };
+    let width = thickness * camera.zoom;
+    let arrow_length = (ARROW_SIZE * camera.zoom).max(width * 3.0);
+
+    // Stop the line at the base of the arrowhead so it doesn't poke through.
+    let line_end = if shape.directed {
+        geometry.to - geometry.tangent * arrow_length * 0.8
+    } else {
+        geometry.to
+    };
+    let line = canvas::Path::new(|builder| {
+        builder.move_to(geometry.from);
+        match geometry.control {
+            Some(control) => builder.quadratic_curve_to(control, line_end),
+            None => builder.line_to(line_end),
+        }
+    });
+    let segments = line_dash(line_type, camera.zoom);
     frame.stroke(
         &line,
         canvas::Stroke {
-            width: edge.thickness as f32,
-            style: canvas::Style::Solid(Color::WHITE),
+            width,
+            style: canvas::Style::Solid(color),
+            line_dash: canvas::LineDash {
+                segments: &segments,
+                offset: 0,
+            },
             ..Default::default()
         },
     );
+
+    if shape.directed {
+        let base = geometry.to - geometry.tangent * arrow_length;
+        let side = Vector::new(-geometry.tangent.y, geometry.tangent.x) * (arrow_length / 2.0);
+        let arrow = canvas::Path::new(|builder| {
+            builder.move_to(geometry.to);
+            builder.line_to(base + side);
+            builder.line_to(base - side);
+            builder.close();
+        });
+        frame.fill(&arrow, color);
+    }
+
+    if let (Some(label), Some(size)) = (label, camera.label_size(LABEL_SIZE)) {
+        let offset = EDGE_LABEL_OFFSET * camera.zoom + width;
+        draw_label(
+            frame,
+            String::from(label),
+            geometry.middle + geometry.normal * offset,
+            size,
+            EDGE_LABEL_COLOR,
+            alignment::Horizontal::Center,
+        );
+    }
 }
 
-// fn draw_element(frame: &mut canvas::Frame, element: FinalGUIModel) {
-//     match element {
-//         FinalGUIModel::Node(node_element) => draw_node(frame, &node_element),
-//         FinalGUIModel::Edge(edge_element) => draw_edge(frame, &edge_element),
-//     }
-// }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_camera_zoom_keeps_anchor_in_place() -> Result<(), String> {
+        let mut camera = Camera::default();
+        let anchor = Point::new(100.0, 50.0);
+        let world = camera.to_world(anchor);
+
+        camera.zoom_at(anchor, 2.0);
+
+        assert_eq!(camera.zoom, 2.0);
+        assert_eq!(camera.to_screen(world), anchor);
+        Ok(())
+    }
+
+    #[test]
+    fn test_camera_culls_small_labels() -> Result<(), String> {
+        let mut camera = Camera::default();
+        assert_eq!(camera.label_size(LABEL_SIZE), Some(LABEL_SIZE));
+
+        camera.zoom = 0.1;
+        assert_eq!(camera.label_size(LABEL_SIZE), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_palette_colors_are_distinct() -> Result<(), String> {
+        let colors: Vec<Color> = (0..32).map(palette_color).collect();
+
+        for (i, color) in colors.iter().enumerate() {
+            assert!(!colors[i + 1..].contains(color));
+            assert!(
+                color.r.min(color.g).min(color.b) >= 0.5,
+                "Labels stay readable"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_edge_is_trimmed_at_node_radius() -> Result<(), String> {
+        let geometry =
+            edge_geometry(Point::ORIGIN, Point::new(100.0, 0.0), 10.0, 15.0, false).unwrap();
+
+        assert_eq!(geometry.from, Point::new(10.0, 0.0));
+        assert_eq!(geometry.to, Point::new(85.0, 0.0));
+        assert_eq!(geometry.tangent, Vector::new(1.0, 0.0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_opposite_curved_edges_bend_apart() -> Result<(), String> {
+        let (u, v) = (Point::ORIGIN, Point::new(100.0, 0.0));
+        let forward = edge_geometry(u, v, 10.0, 10.0, true).unwrap();
+        let backward = edge_geometry(v, u, 10.0, 10.0, true).unwrap();
+
+        assert!(forward.middle.y * backward.middle.y < 0.0);
+        assert!(edge_geometry(u, Point::new(15.0, 0.0), 10.0, 10.0, false).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_self_loop_sits_above_node() -> Result<(), String> {
+        let geometry = loop_geometry(Point::ORIGIN, 10.0);
+
+        assert!((Point::ORIGIN.distance(geometry.from) - 10.0).abs() < 0.01);
+        assert!((Point::ORIGIN.distance(geometry.to) - 10.0).abs() < 0.01);
+        assert!(
+            geometry.middle.y < -10.0,
+            "Loop is drawn outside the circle"
+        );
+        assert!(geometry.tangent.y > 0.0, "Arrow points back into the node");
+        Ok(())
+    }
+}
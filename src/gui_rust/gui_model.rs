@@ -1,16 +1,56 @@
-use crate::edge::edge_model::EdgeSnapshot;
-use crate::graph::graph::GraphSnapshot;
-use crate::gui_graphs::gui_model::EdgeModel;
-use crate::gui_graphs::gui_model::FinalGUIModel;
-use crate::gui_graphs::gui_model::NodeModel;
+use crate::algorithms::algorithm::Algorithm;
+use crate::algorithms::astar::{AStar, Heuristic, parse_heuristic};
+use crate::algorithms::bfs::BFS;
+use crate::algorithms::bipartite_check::BipartiteCheck;
+use crate::algorithms::bridges::Bridges;
+use crate::algorithms::chromatic::{ExactColoring, MAX_EXACT_NODES};
+use crate::algorithms::coloring::{Coloring, ColoringOrder};
+use crate::algorithms::cycle_detection::CycleDetection;
+use crate::algorithms::dfs::DFS;
+use crate::algorithms::dinic::Dinic;
+use crate::algorithms::edmonds_karp::EdmondsKarp;
+use crate::algorithms::euler::Euler;
+use crate::algorithms::hopcroft_karp::HopcroftKarp;
+use crate::algorithms::inspect::{Inspect, Overlay};
+use crate::algorithms::kahn::Kahn;
+use crate::algorithms::kosaraju::Kosaraju;
+use crate::algorithms::kruskal::Kruskal;
+use crate::algorithms::kuhn::Kuhn;
+use crate::algorithms::lca_lifting::BinaryLifting;
+use crate::algorithms::lca_sparse_table::EulerTourLca;
+use crate::algorithms::min_cost_flow::{MinCostFlow, PathSearch};
+use crate::algorithms::prim::Prim;
+use crate::algorithms::tarjan::Tarjan;
+use crate::algorithms::topological_dfs::TopologicalDFS;
+use crate::algorithms::zero_one_bfs::ZeroOneBFS;
+use crate::edge::edge_model::Edge;
+use crate::graph::graph::Graph;
+use crate::graph::history::{GraphCommand, History};
+use crate::graph::parse::parse_edge_list;
+use crate::gui_graphs::gui_model::Camera;
+use crate::gui_graphs::gui_model::EdgeShape;
+use crate::gui_graphs::gui_model::NodeStyle;
 use crate::gui_graphs::gui_model::draw_edge;
 use crate::gui_graphs::gui_model::draw_node;
-use iced::Size;
+use crate::gui_graphs::gui_model::node_to_point;
+use crate::gui_graphs::gui_model::palette_color;
+use crate::gui_rust::variables_panel::Inspector;
+use crate::layouts::bipartite::{BipartiteLayout, two_coloring};
+use crate::layouts::circular::{CircularLayout, CircularOrder};
+use crate::layouts::concentric::ConcentricLayout;
+use crate::layouts::force::ForceLayout;
+use crate::layouts::grid::GridLayout;
+use crate::layouts::hierarchical::HierarchicalLayout;
+use crate::layouts::layout::{Layout, position, sorted_ids};
+use crate::layouts::multilevel::MultilevelLayout;
+use crate::layouts::tree::{SpanningTree, TreeLayout};
+use iced::keyboard;
 use iced::mouse;
-use iced::widget::Column;
 use iced::widget::canvas;
-use iced::widget::column;
-use iced::{Element, Rectangle, Renderer, Theme};
+use iced::widget::canvas::event::{self, Event};
+use iced::widget::{button, column, pick_list, row, text, text_input};
+use iced::window;
+use iced::{Element, Length, Point, Rectangle, Renderer, Subscription, Theme};
 use ordered_float::OrderedFloat;
 use std::collections::HashMap;
 #[derive(Debug, Clone)]
@@ -18,142 +58,993 @@ pub enum NodeMessage {
     AddNode(u32, OrderedFloat<f64>, OrderedFloat<f64>),
     AddNodeSafe(u32, String, String),
     DeleteNode(u32),
+    UnpinNode(u32),
     UpdateX(String),
     UpdateY(String),
     UpdateError(String),
     AddEdge(String, String),
+    SelectAlgorithm(AlgorithmChoice),
+    UpdateSource(String),
+    UpdateTarget(String),
+    UpdateHeuristic(String),
+    RunAlgorithm,
+    ToggleSecondary,
+    StepForward,
+    StepBack,
+    UpdateStart(String),
+    UpdateEnd(String),
+    UpdateWeight(String),
+    RemoveEdge,
+    ChangeWeight,
+    UpdateCost(String),
+    ChangeCost,
+    ToggleDirection,
+    MoveNode(u32, OrderedFloat<f64>, OrderedFloat<f64>),
+    DragEnded,
+    ClickNode(u32),
+    Undo,
+    Redo,
+    SelectLayout(LayoutChoice),
+    UpdateRoot(String),
+    SelectSpanning(SpanningTree),
+    ApplyLayout,
+    LayoutTick,
+    UpdateImport(String),
+    ImportGraph,
 }
 
-#[derive(Clone)]
-pub struct State {
-    pub last_snapshot: GraphSnapshot,
-    pub elements_data: HashMap<u32, FinalGUIModel>,
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlgorithmChoice {
+    Bfs,
+    ZeroOneBfs,
+    Dfs,
+    CycleDetection,
+    Kahn,
+    TopologicalDfs,
+    Tarjan,
+    Kosaraju,
+    Bridges,
+    Kruskal,
+    Prim,
+    EdmondsKarp,
+    Dinic,
+    MinCostSpfa,
+    MinCostDijkstra,
+    BipartiteCheck,
+    Kuhn,
+    HopcroftKarp,
+    Euler,
+    LcaLifting,
+    LcaEulerTour,
+    AStarEuclidean,
+    AStarManhattan,
+    AStarZero,
+    AStarTable,
+    ColoringGreedy,
+    ColoringWelshPowell,
+    ColoringDSatur,
+    ChromaticNumber,
+}
+impl AlgorithmChoice {
+    pub const ALL: [AlgorithmChoice; 29] = [
+        AlgorithmChoice::Bfs,
+        AlgorithmChoice::ZeroOneBfs,
+        AlgorithmChoice::Dfs,
+        AlgorithmChoice::CycleDetection,
+        AlgorithmChoice::Kahn,
+        AlgorithmChoice::TopologicalDfs,
+        AlgorithmChoice::Tarjan,
+        AlgorithmChoice::Kosaraju,
+        AlgorithmChoice::Bridges,
+        AlgorithmChoice::Kruskal,
+        AlgorithmChoice::Prim,
+        AlgorithmChoice::EdmondsKarp,
+        AlgorithmChoice::Dinic,
+        AlgorithmChoice::MinCostSpfa,
+        AlgorithmChoice::MinCostDijkstra,
+        AlgorithmChoice::BipartiteCheck,
+        AlgorithmChoice::Kuhn,
+        AlgorithmChoice::HopcroftKarp,
+        AlgorithmChoice::Euler,
+        AlgorithmChoice::LcaLifting,
+        AlgorithmChoice::LcaEulerTour,
+        AlgorithmChoice::AStarEuclidean,
+        AlgorithmChoice::AStarManhattan,
+        AlgorithmChoice::AStarZero,
+        AlgorithmChoice::AStarTable,
+        AlgorithmChoice::ColoringGreedy,
+        AlgorithmChoice::ColoringWelshPowell,
+        AlgorithmChoice::ColoringDSatur,
+        AlgorithmChoice::ChromaticNumber,
+    ];
+}
+impl std::fmt::Display for AlgorithmChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            AlgorithmChoice::Bfs => "BFS",
+            AlgorithmChoice::ZeroOneBfs => "0-1 BFS",
+            AlgorithmChoice::Dfs => "DFS",
+            AlgorithmChoice::CycleDetection => "Cycle detection",
+            AlgorithmChoice::Kahn => "Topological sort (Kahn)",
+            AlgorithmChoice::TopologicalDfs => "Topological sort (DFS)",
+            AlgorithmChoice::Tarjan => "SCC (Tarjan)",
+            AlgorithmChoice::Kosaraju => "SCC (Kosaraju)",
+            AlgorithmChoice::Bridges => "Bridges and cut vertices",
+            AlgorithmChoice::Kruskal => "MST (Kruskal)",
+            AlgorithmChoice::Prim => "MST (Prim)",
+            AlgorithmChoice::EdmondsKarp => "Max flow (Edmonds-Karp)",
+            AlgorithmChoice::Dinic => "Max flow (Dinic)",
+            AlgorithmChoice::MinCostSpfa => "Min-cost flow (SPFA)",
+            AlgorithmChoice::MinCostDijkstra => "Min-cost flow (Dijkstra)",
+            AlgorithmChoice::BipartiteCheck => "Bipartite check",
+            AlgorithmChoice::Kuhn => "Matching (Kuhn)",
+            AlgorithmChoice::HopcroftKarp => "Matching (Hopcroft-Karp)",
+            AlgorithmChoice::Euler => "Euler tour (Hierholzer)",
+            AlgorithmChoice::LcaLifting => "LCA (binary lifting)",
+            AlgorithmChoice::LcaEulerTour => "LCA (Euler tour + sparse table)",
+            AlgorithmChoice::AStarEuclidean => "A* (Euclidean)",
+            AlgorithmChoice::AStarManhattan => "A* (Manhattan)",
+            AlgorithmChoice::AStarZero => "A* (zero heuristic)",
+            AlgorithmChoice::AStarTable => "A* (heuristic table)",
+            AlgorithmChoice::ColoringGreedy => "Coloring (greedy)",
+            AlgorithmChoice::ColoringWelshPowell => "Coloring (Welsh-Powell)",
+            AlgorithmChoice::ColoringDSatur => "Coloring (DSatur)",
+            AlgorithmChoice::ChromaticNumber => "Chromatic number (exact)",
+        };
+        write!(f, "{}", name)
+    }
 }
-impl Default for State {
-    fn default() -> Self {
-        let pairs: Vec<(u32, FinalGUIModel)> = vec![
-            (
-                0,
-                FinalGUIModel::Node(NodeModel {
-                    position_x: OrderedFloat(100.0),
-                    position_y: OrderedFloat(50.0),
-                    radius: OrderedFloat(15.0),
-                }),
-            ),
-            (
-                1,
-                FinalGUIModel::Node(NodeModel {
-                    position_x: OrderedFloat(50.0),
-                    position_y: OrderedFloat(100.0),
-                    radius: OrderedFloat(15.0),
-                }),
-            ),
-            (
-                2,
-                FinalGUIModel::Node(NodeModel {
-                    position_x: OrderedFloat(150.0),
-                    position_y: OrderedFloat(150.0),
-                    radius: OrderedFloat(15.0),
-                }),
-            ),
-            (
-                10,
-                FinalGUIModel::Edge(EdgeModel {
-                    line_type: 1,
-                    thickness: 5,
-                }),
-            ),
-            (
-                11,
-                FinalGUIModel::Edge(EdgeModel {
-                    line_type: 1,
-                    thickness: 5,
-                }),
-            ),
-        ];
 
-        let map: HashMap<u32, FinalGUIModel> = pairs.into_iter().collect();
-        State {
-            elements_data: map,
-            last_snapshot: GraphSnapshot {
-                nodes: vec![0, 1, 2],
-                edges: vec![
-                    EdgeSnapshot {
-                        node_start: 0,
-                        node_end: 1,
-                        id: 10,
-                        weight: 20,
-                    },
-                    EdgeSnapshot {
-                        node_start: 1,
-                        node_end: 2,
-                        id: 11,
-                        weight: 20,
-                    },
-                ],
-            },
-        }
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayoutChoice {
+    Force,
+    Multilevel,
+    Hierarchical,
+    Tree,
+    Circular,
+    CircularDfs,
+    Grid,
+    Bipartite,
+    BfsLevels,
+}
+
+impl LayoutChoice {
+    pub const ALL: [LayoutChoice; 9] = [
+        LayoutChoice::Force,
+        LayoutChoice::Multilevel,
+        LayoutChoice::Hierarchical,
+        LayoutChoice::Tree,
+        LayoutChoice::Circular,
+        LayoutChoice::CircularDfs,
+        LayoutChoice::Grid,
+        LayoutChoice::Bipartite,
+        LayoutChoice::BfsLevels,
+    ];
+    /// Layouts drawn around the node typed in the root field.
+    fn uses_root(&self) -> bool {
+        matches!(self, LayoutChoice::Tree | LayoutChoice::BfsLevels)
     }
 }
 
-#[derive(Default, Clone)]
-pub struct NewState {
-    pub data: Vec<(OrderedFloat<f64>, OrderedFloat<f64>)>,
-    pub drawn_nodes: HashMap<u32, (OrderedFloat<f64>, OrderedFloat<f64>)>,
-    pub drawn_edges: Vec<(u32, u32)>,
+impl std::fmt::Display for LayoutChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            LayoutChoice::Force => "Force",
+            LayoutChoice::Multilevel => "Force (multilevel)",
+            LayoutChoice::Hierarchical => "Hierarchical",
+            LayoutChoice::Tree => "Tree",
+            LayoutChoice::Circular => "Circular (id)",
+            LayoutChoice::CircularDfs => "Circular (DFS)",
+            LayoutChoice::Grid => "Grid",
+            LayoutChoice::Bipartite => "Bipartite",
+            LayoutChoice::BfsLevels => "BFS levels",
+        };
+        write!(f, "{}", name)
+    }
 }
 
+/// Canvas program drawing a `Graph` straight from its nodes and edges, with
+/// the overlay of the current algorithm step on top.
+pub struct GraphCanvas<'a> {
+    pub graph: &'a Graph,
+    pub overlay: &'a Overlay,
+    /// Whether nodes can be dragged; derived views like the condensation can
+    /// only be panned and zoomed.
+    pub editable: bool,
+}
+
+/// Canvas-local state: the camera and whatever the mouse is dragging.
 #[derive(Default)]
+pub struct CanvasState {
+    pub camera: Camera,
+    pub dragging: Option<u32>,
+    /// Whether the node being dragged has moved since the button went down;
+    /// a press and release in place is a click.
+    pub moved: bool,
+    pub panning: Option<Point>,
+}
+
+/// An LCA run kept after it finished, so clicked node pairs are answered
+/// against its table instead of preprocessing again.
+pub enum LcaSession {
+    Lifting(BinaryLifting),
+    EulerTour(EulerTourLca),
+}
+
+fn demo_graph() -> Graph {
+    let mut graph = Graph::new();
+    graph.add_new_node_to_graph(0, 100.0, 50.0);
+    graph.add_new_node_to_graph(1, 50.0, 100.0);
+    graph.add_new_node_to_graph(2, 150.0, 150.0);
+    graph.insert_edge(Edge::new(0, 1, 20));
+    graph.insert_edge(Edge::new(1, 2, 20));
+    graph
+}
+
 pub struct GraphApp {
     pub counter: u32,
-    pub state: State,
-    pub drawn_nodes: HashMap<u32, (OrderedFloat<f64>, OrderedFloat<f64>)>,
     pub x_input: String,
     pub y_input: String,
     pub error_message: String,
+    pub algorithm: Option<AlgorithmChoice>,
+    pub source_input: String,
+    /// Second node some algorithms need, e.g. the sink of a flow.
+    pub target_input: String,
+    pub inspector: Inspector,
+    pub overlay: Overlay,
+    /// Shows the step's secondary view, e.g. the condensation, instead of the graph.
+    pub show_secondary: bool,
+    pub graph: Graph,
+    pub history: History,
+    pub start_input: String,
+    pub end_input: String,
+    pub weight_input: String,
+    pub cost_input: String,
+    /// `node=value` pairs for the A* heuristic table.
+    pub heuristic_input: String,
+    /// Edge list in the Codeforces input format, see `parse_edge_list`.
+    pub import_input: String,
+    pub layout: Option<ForceLayout>,
+    /// Positions before the running force layout, recorded as one undo entry
+    /// once it settles.
+    pub layout_from: Option<HashMap<u32, (f64, f64)>>,
+    pub layout_choice: LayoutChoice,
+    pub root_input: String,
+    pub spanning: SpanningTree,
+    /// Last layout applied; a new root re-lays the graph out while it is a
+    /// layout that uses one.
+    pub applied_layout: Option<LayoutChoice>,
+    pub lca: Option<LcaSession>,
+    /// Nodes clicked so far for the next LCA query.
+    pub lca_picks: Vec<u32>,
+}
+impl Default for GraphApp {
+    fn default() -> Self {
+        let graph = demo_graph();
+        GraphApp {
+            counter: graph.nodes.len() as u32,
+            x_input: String::new(),
+            y_input: String::new(),
+            error_message: String::new(),
+            algorithm: None,
+            source_input: String::new(),
+            target_input: String::new(),
+            inspector: Inspector::default(),
+            overlay: Overlay::default(),
+            show_secondary: false,
+            graph,
+            history: History::new(),
+            start_input: String::new(),
+            end_input: String::new(),
+            weight_input: String::new(),
+            cost_input: String::new(),
+            heuristic_input: String::new(),
+            import_input: String::new(),
+            layout: None,
+            layout_from: None,
+            root_input: String::new(),
+            layout_choice: LayoutChoice::Force,
+            spanning: SpanningTree::Bfs,
+            applied_layout: None,
+            lca: None,
+            lca_picks: Vec::new(),
+        }
+    }
 }
 
-fn draw_edges(state: &State, canvas: &mut canvas::Frame) -> Option<()> {
-    let edges = state.last_snapshot.edges.clone();
-    for edge in edges {
-        let edge_model = state.elements_data.get(&edge.id)?.as_edge()?;
-        let node_start = state.elements_data.get(&edge.node_start)?.as_node()?;
-        let node_end = state.elements_data.get(&edge.node_end)?.as_node()?;
-        draw_edge(canvas, edge_model, node_start, node_end);
-    }
-    Some(())
-}
-fn draw_nodes(state: &State, canvas: &mut canvas::Frame) -> Option<()> {
-    let nodes = state.last_snapshot.nodes.clone();
-    for node in nodes {
-        let node_model = state.elements_data.get(&node)?.as_node()?;
-        draw_node(canvas, node_model);
-    }
-    Some(())
-}
-impl<NodeMessage> canvas::Program<NodeMessage> for State {
-    type State = ();
+fn draw_edges(graph: &Graph, overlay: &Overlay, camera: &Camera, canvas: &mut canvas::Frame) {
+    for node in graph.nodes.values() {
+        for edge in node.runtime.edges.values() {
+            let (start, end) = (edge.runtime.node_start, edge.runtime.node_end);
+            let has_reverse = graph.get_edge(end, start).is_some();
+            // An undirected edge is stored in both directions but drawn once.
+            if !edge.runtime.directed && has_reverse && start > end {
+                continue;
+            }
+            let Some(node_end) = graph.nodes.get(&end) else {
+                continue;
+            };
+            let shape = EdgeShape {
+                directed: edge.runtime.directed,
+                curved: edge.runtime.directed && has_reverse,
+                highlighted: overlay.is_edge_highlighted(start, end, edge.runtime.directed),
+                marked: overlay.is_edge_marked(start, end, edge.runtime.directed),
+                self_loop: start == end,
+            };
+            let weight = match edge.runtime.cost {
+                0 => edge.runtime.weight.to_string(),
+                cost => format!("{} @{}", edge.runtime.weight, cost),
+            };
+            let label = overlay
+                .edge_label(start, end, edge.runtime.directed)
+                .unwrap_or(&weight);
+            draw_edge(
+                canvas,
+                camera,
+                &edge.gui_model,
+                &node.gui_model,
+                &node_end.gui_model,
+                shape,
+                Some(label),
+            );
+        }
+    }
+}
+fn draw_nodes(graph: &Graph, overlay: &Overlay, camera: &Camera, canvas: &mut canvas::Frame) {
+    for node in graph.nodes.values() {
+        let annotation = overlay.node_annotations.get(&node.id);
+        draw_node(
+            canvas,
+            camera,
+            node.id,
+            &node.gui_model,
+            annotation.map(|s| s.as_str()),
+            NodeStyle {
+                fill: overlay
+                    .node_colors
+                    .get(&node.id)
+                    .map_or(NodeStyle::default().fill, |color| palette_color(*color)),
+                highlighted: overlay.highlighted_nodes.contains(&node.id),
+            },
+        );
+    }
+}
+/// Node under `point` (in graph coordinates), if any.
+fn node_at(graph: &Graph, point: Point) -> Option<u32> {
+    graph
+        .nodes
+        .values()
+        .find(|node| {
+            let center = node_to_point(&node.gui_model);
+            point.distance(center) <= node.gui_model.radius.into_inner() as f32
+        })
+        .map(|node| node.id)
+}
+impl canvas::Program<NodeMessage> for GraphCanvas<'_> {
+    type State = CanvasState;
+    fn update(
+        &self,
+        state: &mut CanvasState,
+        event: Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> (event::Status, Option<NodeMessage>) {
+        let Some(position) = cursor.position_in(bounds) else {
+            return (event::Status::Ignored, None);
+        };
+        match event {
+            Event::Mouse(mouse::Event::WheelScrolled { delta }) => {
+                let lines = match delta {
+                    mouse::ScrollDelta::Lines { y, .. } => y,
+                    mouse::ScrollDelta::Pixels { y, .. } => y / 50.0,
+                };
+                state.camera.zoom_at(position, 1.1_f32.powf(lines));
+                (event::Status::Captured, None)
+            }
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                match node_at(self.graph, state.camera.to_world(position)) {
+                    Some(id) if self.editable => {
+                        state.dragging = Some(id);
+                        state.moved = false;
+                    }
+                    _ => state.panning = Some(position),
+                }
+                (event::Status::Captured, None)
+            }
+            Event::Mouse(mouse::Event::CursorMoved { .. }) => {
+                if let Some(id) = state.dragging {
+                    state.moved = true;
+                    let world = state.camera.to_world(position);
+                    let message = NodeMessage::MoveNode(
+                        id,
+                        OrderedFloat(world.x as f64),
+                        OrderedFloat(world.y as f64),
+                    );
+                    return (event::Status::Captured, Some(message));
+                }
+                if let Some(last) = state.panning {
+                    state.camera.offset = state.camera.offset + (position - last);
+                    state.panning = Some(position);
+                    return (event::Status::Captured, None);
+                }
+                (event::Status::Ignored, None)
+            }
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                state.panning = None;
+                match state.dragging.take() {
+                    Some(id) if !state.moved => {
+                        (event::Status::Captured, Some(NodeMessage::ClickNode(id)))
+                    }
+                    Some(_) => (event::Status::Captured, Some(NodeMessage::DragEnded)),
+                    None => (event::Status::Captured, None),
+                }
+            }
+            _ => (event::Status::Ignored, None),
+        }
+    }
     fn draw(
         &self,
-        _state: &(),
+        state: &CanvasState,
         renderer: &Renderer,
         _theme: &Theme,
-        _: Rectangle,
+        bounds: Rectangle,
         _cursor: mouse::Cursor,
     ) -> Vec<canvas::Geometry> {
-        let mut frame = canvas::Frame::new(renderer, Size::new(800.0, 800.0));
-        draw_nodes(self, &mut frame);
-        draw_edges(self, &mut frame);
+        let mut frame = canvas::Frame::new(renderer, bounds.size());
+        draw_edges(self.graph, self.overlay, &state.camera, &mut frame);
+        draw_nodes(self.graph, self.overlay, &state.camera, &mut frame);
         vec![frame.into_geometry()]
     }
 }
+fn parse_node(input: &str) -> Result<u32, String> {
+    input
+        .trim()
+        .parse()
+        .map_err(|_| format!("Invalid node id: {}", input))
+}
+fn parse_position(input: &str) -> Result<f64, String> {
+    input
+        .trim()
+        .parse()
+        .map_err(|_| format!("Invalid coordinate: {}", input))
+}
 impl GraphApp {
-    pub fn update(&mut self, _: NodeMessage) {
-        // [TODO] Implement messages
+    fn selected_edge(&self) -> Result<(u32, u32), String> {
+        Ok((parse_node(&self.start_input)?, parse_node(&self.end_input)?))
+    }
+    fn weight(&self) -> Result<u32, String> {
+        if self.weight_input.trim().is_empty() {
+            return Ok(1);
+        }
+        self.weight_input
+            .trim()
+            .parse()
+            .map_err(|_| format!("Invalid weight: {}", self.weight_input))
+    }
+    fn cost(&self) -> Result<i32, String> {
+        self.cost_input
+            .trim()
+            .parse()
+            .map_err(|_| format!("Invalid cost: {}", self.cost_input))
+    }
+    fn edit(&mut self, message: NodeMessage) -> Result<(), String> {
+        let graph = &self.graph;
+        let command = match message {
+            NodeMessage::AddNode(id, x, y) => {
+                GraphCommand::add_node(graph, id, x.into_inner(), y.into_inner())?
+            }
+            NodeMessage::AddNodeSafe(id, x, y) => {
+                GraphCommand::add_node(graph, id, parse_position(&x)?, parse_position(&y)?)?
+            }
+            NodeMessage::DeleteNode(id) => GraphCommand::remove_node(graph, id)?,
+            NodeMessage::UnpinNode(id) => GraphCommand::set_pinned(graph, id, false)?,
+            NodeMessage::AddEdge(start, end) => GraphCommand::add_edge(
+                graph,
+                parse_node(&start)?,
+                parse_node(&end)?,
+                self.weight()?,
+                true,
+            )?,
+            NodeMessage::RemoveEdge => {
+                let (start, end) = self.selected_edge()?;
+                GraphCommand::remove_edge(graph, start, end)?
+            }
+            NodeMessage::ChangeWeight => {
+                let (start, end) = self.selected_edge()?;
+                GraphCommand::change_weight(graph, start, end, self.weight()?)?
+            }
+            NodeMessage::ChangeCost => {
+                let (start, end) = self.selected_edge()?;
+                GraphCommand::change_cost(graph, start, end, self.cost()?)?
+            }
+            NodeMessage::ToggleDirection => {
+                let (start, end) = self.selected_edge()?;
+                GraphCommand::toggle_direction(graph, start, end)?
+            }
+            NodeMessage::MoveNode(id, x, y) => {
+                if !self.history.is_grouping() {
+                    self.history.begin_group();
+                }
+                // Nodes placed by hand stay put when a layout runs.
+                if graph
+                    .nodes
+                    .get(&id)
+                    .is_some_and(|node| !node.gui_model.pinned)
+                {
+                    let pin = GraphCommand::set_pinned(graph, id, true)?;
+                    self.history.execute(&mut self.graph, pin);
+                }
+                let graph = &self.graph;
+                GraphCommand::move_node(graph, id, x.into_inner(), y.into_inner())?
+            }
+            _ => return Ok(()),
+        };
+        // A kept LCA table is stale once the tree itself changes.
+        if !matches!(
+            command,
+            GraphCommand::MoveNode { .. } | GraphCommand::SetPinned { .. }
+        ) {
+            self.lca = None;
+        }
+        self.history.execute(&mut self.graph, command);
+        while self.graph.nodes.contains_key(&self.counter) {
+            self.counter += 1;
+        }
+        Ok(())
+    }
+    /// Runs `algorithm` and loads its recorded steps into the inspector.
+    fn show<T: Inspect + Clone + 'static>(&mut self, mut algorithm: impl Algorithm<T>) {
+        algorithm.run();
+        self.inspector.load(algorithm.get_history());
+    }
+    /// Collects clicked nodes into an LCA query against the kept table; the
+    /// second click answers it and jumps to its first step.
+    fn pick_lca_node(&mut self, id: u32) -> Result<(), String> {
+        let Some(session) = self.lca.as_mut() else {
+            return Ok(());
+        };
+        self.lca_picks.push(id);
+        let &[u, v] = &self.lca_picks[..] else {
+            self.source_input = id.to_string();
+            return Ok(());
+        };
+        self.lca_picks.clear();
+        self.target_input = id.to_string();
+        let off_tree = String::from("Query nodes must be in the tree of the root");
+        let first = match session {
+            LcaSession::Lifting(lca) => {
+                let first = lca.ask((u, v)).ok_or(off_tree)?;
+                self.inspector.load(lca.get_history());
+                first
+            }
+            LcaSession::EulerTour(lca) => {
+                let first = lca.ask((u, v)).ok_or(off_tree)?;
+                self.inspector.load(lca.get_history());
+                first
+            }
+        };
+        self.inspector.show(first);
+        Ok(())
+    }
+    fn run_algorithm(&mut self) -> Result<(), String> {
+        self.lca = None;
+        self.lca_picks.clear();
+        let choice = self.algorithm.ok_or("Select an algorithm first")?;
+        let source: Result<u32, String> = self
+            .source_input
+            .trim()
+            .parse()
+            .map_err(|_| format!("Invalid source node: {}", self.source_input));
+        let target: Result<u32, String> = self
+            .target_input
+            .trim()
+            .parse()
+            .map_err(|_| format!("Invalid target node: {}", self.target_input));
+        let graph = self.graph.clone();
+        let missing = format!("Node {} is not in the graph", self.source_input.trim());
+        let ends = String::from("Source and sink must be two different nodes of the graph");
+        let not_bipartite = String::from("Graph is not bipartite");
+        let missing_ends = String::from("Source and target must be nodes of the graph");
+        let off_tree = String::from("Query nodes must be in the tree of the root");
+        let heuristic = match choice {
+            AlgorithmChoice::AStarEuclidean => Heuristic::Euclidean,
+            AlgorithmChoice::AStarManhattan => Heuristic::Manhattan,
+            AlgorithmChoice::AStarTable => {
+                Heuristic::Table(parse_heuristic(&self.heuristic_input)?)
+            }
+            _ => Heuristic::Zero,
+        };
+        match choice {
+            AlgorithmChoice::Bfs => self.show(BFS::new(graph, source?).ok_or(missing)?),
+            AlgorithmChoice::ZeroOneBfs => {
+                let invalid = format!("{}, or some weight is not 0 or 1", missing);
+                self.show(ZeroOneBFS::new(graph, source?).ok_or(invalid)?)
+            }
+            AlgorithmChoice::Dfs => self.show(DFS::new(graph, source?).ok_or(missing)?),
+            AlgorithmChoice::CycleDetection => self.show(CycleDetection::new(graph)),
+            AlgorithmChoice::Kahn => self.show(Kahn::new(graph)),
+            AlgorithmChoice::TopologicalDfs => self.show(TopologicalDFS::new(graph)),
+            AlgorithmChoice::Tarjan => self.show(Tarjan::new(graph)),
+            AlgorithmChoice::Kosaraju => self.show(Kosaraju::new(graph)),
+            AlgorithmChoice::Bridges => self.show(Bridges::new(graph)),
+            AlgorithmChoice::Kruskal => self.show(Kruskal::new(graph)),
+            AlgorithmChoice::Prim => self.show(Prim::new(graph, source?).ok_or(missing)?),
+            AlgorithmChoice::EdmondsKarp => {
+                self.show(EdmondsKarp::new(graph, source?, target?).ok_or(ends)?)
+            }
+            AlgorithmChoice::Dinic => self.show(Dinic::new(graph, source?, target?).ok_or(ends)?),
+            AlgorithmChoice::MinCostSpfa => {
+                self.show(MinCostFlow::new(graph, source?, target?, PathSearch::Spfa).ok_or(ends)?)
+            }
+            AlgorithmChoice::MinCostDijkstra => self
+                .show(MinCostFlow::new(graph, source?, target?, PathSearch::Dijkstra).ok_or(ends)?),
+            AlgorithmChoice::BipartiteCheck => self.show(BipartiteCheck::new(graph)),
+            AlgorithmChoice::Kuhn => self.show(Kuhn::new(graph).ok_or(not_bipartite)?),
+            AlgorithmChoice::HopcroftKarp => {
+                self.show(HopcroftKarp::new(graph).ok_or(not_bipartite)?)
+            }
+            AlgorithmChoice::Euler => self.show(Euler::new(graph)),
+            AlgorithmChoice::LcaLifting => {
+                let root = self.parse_root()?;
+                let mut lca = BinaryLifting::new(graph, root, self.lca_query()?).ok_or(off_tree)?;
+                lca.run();
+                self.inspector.load(lca.get_history());
+                self.lca = Some(LcaSession::Lifting(lca));
+            }
+            AlgorithmChoice::LcaEulerTour => {
+                let root = self.parse_root()?;
+                let mut lca = EulerTourLca::new(graph, root, self.lca_query()?).ok_or(off_tree)?;
+                lca.run();
+                self.inspector.load(lca.get_history());
+                self.lca = Some(LcaSession::EulerTour(lca));
+            }
+            AlgorithmChoice::AStarEuclidean
+            | AlgorithmChoice::AStarManhattan
+            | AlgorithmChoice::AStarZero
+            | AlgorithmChoice::AStarTable => {
+                self.show(AStar::new(graph, source?, target?, heuristic).ok_or(missing_ends)?)
+            }
+            AlgorithmChoice::ColoringGreedy => {
+                self.show(Coloring::new(graph, ColoringOrder::Greedy))
+            }
+            AlgorithmChoice::ColoringWelshPowell => {
+                self.show(Coloring::new(graph, ColoringOrder::WelshPowell))
+            }
+            AlgorithmChoice::ColoringDSatur => {
+                self.show(Coloring::new(graph, ColoringOrder::DSatur))
+            }
+            AlgorithmChoice::ChromaticNumber => {
+                let too_big = format!("Exact coloring handles at most {} nodes", MAX_EXACT_NODES);
+                self.show(ExactColoring::new(graph).ok_or(too_big)?)
+            }
+        }
+        Ok(())
+    }
+    /// Replaces the graph with the pasted edge list; undo history starts over.
+    fn import_graph(&mut self) -> Result<(), String> {
+        let parsed = parse_edge_list(&self.import_input)?;
+        self.graph = parsed.graph;
+        self.layout = None;
+        self.layout_from = None;
+        self.inspector = Inspector::default();
+        self.lca = None;
+        self.counter = self.graph.nodes.keys().max().map_or(0, |id| id + 1);
+        let result = match parsed.bipartite {
+            true => self.apply_layout(LayoutChoice::Bipartite),
+            false => self.apply_layout(LayoutChoice::Force),
+        };
+        // The first layout is where the graph starts, not an undoable move.
+        self.history = History::new();
+        self.layout_from = None;
+        result
+    }
+    /// Source and target as the two nodes of an LCA query; both empty means
+    /// preprocessing only.
+    fn lca_query(&self) -> Result<Option<(u32, u32)>, String> {
+        if self.source_input.trim().is_empty() && self.target_input.trim().is_empty() {
+            return Ok(None);
+        }
+        Ok(Some((
+            parse_node(&self.source_input)?,
+            parse_node(&self.target_input)?,
+        )))
+    }
+    fn parse_root(&self) -> Result<u32, String> {
+        let root = parse_node(&self.root_input)?;
+        if !self.graph.nodes.contains_key(&root) {
+            return Err(format!("Node {} does not exist", root));
+        }
+        Ok(root)
+    }
+    /// Records every node moved since `before` as a single undo entry.
+    fn record_moves(&mut self, before: HashMap<u32, (f64, f64)>) {
+        self.history.begin_group();
+        for id in sorted_ids(&self.graph) {
+            if let (Some(from), Some(to)) = (before.get(&id), position(&self.graph, id))
+                && *from != to
+            {
+                let command = GraphCommand::MoveNode {
+                    id,
+                    from: *from,
+                    to,
+                };
+                self.history.execute(&mut self.graph, command);
+            }
+        }
+        self.history.end_group();
+    }
+    /// Stops the running force layout, keeping what it moved so far.
+    fn finish_layout(&mut self) {
+        self.layout = None;
+        if let Some(before) = self.layout_from.take() {
+            self.record_moves(before);
+        }
+    }
+    fn apply_layout(&mut self, choice: LayoutChoice) -> Result<(), String> {
+        self.finish_layout();
+        self.applied_layout = Some(choice);
+        let before = sorted_ids(&self.graph)
+            .into_iter()
+            .filter_map(|id| Some((id, position(&self.graph, id)?)))
+            .collect();
+        let result = self.place(choice);
+        match self.layout {
+            Some(_) => self.layout_from = Some(before),
+            None => self.record_moves(before),
+        }
+        result
+    }
+    fn place(&mut self, choice: LayoutChoice) -> Result<(), String> {
+        let graph = &mut self.graph;
+        match choice {
+            LayoutChoice::Force => self.layout = Some(ForceLayout::new(graph)),
+            LayoutChoice::Multilevel => MultilevelLayout::default().apply(graph),
+            LayoutChoice::Hierarchical => HierarchicalLayout::default().apply(graph),
+            LayoutChoice::Tree => {
+                let mut layout = TreeLayout::new(self.parse_root()?);
+                layout.spanning = self.spanning;
+                layout.apply(&mut self.graph);
+            }
+            LayoutChoice::Circular => CircularLayout::new(CircularOrder::Id).apply(graph),
+            LayoutChoice::CircularDfs => CircularLayout::new(CircularOrder::Dfs).apply(graph),
+            LayoutChoice::Grid => GridLayout::default().apply(graph),
+            LayoutChoice::Bipartite => {
+                if !two_coloring(graph).1 {
+                    return Err("Graph is not bipartite".to_string());
+                }
+                BipartiteLayout::default().apply(graph);
+            }
+            LayoutChoice::BfsLevels => {
+                ConcentricLayout::new(self.parse_root()?).apply(&mut self.graph)
+            }
+        }
+        Ok(())
+    }
+    /// Redraws a root-based layout after its root or spanning tree changed.
+    fn relayout(&mut self) {
+        if let Some(choice) = self.applied_layout
+            && choice.uses_root()
+        {
+            self.error_message = match self.apply_layout(choice) {
+                Ok(()) => String::new(),
+                Err(error) => error,
+            }
+        }
+    }
+    pub fn update(&mut self, message: NodeMessage) {
+        match message {
+            NodeMessage::SelectAlgorithm(choice) => self.algorithm = Some(choice),
+            NodeMessage::UpdateSource(source) => self.source_input = source,
+            NodeMessage::UpdateTarget(target) => self.target_input = target,
+            NodeMessage::UpdateHeuristic(heuristic) => self.heuristic_input = heuristic,
+            NodeMessage::RunAlgorithm => {
+                self.show_secondary = false;
+                self.error_message = match self.run_algorithm() {
+                    Ok(()) => String::new(),
+                    Err(error) => error,
+                }
+            }
+            NodeMessage::ToggleSecondary => self.show_secondary = !self.show_secondary,
+            NodeMessage::StepForward => self.inspector.step_forward(),
+            NodeMessage::StepBack => self.inspector.step_back(),
+            NodeMessage::UpdateX(x) => self.x_input = x,
+            NodeMessage::UpdateY(y) => self.y_input = y,
+            NodeMessage::UpdateStart(start) => self.start_input = start,
+            NodeMessage::UpdateEnd(end) => self.end_input = end,
+            NodeMessage::UpdateWeight(weight) => self.weight_input = weight,
+            NodeMessage::UpdateCost(cost) => self.cost_input = cost,
+            NodeMessage::UpdateError(error) => self.error_message = error,
+            NodeMessage::UpdateImport(import) => self.import_input = import,
+            NodeMessage::ImportGraph => {
+                self.error_message = match self.import_graph() {
+                    Ok(()) => String::new(),
+                    Err(error) => error,
+                }
+            }
+            NodeMessage::DragEnded => self.history.end_group(),
+            NodeMessage::ClickNode(id) => {
+                self.error_message = match self.pick_lca_node(id) {
+                    Ok(()) => String::new(),
+                    Err(error) => error,
+                }
+            }
+            NodeMessage::SelectLayout(choice) => self.layout_choice = choice,
+            NodeMessage::UpdateRoot(root) => {
+                // Partial ids typed on the way are neither laid out nor reported.
+                let previous = self.parse_root().ok();
+                self.root_input = root;
+                if let Ok(root) = self.parse_root()
+                    && previous != Some(root)
+                {
+                    self.relayout();
+                }
+            }
+            NodeMessage::SelectSpanning(spanning) => {
+                self.spanning = spanning;
+                self.relayout();
+            }
+            NodeMessage::ApplyLayout => {
+                self.error_message = match self.apply_layout(self.layout_choice) {
+                    Ok(()) => String::new(),
+                    Err(error) => error,
+                }
+            }
+            NodeMessage::LayoutTick => {
+                if let Some(layout) = self.layout.as_mut()
+                    && !layout.step(&mut self.graph)
+                {
+                    self.finish_layout();
+                }
+            }
+            NodeMessage::Undo => {
+                self.finish_layout();
+                self.history.undo(&mut self.graph);
+                self.lca = None;
+            }
+            NodeMessage::Redo => {
+                self.finish_layout();
+                self.history.redo(&mut self.graph);
+                self.lca = None;
+            }
+            edit => {
+                self.error_message = match self.edit(edit) {
+                    Ok(()) => String::new(),
+                    Err(error) => error,
+                }
+            }
+        }
+        self.overlay = self.inspector.current_overlay();
+    }
+    pub fn view(&self) -> Element<'_, NodeMessage> {
+        let secondary = self.overlay.secondary.as_deref();
+        let toggle = secondary.map(|view| {
+            let label = match self.show_secondary {
+                true => String::from("Show graph"),
+                false => format!("Show {}", view.title),
+            };
+            button(text(label)).on_press(NodeMessage::ToggleSecondary)
+        });
+        let toolbar = row![
+            pick_list(
+                AlgorithmChoice::ALL,
+                self.algorithm,
+                NodeMessage::SelectAlgorithm
+            )
+            .placeholder("Algorithm"),
+            text_input("Source", &self.source_input)
+                .on_input(NodeMessage::UpdateSource)
+                .width(Length::Fixed(80.0)),
+            text_input("Target", &self.target_input)
+                .on_input(NodeMessage::UpdateTarget)
+                .width(Length::Fixed(80.0)),
+            text_input("h: node=value", &self.heuristic_input)
+                .on_input(NodeMessage::UpdateHeuristic)
+                .width(Length::Fixed(140.0)),
+            button("Run").on_press(NodeMessage::RunAlgorithm),
+            button("<").on_press(NodeMessage::StepBack),
+            button(">").on_press(NodeMessage::StepForward),
+        ]
+        .push_maybe(toggle)
+        .push(text(&self.error_message))
+        .spacing(8);
+        let input = |placeholder, value, on_input: fn(String) -> NodeMessage| {
+            text_input(placeholder, value)
+                .on_input(on_input)
+                .width(Length::Fixed(60.0))
+        };
+        let edit_toolbar = row![
+            button("Undo").on_press_maybe(self.history.can_undo().then_some(NodeMessage::Undo)),
+            button("Redo").on_press_maybe(self.history.can_redo().then_some(NodeMessage::Redo)),
+            input("x", &self.x_input, NodeMessage::UpdateX),
+            input("y", &self.y_input, NodeMessage::UpdateY),
+            button("Add node").on_press(NodeMessage::AddNodeSafe(
+                self.counter,
+                self.x_input.clone(),
+                self.y_input.clone()
+            )),
+            input("from", &self.start_input, NodeMessage::UpdateStart),
+            input("to", &self.end_input, NodeMessage::UpdateEnd),
+            input("weight", &self.weight_input, NodeMessage::UpdateWeight),
+            button("Delete node").on_press_maybe(
+                parse_node(&self.start_input)
+                    .ok()
+                    .map(NodeMessage::DeleteNode)
+            ),
+            button("Unpin node").on_press_maybe(
+                parse_node(&self.start_input)
+                    .ok()
+                    .map(NodeMessage::UnpinNode)
+            ),
+            button("Add edge").on_press(NodeMessage::AddEdge(
+                self.start_input.clone(),
+                self.end_input.clone()
+            )),
+            button("Remove edge").on_press(NodeMessage::RemoveEdge),
+            button("Set weight").on_press(NodeMessage::ChangeWeight),
+            input("cost", &self.cost_input, NodeMessage::UpdateCost),
+            button("Set cost").on_press(NodeMessage::ChangeCost),
+            button("Toggle direction").on_press(NodeMessage::ToggleDirection),
+        ]
+        .spacing(8);
+        let layout_toolbar = row![
+            pick_list(
+                LayoutChoice::ALL,
+                Some(self.layout_choice),
+                NodeMessage::SelectLayout
+            ),
+            input("root", &self.root_input, NodeMessage::UpdateRoot),
+            pick_list(
+                SpanningTree::ALL,
+                Some(self.spanning),
+                NodeMessage::SelectSpanning
+            ),
+            button("Apply layout").on_press(NodeMessage::ApplyLayout),
+            text_input("n m / u v [w]", &self.import_input)
+                .on_input(NodeMessage::UpdateImport)
+                .width(Length::Fixed(200.0)),
+            button("Load").on_press(NodeMessage::ImportGraph),
+        ]
+        .spacing(8);
+        let program = match secondary {
+            Some(view) if self.show_secondary => GraphCanvas {
+                graph: &view.graph,
+                overlay: &view.overlay,
+                editable: false,
+            },
+            _ => GraphCanvas {
+                graph: &self.graph,
+                overlay: &self.overlay,
+                editable: true,
+            },
+        };
+        let canvas: Element<NodeMessage> = canvas(program)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into();
+        row![
+            column![edit_toolbar, layout_toolbar, toolbar, canvas].spacing(8),
+            self.inspector.view()
+        ]
+        .padding(8)
+        .into()
     }
-    pub fn view(&self) -> Column<NodeMessage> {
-        let canvas: Element<NodeMessage> = canvas(self.state.clone()).into();
-        column![canvas]
+    pub fn subscription(&self) -> Subscription<NodeMessage> {
+        let shortcuts = keyboard::on_key_press(|key, modifiers| {
+            if !modifiers.command() {
+                return None;
+            }
+            match key.as_ref() {
+                keyboard::Key::Character("z") if modifiers.shift() => Some(NodeMessage::Redo),
+                keyboard::Key::Character("z") => Some(NodeMessage::Undo),
+                keyboard::Key::Character("y") => Some(NodeMessage::Redo),
+                _ => None,
+            }
+        });
+        let animation = match self.layout {
+            Some(_) => window::frames().map(|_| NodeMessage::LayoutTick),
+            None => Subscription::none(),
+        };
+        Subscription::batch([shortcuts, animation])
     }
 }
 
@@ -1 +1,2 @@
-pub mod gui_model;
\ No newline at end of file
+pub mod gui_model;
+pub mod variables_panel;
\ No newline at end of file
@@ -0,0 +1,136 @@
+use crate::algorithms::inspect::{
+    Inspect, Overlay, Variable, VariableKind, VariableValue, find_variable,
+};
+use iced::widget::{Column, column, container, row, scrollable, text};
+use iced::{Color, Element, Length};
+use std::rc::Rc;
+
+const CHANGED_COLOR: Color = Color::from_rgb(1.0, 0.8, 0.2);
+const MAX_ENTRIES: usize = 200;
+
+/// Recorded runtimes of an algorithm run, plus the step currently displayed.
+/// Variables and overlay are only built for the step on screen.
+#[derive(Default, Clone)]
+pub struct Inspector {
+    history: Rc<Vec<Box<dyn Inspect>>>,
+    pub current: usize,
+    variables: Vec<Variable>,
+    previous: Vec<Variable>,
+    overlay: Overlay,
+}
+
+impl Inspector {
+    pub fn load<T: Inspect + Clone + 'static>(&mut self, history: &[T]) {
+        let history: Vec<Box<dyn Inspect>> = history
+            .iter()
+            .map(|runtime| Box::new(runtime.clone()) as Box<dyn Inspect>)
+            .collect();
+        self.history = Rc::new(history);
+        self.show(0);
+    }
+    pub fn show(&mut self, step: usize) {
+        self.current = step;
+        self.variables = self
+            .history
+            .get(step)
+            .map_or(Vec::new(), |runtime| runtime.variables());
+        self.overlay = self
+            .history
+            .get(step)
+            .map_or(Overlay::default(), |runtime| runtime.overlay());
+        self.previous = match step {
+            0 => Vec::new(),
+            step => self.history[step - 1].variables(),
+        };
+    }
+    pub fn step_forward(&mut self) {
+        if self.current + 1 < self.history.len() {
+            self.show(self.current + 1);
+        }
+    }
+    pub fn step_back(&mut self) {
+        if self.current > 0 {
+            self.show(self.current - 1);
+        }
+    }
+    pub fn current_variables(&self) -> &[Variable] {
+        &self.variables
+    }
+    pub fn current_overlay(&self) -> Overlay {
+        self.overlay.clone()
+    }
+    pub fn previous_variables(&self) -> &[Variable] {
+        &self.previous
+    }
+
+    pub fn view<'a, Message: 'a>(&'a self) -> Element<'a, Message> {
+        let header = text(format!(
+            "Step {} / {}",
+            if self.history.is_empty() {
+                0
+            } else {
+                self.current + 1
+            },
+            self.history.len()
+        ));
+        let previous = self.previous_variables();
+        let sections = self
+            .current_variables()
+            .iter()
+            .map(|variable| variable_view(variable, find_variable(previous, &variable.name)));
+
+        let content = column![header, Column::with_children(sections).spacing(12)].spacing(12);
+        container(scrollable(content))
+            .width(Length::Fixed(280.0))
+            .height(Length::Fill)
+            .padding(10)
+            .into()
+    }
+}
+
+fn kind_label(kind: VariableKind) -> &'static str {
+    match kind {
+        VariableKind::Scalar => "scalar",
+        VariableKind::Array => "array",
+        VariableKind::Queue => "queue",
+        VariableKind::Stack => "stack",
+    }
+}
+
+fn entry_view<'a, Message: 'a>(content: String, changed: bool) -> Element<'a, Message> {
+    let entry = text(content).size(14);
+    if changed {
+        entry.color(CHANGED_COLOR).into()
+    } else {
+        entry.into()
+    }
+}
+
+fn variable_view<'a, Message: 'a>(
+    variable: &'a Variable,
+    previous: Option<&'a Variable>,
+) -> Element<'a, Message> {
+    let changed = variable.changed_entries(previous);
+    let title = text(format!("{} ({})", variable.name, kind_label(variable.kind))).size(16);
+
+    let entries: Vec<Element<'a, Message>> = match &variable.value {
+        VariableValue::Scalar(value) => vec![entry_view(value.clone(), changed[0])],
+        VariableValue::List(values) => values
+            .iter()
+            .zip(changed)
+            .take(MAX_ENTRIES)
+            .map(|(value, changed)| entry_view(value.clone(), changed))
+            .collect(),
+        VariableValue::Map(entries) => entries
+            .iter()
+            .zip(changed)
+            .take(MAX_ENTRIES)
+            .map(|((key, value), changed)| entry_view(format!("[{}] {}", key, value), changed))
+            .collect(),
+    };
+    let mut body = column![title, row(entries).spacing(8).wrap()].spacing(4);
+    if variable.len() > MAX_ENTRIES {
+        body = body.push(text(format!("... {} more", variable.len() - MAX_ENTRIES)).size(12));
+    }
+    body.into()
+}
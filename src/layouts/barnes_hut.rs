@@ -0,0 +1,242 @@
+use std::thread;
+
+/// Opening angle: a cell is treated as a single body when
+/// `size / distance < theta`. Higher is faster and less accurate.
+pub const DEFAULT_THETA: f64 = 0.9;
+/// Below this many bodies spawning threads costs more than it saves.
+const PARALLEL_THRESHOLD: usize = 4096;
+/// Coincident bodies would split forever; past this depth they share a leaf.
+const MAX_DEPTH: usize = 48;
+const MIN_DISTANCE_SQUARED: f64 = 1e-4;
+
+#[derive(Debug, Clone)]
+struct Cell {
+    x: f64,
+    y: f64,
+    size: f64,
+    mass: f64,
+    mass_x: f64,
+    mass_y: f64,
+    children: Option<[usize; 4]>,
+}
+
+impl Cell {
+    fn new(x: f64, y: f64, size: f64) -> Self {
+        Cell {
+            x,
+            y,
+            size,
+            mass: 0.0,
+            mass_x: 0.0,
+            mass_y: 0.0,
+            children: None,
+        }
+    }
+    fn center_of_mass(&self) -> (f64, f64) {
+        (self.mass_x / self.mass, self.mass_y / self.mass)
+    }
+    fn quadrant(&self, (x, y): (f64, f64)) -> usize {
+        let half = self.size / 2.0;
+        let right = (x >= self.x + half) as usize;
+        let bottom = (y >= self.y + half) as usize;
+        right + 2 * bottom
+    }
+}
+
+/// Quadtree over node positions, each cell storing the total mass (node
+/// count) and center of mass of the bodies below it.
+#[derive(Debug, Clone)]
+pub struct QuadTree {
+    cells: Vec<Cell>,
+}
+
+impl QuadTree {
+    pub fn build(positions: &[(f64, f64)]) -> Self {
+        if positions.is_empty() {
+            return QuadTree {
+                cells: vec![Cell::new(0.0, 0.0, 1.0)],
+            };
+        }
+        let (mut min_x, mut min_y) = (f64::INFINITY, f64::INFINITY);
+        let (mut max_x, mut max_y) = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+        for (x, y) in positions {
+            min_x = min_x.min(*x);
+            min_y = min_y.min(*y);
+            max_x = max_x.max(*x);
+            max_y = max_y.max(*y);
+        }
+        let size = (max_x - min_x).max(max_y - min_y).max(1.0) * 1.0001;
+        let mut tree = QuadTree {
+            cells: vec![Cell::new(min_x, min_y, size)],
+        };
+        for point in positions {
+            tree.insert(*point);
+        }
+        tree
+    }
+
+    fn insert(&mut self, point: (f64, f64)) {
+        let mut current = 0;
+        let mut depth = 0;
+        loop {
+            let was_empty = self.cells[current].mass == 0.0;
+            let cell = &mut self.cells[current];
+            cell.mass += 1.0;
+            cell.mass_x += point.0;
+            cell.mass_y += point.1;
+
+            if let Some(children) = cell.children {
+                current = children[cell.quadrant(point)];
+                depth += 1;
+                continue;
+            }
+            if was_empty || depth >= MAX_DEPTH {
+                return;
+            }
+            // An occupied leaf: push its previous body one level down.
+            let cell = &self.cells[current];
+            let resident_mass = cell.mass - 1.0;
+            let resident_x = cell.mass_x - point.0;
+            let resident_y = cell.mass_y - point.1;
+            let resident_quadrant =
+                cell.quadrant((resident_x / resident_mass, resident_y / resident_mass));
+            let point_quadrant = cell.quadrant(point);
+
+            let children = self.split(current);
+            let child = &mut self.cells[children[resident_quadrant]];
+            child.mass = resident_mass;
+            child.mass_x = resident_x;
+            child.mass_y = resident_y;
+            current = children[point_quadrant];
+            depth += 1;
+        }
+    }
+
+    fn split(&mut self, index: usize) -> [usize; 4] {
+        let (x, y, half) = {
+            let cell = &self.cells[index];
+            (cell.x, cell.y, cell.size / 2.0)
+        };
+        let first = self.cells.len();
+        self.cells.push(Cell::new(x, y, half));
+        self.cells.push(Cell::new(x + half, y, half));
+        self.cells.push(Cell::new(x, y + half, half));
+        self.cells.push(Cell::new(x + half, y + half, half));
+        let children = [first, first + 1, first + 2, first + 3];
+        self.cells[index].children = Some(children);
+        children
+    }
+
+    /// Approximate repulsion `spacing² / d` felt by a body at `point` from
+    /// every other body. The body itself (distance zero) is skipped.
+    pub fn repulsion(&self, point: (f64, f64), spacing: f64, theta: f64) -> (f64, f64) {
+        let mut force = (0.0, 0.0);
+        let mut stack = vec![0];
+        while let Some(index) = stack.pop() {
+            let cell = &self.cells[index];
+            if cell.mass == 0.0 {
+                continue;
+            }
+            let center = cell.center_of_mass();
+            let (dx, dy) = (point.0 - center.0, point.1 - center.1);
+            let distance_squared = dx * dx + dy * dy;
+            match cell.children {
+                Some(children) if cell.size * cell.size >= theta * theta * distance_squared => {
+                    stack.extend_from_slice(&children);
+                }
+                _ => {
+                    if distance_squared < MIN_DISTANCE_SQUARED {
+                        continue;
+                    }
+                    let push = spacing * spacing * cell.mass / distance_squared;
+                    force.0 += dx * push;
+                    force.1 += dy * push;
+                }
+            }
+        }
+        force
+    }
+}
+
+/// Barnes–Hut counterpart of `force::repulsive_forces`, O(n log n). Large
+/// inputs are split across the available CPU cores.
+pub fn repulsive_forces(positions: &[(f64, f64)], spacing: f64, theta: f64) -> Vec<(f64, f64)> {
+    let tree = QuadTree::build(positions);
+    let threads = thread::available_parallelism().map_or(1, |count| count.get());
+    if positions.len() < PARALLEL_THRESHOLD || threads == 1 {
+        return positions
+            .iter()
+            .map(|point| tree.repulsion(*point, spacing, theta))
+            .collect();
+    }
+    let chunk = positions.len().div_ceil(threads);
+    let mut forces = vec![(0.0, 0.0); positions.len()];
+    thread::scope(|scope| {
+        for (points, output) in positions.chunks(chunk).zip(forces.chunks_mut(chunk)) {
+            let tree = &tree;
+            scope.spawn(move || {
+                for (point, force) in points.iter().zip(output.iter_mut()) {
+                    *force = tree.repulsion(*point, spacing, theta);
+                }
+            });
+        }
+    });
+    forces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layouts::force;
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+
+    fn setup_random_points(count: usize) -> Vec<(f64, f64)> {
+        let mut rng = StdRng::seed_from_u64(7);
+        (0..count)
+            .map(|_| (rng.random_range(0.0..1000.0), rng.random_range(0.0..1000.0)))
+            .collect()
+    }
+
+    #[test]
+    fn test_barnes_hut_matches_exact_forces() -> Result<(), String> {
+        let points = setup_random_points(300);
+        let exact = force::repulsive_forces(&points, 50.0);
+        let approximate = repulsive_forces(&points, 50.0, 0.5);
+
+        let (mut error, mut magnitude) = (0.0, 0.0);
+        for (a, b) in exact.iter().zip(approximate.iter()) {
+            error += ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt();
+            magnitude += (a.0 * a.0 + a.1 * a.1).sqrt();
+        }
+        assert!(
+            error < 0.05 * magnitude,
+            "Relative error too high: {}",
+            error / magnitude
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_barnes_hut_parallel_is_consistent() -> Result<(), String> {
+        let points = setup_random_points(PARALLEL_THRESHOLD * 2);
+        let tree = QuadTree::build(&points);
+        let forces = repulsive_forces(&points, 50.0, DEFAULT_THETA);
+
+        for index in [0, PARALLEL_THRESHOLD, points.len() - 1] {
+            assert_eq!(
+                forces[index],
+                tree.repulsion(points[index], 50.0, DEFAULT_THETA)
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_barnes_hut_handles_coincident_points() -> Result<(), String> {
+        let points = vec![(1.0, 1.0); 10];
+        let forces = repulsive_forces(&points, 50.0, DEFAULT_THETA);
+
+        assert!(forces.iter().all(|(x, y)| x.is_finite() && y.is_finite()));
+        Ok(())
+    }
+}
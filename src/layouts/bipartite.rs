@@ -0,0 +1,92 @@
+use crate::{
+    graph::graph::Graph,
+    layouts::layout::{Layout, set_position, sorted_ids, undirected_neighbors},
+};
+use std::collections::{HashMap, VecDeque};
+
+/// Side of every node in a BFS 2-coloring, ignoring edge direction, and
+/// whether the coloring is proper (no edge inside one side).
+pub fn two_coloring(graph: &Graph) -> (HashMap<u32, bool>, bool) {
+    let neighbors = undirected_neighbors(graph);
+    let mut side: HashMap<u32, bool> = HashMap::new();
+    let mut proper = true;
+    for root in sorted_ids(graph) {
+        if side.contains_key(&root) {
+            continue;
+        }
+        side.insert(root, false);
+        let mut queue = VecDeque::from([root]);
+        while let Some(node) = queue.pop_front() {
+            let color = side[&node];
+            for next in neighbors[&node].iter() {
+                match side.get(next) {
+                    None => {
+                        side.insert(*next, !color);
+                        queue.push_back(*next);
+                    }
+                    Some(other) if *other == color => proper = false,
+                    Some(_) => {}
+                }
+            }
+        }
+    }
+    (side, proper)
+}
+
+/// Two columns from a 2-coloring. On a graph that is not bipartite the BFS
+/// parity is used anyway, so some edges stay inside a column.
+#[derive(Debug, PartialEq, Clone)]
+pub struct BipartiteLayout {
+    pub column_spacing: f64,
+    pub row_spacing: f64,
+}
+
+impl Default for BipartiteLayout {
+    fn default() -> Self {
+        BipartiteLayout {
+            column_spacing: 240.0,
+            row_spacing: 60.0,
+        }
+    }
+}
+
+impl Layout for BipartiteLayout {
+    fn apply(&mut self, graph: &mut Graph) {
+        let (side, _) = two_coloring(graph);
+        let mut rows = [0.0, 0.0];
+        for id in sorted_ids(graph) {
+            let column = side[&id] as usize;
+            let x = column as f64 * self.column_spacing;
+            set_position(graph, id, (x, rows[column] * self.row_spacing));
+            rows[column] += 1.0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layouts::layout::position;
+
+    #[test]
+    fn test_bipartite_layout_splits_sides() -> Result<(), String> {
+        let mut my_graph = Graph::from_list_of_edges(vec![(0, 1), (1, 2), (2, 3), (3, 0)], true);
+        assert!(two_coloring(&my_graph).1);
+
+        BipartiteLayout::default().apply(&mut my_graph);
+
+        assert_eq!(position(&my_graph, 0), Some((0.0, 0.0)));
+        assert_eq!(position(&my_graph, 2), Some((0.0, 60.0)));
+        assert_eq!(position(&my_graph, 1), Some((240.0, 0.0)));
+        assert_eq!(position(&my_graph, 3), Some((240.0, 60.0)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_two_coloring_detects_odd_cycle() -> Result<(), String> {
+        let my_graph = Graph::from_list_of_edges(vec![(0, 1), (1, 2), (2, 0)], false);
+
+        assert!(!two_coloring(&my_graph).1);
+        Ok(())
+    }
+}
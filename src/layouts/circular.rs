@@ -0,0 +1,96 @@
+use crate::{
+    graph::graph::Graph,
+    layouts::layout::{Layout, set_position, sorted_ids, undirected_neighbors},
+};
+use std::collections::HashSet;
+use std::f64::consts::TAU;
+
+/// Order in which nodes are placed around the ring.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CircularOrder {
+    Id,
+    /// Depth-first preorder, which keeps most tree edges short.
+    Dfs,
+}
+
+/// Puts every node on a single circle, clockwise from the top.
+#[derive(Debug, PartialEq, Clone)]
+pub struct CircularLayout {
+    pub order: CircularOrder,
+    /// Distance between neighbors on the ring; the radius follows from it.
+    pub spacing: f64,
+}
+
+impl CircularLayout {
+    pub fn new(order: CircularOrder) -> Self {
+        CircularLayout {
+            order,
+            spacing: 60.0,
+        }
+    }
+}
+
+/// Depth-first preorder over all components, roots and neighbors by id.
+pub fn dfs_order(graph: &Graph) -> Vec<u32> {
+    let neighbors = undirected_neighbors(graph);
+    let mut visited = HashSet::new();
+    let mut order = Vec::new();
+    for root in sorted_ids(graph) {
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            if !visited.insert(node) {
+                continue;
+            }
+            order.push(node);
+            stack.extend(
+                neighbors[&node]
+                    .iter()
+                    .rev()
+                    .filter(|n| !visited.contains(*n)),
+            );
+        }
+    }
+    order
+}
+
+impl Layout for CircularLayout {
+    fn apply(&mut self, graph: &mut Graph) {
+        let order = match self.order {
+            CircularOrder::Id => sorted_ids(graph),
+            CircularOrder::Dfs => dfs_order(graph),
+        };
+        let count = order.len() as f64;
+        let radius = (self.spacing * count / TAU).max(self.spacing);
+        for (index, id) in order.into_iter().enumerate() {
+            let angle = TAU * index as f64 / count;
+            set_position(graph, id, (radius * angle.sin(), -radius * angle.cos()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layouts::layout::position;
+
+    #[test]
+    fn test_circular_layout_uses_dfs_order() -> Result<(), String> {
+        let mut my_graph = Graph::from_list_of_edges(vec![(0, 3), (3, 1), (0, 2)], true);
+        assert_eq!(dfs_order(&my_graph), vec![0, 2, 3, 1]);
+
+        CircularLayout::new(CircularOrder::Dfs).apply(&mut my_graph);
+
+        let top = position(&my_graph, 0).unwrap();
+        let radius = top.1.abs();
+        assert!(top.0.abs() < 1e-9 && top.1 < 0.0);
+        for id in 1..4 {
+            let (x, y) = position(&my_graph, id).unwrap();
+            assert!(((x * x + y * y).sqrt() - radius).abs() < 1e-9);
+        }
+        assert!(
+            position(&my_graph, 2).unwrap().0 > 0.0,
+            "Second node is right of the top"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,100 @@
+use crate::{
+    graph::graph::Graph,
+    layouts::layout::{Layout, set_position, sorted_ids, undirected_neighbors},
+};
+use std::collections::{HashMap, VecDeque};
+use std::f64::consts::TAU;
+
+/// BFS-level layout: the source in the center and one ring per distance.
+/// Nodes the source cannot reach share an extra outermost ring. A missing
+/// source falls back to the lowest id, like the root of `TreeLayout`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ConcentricLayout {
+    pub source: u32,
+    pub ring_spacing: f64,
+}
+
+impl ConcentricLayout {
+    pub fn new(source: u32) -> Self {
+        ConcentricLayout {
+            source,
+            ring_spacing: 90.0,
+        }
+    }
+
+    /// Nodes grouped by BFS distance from the source (ignoring edge
+    /// direction), in discovery order; unreachable nodes come last.
+    pub fn levels(&self, graph: &Graph) -> Vec<Vec<u32>> {
+        let neighbors = undirected_neighbors(graph);
+        let mut distance: HashMap<u32, usize> = HashMap::new();
+        let mut levels: Vec<Vec<u32>> = Vec::new();
+        let source = match graph.nodes.contains_key(&self.source) {
+            true => Some(self.source),
+            false => sorted_ids(graph).first().copied(),
+        };
+        if let Some(source) = source {
+            distance.insert(source, 0);
+            let mut queue = VecDeque::from([source]);
+            while let Some(node) = queue.pop_front() {
+                let level = distance[&node];
+                if levels.len() == level {
+                    levels.push(Vec::new());
+                }
+                levels[level].push(node);
+                for next in neighbors[&node].iter() {
+                    if !distance.contains_key(next) {
+                        distance.insert(*next, level + 1);
+                        queue.push_back(*next);
+                    }
+                }
+            }
+        }
+        let unreachable: Vec<u32> = sorted_ids(graph)
+            .into_iter()
+            .filter(|id| !distance.contains_key(id))
+            .collect();
+        if !unreachable.is_empty() {
+            levels.push(unreachable);
+        }
+        levels
+    }
+}
+
+impl Layout for ConcentricLayout {
+    fn apply(&mut self, graph: &mut Graph) {
+        for (ring, nodes) in self.levels(graph).into_iter().enumerate() {
+            let radius = ring as f64 * self.ring_spacing;
+            let count = nodes.len() as f64;
+            for (index, id) in nodes.into_iter().enumerate() {
+                let angle = TAU * index as f64 / count;
+                set_position(graph, id, (radius * angle.sin(), -radius * angle.cos()));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layouts::layout::position;
+
+    #[test]
+    fn test_concentric_rings_follow_distance() -> Result<(), String> {
+        let mut my_graph = Graph::from_list_of_edges(vec![(0, 1), (0, 2), (2, 3)], true);
+        my_graph.add_new_node_to_graph(4, 10.0, 10.0);
+        let mut layout = ConcentricLayout::new(2);
+
+        assert_eq!(
+            layout.levels(&my_graph),
+            vec![vec![2], vec![0, 3], vec![1], vec![4]]
+        );
+        layout.apply(&mut my_graph);
+
+        assert_eq!(position(&my_graph, 2), Some((0.0, 0.0)));
+        let (x, y) = position(&my_graph, 1).unwrap();
+        assert!(((x * x + y * y).sqrt() - 2.0 * layout.ring_spacing).abs() < 1e-9);
+        let missing = ConcentricLayout::new(9).levels(&my_graph);
+        assert_eq!(missing[0], vec![0], "Lowest id stands in for the source");
+        Ok(())
+    }
+}
@@ -0,0 +1,277 @@
+use crate::{
+    graph::graph::Graph,
+    layouts::barnes_hut::{self, DEFAULT_THETA},
+    layouts::layout::{
+        GOLDEN_ANGLE, Layout, position, set_position, sorted_ids, undirected_neighbors,
+    },
+};
+use std::collections::HashMap;
+
+const MIN_DISTANCE: f64 = 0.01;
+/// Above this many nodes the exact O(n²) repulsion is replaced by Barnes–Hut.
+const BARNES_HUT_THRESHOLD: usize = 1000;
+
+/// Index-based copy of a graph for the force computations: positions and
+/// pins by node index and each undirected edge once.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Embedding {
+    pub ids: Vec<u32>,
+    pub positions: Vec<(f64, f64)>,
+    pub pinned: Vec<bool>,
+    pub edges: Vec<(usize, usize)>,
+    edge_count: usize,
+}
+
+fn edge_count(graph: &Graph) -> usize {
+    graph
+        .nodes
+        .values()
+        .map(|node| node.runtime.edges.len())
+        .sum()
+}
+
+impl Embedding {
+    pub fn from_graph(graph: &Graph) -> Self {
+        let ids = sorted_ids(graph);
+        let index: HashMap<u32, usize> = ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+        let mut edges = Vec::new();
+        for (u, neighbors) in undirected_neighbors(graph) {
+            for v in neighbors.into_iter().filter(|v| u < *v) {
+                edges.push((index[&u], index[&v]));
+            }
+        }
+        edges.sort_unstable();
+        Embedding {
+            positions: ids
+                .iter()
+                .map(|id| position(graph, *id).unwrap_or_default())
+                .collect(),
+            pinned: ids
+                .iter()
+                .map(|id| graph.nodes[id].gui_model.pinned)
+                .collect(),
+            ids,
+            edges,
+            edge_count: edge_count(graph),
+        }
+    }
+    /// Reloads positions and pins (the user may have dragged nodes).
+    /// Returns `false` when nodes or edges changed and it must be rebuilt.
+    pub fn refresh(&mut self, graph: &Graph) -> bool {
+        if self.ids.len() != graph.nodes.len() || self.edge_count != edge_count(graph) {
+            return false;
+        }
+        for (i, id) in self.ids.iter().enumerate() {
+            let Some(node) = graph.nodes.get(id) else {
+                return false;
+            };
+            self.positions[i] = (
+                node.gui_model.position_x.into_inner(),
+                node.gui_model.position_y.into_inner(),
+            );
+            self.pinned[i] = node.gui_model.pinned;
+        }
+        true
+    }
+    pub fn write_back(&self, graph: &mut Graph) {
+        for (id, point) in self.ids.iter().zip(self.positions.iter()) {
+            set_position(graph, *id, *point);
+        }
+    }
+
+    /// Spreads unpinned nodes sharing a position (e.g. everything created at
+    /// the origin by `Graph::from_list_of_edges`) on a sunflower spiral, since
+    /// the forces between coincident nodes have no direction.
+    pub fn scatter_overlapping(&mut self, spacing: f64) {
+        let mut seen: HashMap<(u64, u64), usize> = HashMap::new();
+        let mut overlapping = 0;
+        for i in 0..self.positions.len() {
+            let (x, y) = self.positions[i];
+            if seen.insert((x.to_bits(), y.to_bits()), i).is_none() || self.pinned[i] {
+                continue;
+            }
+            overlapping += 1;
+            let radius = spacing * 0.5 * (overlapping as f64).sqrt();
+            let angle = overlapping as f64 * GOLDEN_ANGLE;
+            self.positions[i] = (x + radius * angle.cos(), y + radius * angle.sin());
+        }
+    }
+
+    /// One Fruchterman–Reingold iteration: nodes move along the net force,
+    /// by at most `temperature`.
+    pub fn iterate(&mut self, spacing: f64, gravity: f64, temperature: f64, theta: f64) {
+        let mut forces = if theta > 0.0 && self.positions.len() > BARNES_HUT_THRESHOLD {
+            barnes_hut::repulsive_forces(&self.positions, spacing, theta)
+        } else {
+            repulsive_forces(&self.positions, spacing)
+        };
+        for (i, j) in self.edges.iter().copied() {
+            let (dx, dy) = delta(self.positions[i], self.positions[j]);
+            let distance = (dx * dx + dy * dy).sqrt().max(MIN_DISTANCE);
+            let pull = distance / spacing;
+            forces[i].0 -= dx * pull;
+            forces[i].1 -= dy * pull;
+            forces[j].0 += dx * pull;
+            forces[j].1 += dy * pull;
+        }
+
+        let count = self.positions.len().max(1) as f64;
+        let center = self
+            .positions
+            .iter()
+            .fold((0.0, 0.0), |(x, y), p| (x + p.0 / count, y + p.1 / count));
+        for (i, (x, y)) in self.positions.iter_mut().enumerate() {
+            if self.pinned[i] {
+                continue;
+            }
+            let fx = forces[i].0 - gravity * (*x - center.0);
+            let fy = forces[i].1 - gravity * (*y - center.1);
+            let length = (fx * fx + fy * fy).sqrt();
+            if length < MIN_DISTANCE {
+                continue;
+            }
+            let moved = length.min(temperature);
+            *x += fx / length * moved;
+            *y += fy / length * moved;
+        }
+    }
+}
+
+/// Fruchterman–Reingold spring embedder. Every call to `step` runs one
+/// iteration and cools the system a bit, so the GUI can animate it frame by
+/// frame; `apply` runs it to completion. Large graphs use the Barnes–Hut
+/// approximation for the repulsive forces.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ForceLayout {
+    /// Ideal distance between two connected nodes.
+    pub spacing: f64,
+    /// Maximum distance a node may move in the next iteration.
+    pub temperature: f64,
+    pub cooling: f64,
+    pub min_temperature: f64,
+    /// Pull towards the centroid so disconnected components stay close.
+    pub gravity: f64,
+    /// Barnes–Hut opening angle; `0.0` always uses the exact forces.
+    pub theta: f64,
+    pub iteration: u32,
+    pub max_iterations: u32,
+    embedding: Option<Embedding>,
+}
+
+impl ForceLayout {
+    pub fn new(graph: &Graph) -> Self {
+        let spacing = 80.0;
+        ForceLayout {
+            spacing,
+            temperature: spacing * (graph.nodes.len().max(1) as f64).sqrt(),
+            cooling: 0.95,
+            min_temperature: 0.5,
+            gravity: 0.05,
+            theta: DEFAULT_THETA,
+            iteration: 0,
+            max_iterations: 500,
+            embedding: None,
+        }
+    }
+    pub fn is_done(&self) -> bool {
+        self.temperature < self.min_temperature || self.iteration >= self.max_iterations
+    }
+
+    /// Runs one iteration. Returns `false` once the layout has cooled down.
+    pub fn step(&mut self, graph: &mut Graph) -> bool {
+        if self.is_done() {
+            return false;
+        }
+        let mut embedding = self
+            .embedding
+            .take()
+            .and_then(|mut embedding| embedding.refresh(graph).then_some(embedding))
+            .unwrap_or_else(|| Embedding::from_graph(graph));
+        if self.iteration == 0 {
+            embedding.scatter_overlapping(self.spacing);
+        }
+        embedding.iterate(self.spacing, self.gravity, self.temperature, self.theta);
+        embedding.write_back(graph);
+        self.embedding = Some(embedding);
+
+        self.temperature *= self.cooling;
+        self.iteration += 1;
+        true
+    }
+}
+
+impl Layout for ForceLayout {
+    fn apply(&mut self, graph: &mut Graph) {
+        while self.step(graph) {}
+    }
+}
+
+fn delta(a: (f64, f64), b: (f64, f64)) -> (f64, f64) {
+    (a.0 - b.0, a.1 - b.1)
+}
+
+/// Exact pairwise repulsion `spacing² / d` for every node, O(n²).
+pub fn repulsive_forces(positions: &[(f64, f64)], spacing: f64) -> Vec<(f64, f64)> {
+    let mut forces = vec![(0.0, 0.0); positions.len()];
+    for i in 0..positions.len() {
+        for j in (i + 1)..positions.len() {
+            let (dx, dy) = delta(positions[i], positions[j]);
+            let distance_squared = (dx * dx + dy * dy).max(MIN_DISTANCE);
+            let push = spacing * spacing / distance_squared;
+            forces[i].0 += dx * push;
+            forces[i].1 += dy * push;
+            forces[j].0 -= dx * push;
+            forces[j].1 -= dy * push;
+        }
+    }
+    forces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn setup_left_chain() -> Graph {
+        let edges = vec![(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 6)];
+        Graph::from_list_of_edges(edges, false)
+    }
+    fn distance(graph: &Graph, u: u32, v: u32) -> f64 {
+        let (a, b) = (position(graph, u).unwrap(), position(graph, v).unwrap());
+        ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt()
+    }
+
+    #[test]
+    fn test_force_layout_spreads_nodes() -> Result<(), String> {
+        let mut my_graph = setup_left_chain();
+        let mut layout = ForceLayout::new(&my_graph);
+
+        layout.apply(&mut my_graph);
+
+        assert!(layout.is_done());
+        for u in 0..7 {
+            for v in (u + 1)..7 {
+                assert!(distance(&my_graph, u, v) > 10.0, "{} and {} overlap", u, v);
+            }
+        }
+        assert!(
+            distance(&my_graph, 0, 1) < distance(&my_graph, 0, 6),
+            "Neighbors should end up closer than the chain ends"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_force_layout_keeps_pinned_nodes() -> Result<(), String> {
+        let mut my_graph = setup_left_chain();
+        my_graph.add_new_node_to_graph(7, 0.0, 0.0);
+        let pinned = my_graph.nodes.get_mut(&3).unwrap();
+        pinned.gui_model.pinned = true;
+        let mut layout = ForceLayout::new(&my_graph);
+
+        assert!(layout.step(&mut my_graph));
+        layout.apply(&mut my_graph);
+
+        assert_eq!(position(&my_graph, 3), Some((0.0, 0.0)));
+        assert!(!layout.step(&mut my_graph), "A cooled layout stops moving");
+        Ok(())
+    }
+}
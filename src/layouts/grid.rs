@@ -0,0 +1,58 @@
+use crate::{
+    graph::graph::Graph,
+    layouts::layout::{Layout, set_position, sorted_ids},
+};
+
+/// Places the nodes row by row in id order.
+#[derive(Debug, PartialEq, Clone)]
+pub struct GridLayout {
+    /// Nodes per row; `None` makes the grid as square as possible.
+    pub columns: Option<usize>,
+    pub spacing: f64,
+}
+
+impl Default for GridLayout {
+    fn default() -> Self {
+        GridLayout {
+            columns: None,
+            spacing: 80.0,
+        }
+    }
+}
+
+impl Layout for GridLayout {
+    fn apply(&mut self, graph: &mut Graph) {
+        let ids = sorted_ids(graph);
+        let columns = self
+            .columns
+            .unwrap_or_else(|| (ids.len() as f64).sqrt().ceil() as usize)
+            .max(1);
+        for (index, id) in ids.into_iter().enumerate() {
+            let (row, column) = (index / columns, index % columns);
+            set_position(
+                graph,
+                id,
+                (column as f64 * self.spacing, row as f64 * self.spacing),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layouts::layout::position;
+
+    #[test]
+    fn test_grid_layout_fills_rows() -> Result<(), String> {
+        let mut my_graph = Graph::from_list_of_edges(vec![(0, 1), (2, 3), (4, 5)], true);
+        my_graph.add_new_node_to_graph(6, 0.0, 0.0);
+
+        GridLayout::default().apply(&mut my_graph);
+
+        assert_eq!(position(&my_graph, 2), Some((160.0, 0.0)));
+        assert_eq!(position(&my_graph, 3), Some((0.0, 80.0)));
+        assert_eq!(position(&my_graph, 6), Some((0.0, 160.0)));
+        Ok(())
+    }
+}
@@ -0,0 +1,370 @@
+use crate::{
+    graph::graph::Graph,
+    layouts::layout::{Layout, set_position, sorted_ids},
+};
+use std::collections::{HashMap, HashSet};
+
+/// Sugiyama-style layered layout: edges point from top to bottom, long edges
+/// are routed through dummy nodes and the order inside every layer is chosen
+/// to reduce crossings. Cycles are broken by reversing a feedback arc set
+/// for the duration of the layout.
+#[derive(Debug, PartialEq, Clone)]
+pub struct HierarchicalLayout {
+    /// Vertical distance between two layers.
+    pub layer_spacing: f64,
+    /// Minimum horizontal distance between two nodes of a layer.
+    pub node_spacing: f64,
+    /// Number of down-and-up barycenter sweeps.
+    pub sweeps: u32,
+}
+
+impl Default for HierarchicalLayout {
+    fn default() -> Self {
+        HierarchicalLayout {
+            layer_spacing: 100.0,
+            node_spacing: 80.0,
+            sweeps: 12,
+        }
+    }
+}
+
+/// Edges between distinct nodes; an undirected pair counts once, from the
+/// smaller id.
+fn directed_edges(graph: &Graph) -> Vec<(u32, u32)> {
+    let mut edges = Vec::new();
+    for node in graph.nodes.values() {
+        for (end, edge) in node.runtime.edges.iter() {
+            if *end == node.id || !graph.nodes.contains_key(end) {
+                continue;
+            }
+            if !edge.runtime.directed && *end < node.id {
+                continue;
+            }
+            edges.push((node.id, *end));
+        }
+    }
+    edges.sort_unstable();
+    edges.dedup();
+    edges
+}
+
+/// Back edges of a depth-first search: reversing them makes the graph acyclic.
+pub fn feedback_arcs(ids: &[u32], edges: &[(u32, u32)]) -> Vec<(u32, u32)> {
+    let mut adjacency: HashMap<u32, Vec<u32>> = HashMap::new();
+    for (start, end) in edges {
+        adjacency.entry(*start).or_default().push(*end);
+    }
+    // 0 = unvisited, 1 = on the DFS stack, 2 = finished.
+    let mut state: HashMap<u32, u8> = ids.iter().map(|id| (*id, 0)).collect();
+    let mut arcs = Vec::new();
+    for root in ids {
+        if state[root] != 0 {
+            continue;
+        }
+        state.insert(*root, 1);
+        let mut stack = vec![(*root, 0)];
+        while let Some((node, next)) = stack.pop() {
+            let neighbors = adjacency.get(&node).map_or(&[][..], |list| list.as_slice());
+            match neighbors.get(next) {
+                Some(end) => {
+                    stack.push((node, next + 1));
+                    match state[end] {
+                        0 => {
+                            state.insert(*end, 1);
+                            stack.push((*end, 0));
+                        }
+                        1 => arcs.push((node, *end)),
+                        _ => {}
+                    }
+                }
+                None => {
+                    state.insert(node, 2);
+                }
+            }
+        }
+    }
+    arcs
+}
+
+/// Longest-path layering: sources on layer 0, every edge goes down at least
+/// one layer. `edges` must be acyclic.
+fn assign_layers(ids: &[u32], edges: &[(u32, u32)]) -> HashMap<u32, usize> {
+    let mut in_degree: HashMap<u32, usize> = ids.iter().map(|id| (*id, 0)).collect();
+    let mut adjacency: HashMap<u32, Vec<u32>> = HashMap::new();
+    for (start, end) in edges {
+        adjacency.entry(*start).or_default().push(*end);
+        *in_degree.get_mut(end).unwrap() += 1;
+    }
+    let mut layers: HashMap<u32, usize> = ids.iter().map(|id| (*id, 0)).collect();
+    let mut ready: Vec<u32> = ids
+        .iter()
+        .filter(|id| in_degree[id] == 0)
+        .copied()
+        .collect();
+    while let Some(node) = ready.pop() {
+        for end in adjacency.get(&node).into_iter().flatten() {
+            let layer = layers[&node] + 1;
+            if layers[end] < layer {
+                layers.insert(*end, layer);
+            }
+            let degree = in_degree.get_mut(end).unwrap();
+            *degree -= 1;
+            if *degree == 0 {
+                ready.push(*end);
+            }
+        }
+    }
+    layers
+}
+
+/// Proper layered graph: every edge connects two consecutive layers.
+/// Vertices `0..ids.len()` are the real nodes, the rest are dummies.
+struct Layering {
+    layers: Vec<Vec<usize>>,
+    /// Neighbors in the layer below.
+    down: Vec<Vec<usize>>,
+    /// Neighbors in the layer above.
+    up: Vec<Vec<usize>>,
+    layer_of: Vec<usize>,
+}
+
+impl Layering {
+    fn new(ids: &[u32], edges: &[(u32, u32)], layer_of: &HashMap<u32, usize>) -> Self {
+        let index: HashMap<u32, usize> = ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+        let mut layering = Layering {
+            layers: Vec::new(),
+            down: vec![Vec::new(); ids.len()],
+            up: vec![Vec::new(); ids.len()],
+            layer_of: ids.iter().map(|id| layer_of[id]).collect(),
+        };
+        for (start, end) in edges {
+            let mut previous = index[start];
+            for layer in (layer_of[start] + 1)..layer_of[end] {
+                let dummy = layering.layer_of.len();
+                layering.layer_of.push(layer);
+                layering.down.push(Vec::new());
+                layering.up.push(Vec::new());
+                layering.connect(previous, dummy);
+                previous = dummy;
+            }
+            layering.connect(previous, index[end]);
+        }
+        let depth = layering.layer_of.iter().max().map_or(0, |layer| layer + 1);
+        layering.layers = vec![Vec::new(); depth];
+        for (vertex, layer) in layering.layer_of.iter().enumerate() {
+            layering.layers[*layer].push(vertex);
+        }
+        layering
+    }
+    fn connect(&mut self, upper: usize, lower: usize) {
+        self.down[upper].push(lower);
+        self.up[lower].push(upper);
+    }
+
+    fn positions(&self) -> Vec<usize> {
+        let mut positions = vec![0; self.layer_of.len()];
+        for layer in self.layers.iter() {
+            for (position, vertex) in layer.iter().enumerate() {
+                positions[*vertex] = position;
+            }
+        }
+        positions
+    }
+
+    /// Crossings between every pair of consecutive layers, counted as
+    /// inversions with a Fenwick tree.
+    fn crossings(&self) -> usize {
+        let positions = self.positions();
+        let mut total = 0;
+        for pair in self.layers.windows(2) {
+            let mut edges: Vec<(usize, usize)> = pair[0]
+                .iter()
+                .flat_map(|upper| {
+                    self.down[*upper]
+                        .iter()
+                        .map(|lower| (positions[*upper], positions[*lower]))
+                })
+                .collect();
+            edges.sort_unstable();
+            let mut tree = vec![0usize; pair[1].len() + 1];
+            for (inserted, (_, lower)) in edges.iter().enumerate() {
+                let mut not_greater = 0;
+                let mut i = lower + 1;
+                while i > 0 {
+                    not_greater += tree[i];
+                    i -= i & i.wrapping_neg();
+                }
+                total += inserted - not_greater;
+                let mut i = lower + 1;
+                while i < tree.len() {
+                    tree[i] += 1;
+                    i += i & i.wrapping_neg();
+                }
+            }
+        }
+        total
+    }
+
+    /// Reorders `layer` by the mean position of its neighbors in the adjacent
+    /// layer; vertices without neighbors keep their position.
+    fn sort_by_barycenter(&mut self, layer: usize, downward: bool) {
+        let positions = self.positions();
+        let neighbors = if downward { &self.up } else { &self.down };
+        let mut keyed: Vec<(f64, usize)> = self.layers[layer]
+            .iter()
+            .map(|vertex| {
+                let list = &neighbors[*vertex];
+                let key = match list.len() {
+                    0 => positions[*vertex] as f64,
+                    len => list.iter().map(|n| positions[*n] as f64).sum::<f64>() / len as f64,
+                };
+                (key, *vertex)
+            })
+            .collect();
+        keyed.sort_by(|a, b| a.0.total_cmp(&b.0));
+        self.layers[layer] = keyed.into_iter().map(|(_, vertex)| vertex).collect();
+    }
+
+    fn minimize_crossings(&mut self, sweeps: u32) {
+        let mut best = (self.crossings(), self.layers.clone());
+        for _ in 0..sweeps {
+            for layer in 1..self.layers.len() {
+                self.sort_by_barycenter(layer, true);
+            }
+            for layer in (0..self.layers.len().saturating_sub(1)).rev() {
+                self.sort_by_barycenter(layer, false);
+            }
+            let crossings = self.crossings();
+            if crossings < best.0 {
+                best = (crossings, self.layers.clone());
+            }
+            if crossings == 0 {
+                break;
+            }
+        }
+        self.layers = best.1;
+    }
+
+    /// Horizontal coordinates: start evenly spaced, then pull every vertex
+    /// towards its neighbors while keeping the layer order and spacing.
+    fn coordinates(&self, spacing: f64) -> Vec<f64> {
+        let mut x = vec![0.0; self.layer_of.len()];
+        for layer in self.layers.iter() {
+            let offset = (layer.len() as f64 - 1.0) * spacing / 2.0;
+            for (position, vertex) in layer.iter().enumerate() {
+                x[*vertex] = position as f64 * spacing - offset;
+            }
+        }
+        for _ in 0..8 {
+            for layer in self.layers.iter() {
+                let desired: Vec<f64> = layer
+                    .iter()
+                    .map(|vertex| {
+                        let neighbors = self.up[*vertex].iter().chain(self.down[*vertex].iter());
+                        let (sum, count) = neighbors.fold((0.0, 0), |(s, c), n| (s + x[*n], c + 1));
+                        if count == 0 {
+                            x[*vertex]
+                        } else {
+                            sum / count as f64
+                        }
+                    })
+                    .collect();
+                let mut placed = desired.clone();
+                for i in 1..placed.len() {
+                    placed[i] = placed[i].max(placed[i - 1] + spacing);
+                }
+                // Shift the whole layer back so it stays centered on what
+                // the vertices asked for.
+                let shift = desired
+                    .iter()
+                    .zip(placed.iter())
+                    .map(|(d, p)| p - d)
+                    .sum::<f64>()
+                    / placed.len().max(1) as f64;
+                for (vertex, position) in layer.iter().zip(placed) {
+                    x[*vertex] = position - shift;
+                }
+            }
+        }
+        x
+    }
+}
+
+impl Layout for HierarchicalLayout {
+    fn apply(&mut self, graph: &mut Graph) {
+        let ids = sorted_ids(graph);
+        let mut edges = directed_edges(graph);
+        let reversed: HashSet<(u32, u32)> = feedback_arcs(&ids, &edges).into_iter().collect();
+        for edge in edges.iter_mut() {
+            if reversed.contains(edge) {
+                *edge = (edge.1, edge.0);
+            }
+        }
+        edges.sort_unstable();
+        edges.dedup();
+
+        let layer_of = assign_layers(&ids, &edges);
+        let mut layering = Layering::new(&ids, &edges, &layer_of);
+        layering.minimize_crossings(self.sweeps);
+        let x = layering.coordinates(self.node_spacing);
+        for (vertex, id) in ids.iter().enumerate() {
+            let y = layering.layer_of[vertex] as f64 * self.layer_spacing;
+            set_position(graph, *id, (x[vertex], y));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layouts::layout::position;
+
+    fn setup_dag() -> Graph {
+        let edges = vec![(0, 1), (0, 2), (1, 3), (2, 3), (0, 3), (3, 4)];
+        Graph::from_list_of_edges(edges, false)
+    }
+
+    #[test]
+    fn test_hierarchical_edges_point_down() -> Result<(), String> {
+        let mut my_graph = setup_dag();
+        HierarchicalLayout::default().apply(&mut my_graph);
+
+        for (start, end) in directed_edges(&my_graph) {
+            let (upper, lower) = (position(&my_graph, start), position(&my_graph, end));
+            assert!(upper.unwrap().1 < lower.unwrap().1, "{} -> {}", start, end);
+        }
+        let (one, two) = (
+            position(&my_graph, 1).unwrap(),
+            position(&my_graph, 2).unwrap(),
+        );
+        assert_eq!(one.1, two.1);
+        assert!((one.0 - two.0).abs() >= 80.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_feedback_arcs_break_cycles() -> Result<(), String> {
+        let edges = vec![(0, 1), (1, 2), (2, 0), (2, 3), (3, 1)];
+        let arcs = feedback_arcs(&[0, 1, 2, 3], &edges);
+
+        assert_eq!(arcs, vec![(2, 0), (3, 1)]);
+        let mut my_graph = Graph::from_list_of_edges(edges, false);
+        HierarchicalLayout::default().apply(&mut my_graph);
+        assert!(position(&my_graph, 0).unwrap().1 < position(&my_graph, 3).unwrap().1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_barycenter_removes_crossings() -> Result<(), String> {
+        // Two parallel chains wired crosswise: 0 -> 3, 1 -> 2.
+        let ids = [0, 1, 2, 3];
+        let edges = vec![(0, 3), (1, 2)];
+        let layers = assign_layers(&ids, &edges);
+        let mut layering = Layering::new(&ids, &edges, &layers);
+
+        assert_eq!(layering.crossings(), 1);
+        layering.minimize_crossings(4);
+        assert_eq!(layering.crossings(), 0);
+        Ok(())
+    }
+}
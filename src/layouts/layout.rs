@@ -0,0 +1,85 @@
+use crate::graph::graph::Graph;
+use ordered_float::OrderedFloat;
+use std::collections::HashMap;
+
+/// 360° / φ², in radians: successive multiples never line up, which spreads
+/// points (or hues) evenly around a circle.
+pub const GOLDEN_ANGLE: f64 = 2.399_963_229_728_653;
+
+/// Fills the `GUIModel` positions of a graph. Pinned nodes keep their place.
+pub trait Layout {
+    fn apply(&mut self, graph: &mut Graph);
+}
+
+/// Node ids in ascending order, so layouts don't depend on `HashMap` order.
+pub fn sorted_ids(graph: &Graph) -> Vec<u32> {
+    let mut ids: Vec<u32> = graph.nodes.keys().copied().collect();
+    ids.sort_unstable();
+    ids
+}
+
+pub fn position(graph: &Graph, id: u32) -> Option<(f64, f64)> {
+    let node = graph.nodes.get(&id)?;
+    Some((
+        node.gui_model.position_x.into_inner(),
+        node.gui_model.position_y.into_inner(),
+    ))
+}
+
+/// Moves an unpinned node; pinned nodes are left untouched.
+pub fn set_position(graph: &mut Graph, id: u32, (x, y): (f64, f64)) {
+    if let Some(node) = graph.nodes.get_mut(&id)
+        && !node.gui_model.pinned
+    {
+        node.gui_model.position_x = OrderedFloat(x);
+        node.gui_model.position_y = OrderedFloat(y);
+    }
+}
+
+/// Neighbors of every node ignoring edge direction, sorted by id.
+pub fn undirected_neighbors(graph: &Graph) -> HashMap<u32, Vec<u32>> {
+    let mut neighbors: HashMap<u32, Vec<u32>> =
+        graph.nodes.keys().map(|id| (*id, Vec::new())).collect();
+    for node in graph.nodes.values() {
+        for end in node.runtime.edges.keys() {
+            if *end == node.id || !graph.nodes.contains_key(end) {
+                continue;
+            }
+            neighbors.entry(node.id).or_default().push(*end);
+            neighbors.entry(*end).or_default().push(node.id);
+        }
+    }
+    for list in neighbors.values_mut() {
+        list.sort_unstable();
+        list.dedup();
+    }
+    neighbors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_undirected_neighbors_ignore_direction() -> Result<(), String> {
+        let my_graph = Graph::from_list_of_edges(vec![(0, 1), (2, 1), (1, 0)], false);
+        let neighbors = undirected_neighbors(&my_graph);
+
+        assert_eq!(neighbors[&1], vec![0, 2]);
+        assert_eq!(neighbors[&2], vec![1]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_position_respects_pins() -> Result<(), String> {
+        let mut my_graph = Graph::from_list_of_edges(vec![(0, 1)], false);
+        my_graph.nodes.get_mut(&1).unwrap().gui_model.pinned = true;
+
+        set_position(&mut my_graph, 0, (5.0, 6.0));
+        set_position(&mut my_graph, 1, (5.0, 6.0));
+
+        assert_eq!(position(&my_graph, 0), Some((5.0, 6.0)));
+        assert_eq!(position(&my_graph, 1), Some((0.0, 0.0)));
+        Ok(())
+    }
+}
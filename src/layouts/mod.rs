@@ -0,0 +1,10 @@
+pub mod barnes_hut;
+pub mod bipartite;
+pub mod circular;
+pub mod concentric;
+pub mod force;
+pub mod grid;
+pub mod hierarchical;
+pub mod layout;
+pub mod multilevel;
+pub mod tree;
@@ -0,0 +1,222 @@
+use crate::{
+    graph::graph::Graph,
+    layouts::barnes_hut::DEFAULT_THETA,
+    layouts::force::Embedding,
+    layouts::layout::{GOLDEN_ANGLE, Layout},
+};
+
+/// Multilevel force layout for large graphs: the graph is repeatedly coarsened
+/// by collapsing matched edges, the coarsest graph is laid out from scratch
+/// and every finer level starts from its parent's positions, so only a few
+/// cheap refinement iterations are needed per level.
+#[derive(Debug, PartialEq, Clone)]
+pub struct MultilevelLayout {
+    pub spacing: f64,
+    /// Coarsening stops once a level has at most this many nodes.
+    pub coarsest_size: usize,
+    pub coarsest_iterations: u32,
+    pub refine_iterations: u32,
+    pub gravity: f64,
+    pub theta: f64,
+}
+
+impl Default for MultilevelLayout {
+    fn default() -> Self {
+        MultilevelLayout {
+            spacing: 80.0,
+            coarsest_size: 50,
+            coarsest_iterations: 200,
+            refine_iterations: 30,
+            gravity: 0.05,
+            theta: DEFAULT_THETA,
+        }
+    }
+}
+
+/// One coarsening step: `parent[i]` is the coarse node fine node `i` collapsed into.
+struct Level {
+    parent: Vec<usize>,
+    coarse: Embedding,
+}
+
+/// Greedy edge matching: every unmatched node is merged with its first
+/// unmatched neighbor, or joins a neighbor's group when all of them are taken
+/// (otherwise the leaves of a star would never shrink). Pinned nodes are
+/// never merged.
+fn coarsen(fine: &Embedding) -> Level {
+    let count = fine.positions.len();
+    let mut neighbors = vec![Vec::new(); count];
+    for (i, j) in fine.edges.iter().copied() {
+        neighbors[i].push(j);
+        neighbors[j].push(i);
+    }
+    let mut parent = vec![usize::MAX; count];
+    let mut coarse = Embedding::default();
+    let mut sizes: Vec<f64> = Vec::new();
+    for i in 0..count {
+        if parent[i] != usize::MAX || fine.pinned[i] {
+            continue;
+        }
+        let mergeable = |j: &usize| *j != i && !fine.pinned[*j];
+        let partner = neighbors[i]
+            .iter()
+            .copied()
+            .find(|j| mergeable(j) && parent[*j] == usize::MAX);
+        let group = match partner {
+            Some(_) => None,
+            None => neighbors[i]
+                .iter()
+                .find(|j| mergeable(j))
+                .map(|j| parent[*j]),
+        };
+        let index = match group {
+            Some(index) => index,
+            None => {
+                coarse.ids.push(fine.ids[i]);
+                coarse.positions.push((0.0, 0.0));
+                coarse.pinned.push(false);
+                sizes.push(0.0);
+                coarse.positions.len() - 1
+            }
+        };
+        for member in std::iter::once(i).chain(partner) {
+            parent[member] = index;
+            let size = sizes[index];
+            let (x, y) = &mut coarse.positions[index];
+            *x = (*x * size + fine.positions[member].0) / (size + 1.0);
+            *y = (*y * size + fine.positions[member].1) / (size + 1.0);
+            sizes[index] += 1.0;
+        }
+    }
+    for i in (0..count).filter(|i| fine.pinned[*i]) {
+        parent[i] = coarse.positions.len();
+        coarse.ids.push(fine.ids[i]);
+        coarse.positions.push(fine.positions[i]);
+        coarse.pinned.push(true);
+    }
+    let mut edges: Vec<(usize, usize)> = fine
+        .edges
+        .iter()
+        .map(|(i, j)| (parent[*i].min(parent[*j]), parent[*i].max(parent[*j])))
+        .filter(|(i, j)| i != j)
+        .collect();
+    edges.sort_unstable();
+    edges.dedup();
+    coarse.edges = edges;
+    Level { parent, coarse }
+}
+
+/// Places every fine node at its parent's position; siblings are spread
+/// around it so they don't start on top of each other.
+fn prolong(fine: &mut Embedding, level: &Level, spacing: f64) {
+    let mut children = vec![0; level.coarse.positions.len()];
+    for (i, parent) in level.parent.iter().enumerate() {
+        if fine.pinned[i] {
+            continue;
+        }
+        let (x, y) = level.coarse.positions[*parent];
+        let offset = children[*parent] as f64;
+        children[*parent] += 1;
+        let radius = spacing * 0.25 * offset.sqrt();
+        let angle = offset * GOLDEN_ANGLE;
+        fine.positions[i] = (x + radius * angle.cos(), y + radius * angle.sin());
+    }
+}
+
+impl MultilevelLayout {
+    fn refine(&self, embedding: &mut Embedding, iterations: u32, temperature: f64) {
+        let mut temperature = temperature;
+        let cooling = (0.5 / temperature).powf(1.0 / iterations.max(1) as f64);
+        for _ in 0..iterations {
+            embedding.iterate(self.spacing, self.gravity, temperature, self.theta);
+            temperature *= cooling;
+        }
+    }
+}
+
+impl Layout for MultilevelLayout {
+    fn apply(&mut self, graph: &mut Graph) {
+        let mut finest = Embedding::from_graph(graph);
+        finest.scatter_overlapping(self.spacing);
+
+        let mut levels: Vec<Level> = Vec::new();
+        let mut current = finest.clone();
+        while current.positions.len() > self.coarsest_size.max(2) {
+            let level = coarsen(&current);
+            if level.coarse.positions.len() as f64 > 0.95 * current.positions.len() as f64 {
+                break;
+            }
+            current = level.coarse.clone();
+            levels.push(level);
+        }
+
+        let size = (current.positions.len().max(1) as f64).sqrt();
+        self.refine(&mut current, self.coarsest_iterations, self.spacing * size);
+        while let Some(mut level) = levels.pop() {
+            level.coarse = current;
+            current = levels
+                .last()
+                .map_or_else(|| finest.clone(), |parent| parent.coarse.clone());
+            prolong(&mut current, &level, self.spacing);
+            self.refine(&mut current, self.refine_iterations, self.spacing);
+        }
+        current.write_back(graph);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layouts::layout::position;
+
+    fn setup_grid(side: u32) -> Graph {
+        let mut edges = Vec::new();
+        for row in 0..side {
+            for column in 0..side {
+                let id = row * side + column;
+                if column + 1 < side {
+                    edges.push((id, id + 1));
+                }
+                if row + 1 < side {
+                    edges.push((id, id + side));
+                }
+            }
+        }
+        Graph::from_list_of_edges(edges, false)
+    }
+    fn distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+        ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt()
+    }
+
+    #[test]
+    fn test_coarsening_halves_a_path() -> Result<(), String> {
+        let my_graph = Graph::from_list_of_edges(vec![(0, 1), (1, 2), (2, 3)], false);
+        let level = coarsen(&Embedding::from_graph(&my_graph));
+
+        assert_eq!(level.parent, vec![0, 0, 1, 1]);
+        assert_eq!(level.coarse.edges, vec![(0, 1)]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_multilevel_layout_untangles_grid() -> Result<(), String> {
+        let mut my_graph = setup_grid(20);
+        let mut layout = MultilevelLayout::default();
+
+        layout.apply(&mut my_graph);
+
+        let points: Vec<(f64, f64)> = (0..400)
+            .map(|id| position(&my_graph, id).unwrap())
+            .collect();
+        assert!(points.iter().all(|(x, y)| x.is_finite() && y.is_finite()));
+        let edge_length = distance(points[0], points[1]) + distance(points[210], points[230]);
+        let corners = distance(points[0], points[399]);
+        assert!(
+            edge_length < corners / 5.0,
+            "Opposite corners should be far apart: {} vs {}",
+            edge_length,
+            corners
+        );
+        Ok(())
+    }
+}
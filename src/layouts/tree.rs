@@ -0,0 +1,276 @@
+use crate::{
+    graph::graph::Graph,
+    layouts::layout::{Layout, set_position, sorted_ids, undirected_neighbors},
+};
+use std::collections::{HashMap, VecDeque};
+
+/// How a general graph is reduced to the tree that gets drawn.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SpanningTree {
+    Bfs,
+    Dfs,
+}
+
+impl SpanningTree {
+    pub const ALL: [SpanningTree; 2] = [SpanningTree::Bfs, SpanningTree::Dfs];
+}
+
+impl std::fmt::Display for SpanningTree {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            SpanningTree::Bfs => "BFS tree",
+            SpanningTree::Dfs => "DFS tree",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Reingold–Tilford tidy tree drawing of the spanning tree rooted at `root`:
+/// children sit evenly below their parent, parents are centered above their
+/// children and no two subtrees overlap. Nodes the root cannot reach form
+/// further trees placed to the right.
+#[derive(Debug, PartialEq, Clone)]
+pub struct TreeLayout {
+    pub root: u32,
+    pub spanning: SpanningTree,
+    pub level_spacing: f64,
+    /// Minimum horizontal distance between two nodes on the same level.
+    pub node_spacing: f64,
+}
+
+impl TreeLayout {
+    pub fn new(root: u32) -> Self {
+        TreeLayout {
+            root,
+            spanning: SpanningTree::Bfs,
+            level_spacing: 90.0,
+            node_spacing: 60.0,
+        }
+    }
+
+    /// Children of every node in the spanning forest, sorted by id, and the
+    /// forest roots in the order they are drawn.
+    pub fn spanning_forest(&self, graph: &Graph) -> (HashMap<u32, Vec<u32>>, Vec<u32>) {
+        let neighbors = undirected_neighbors(graph);
+        let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+        let mut visited: HashMap<u32, bool> = HashMap::new();
+        let mut roots = Vec::new();
+        let candidates = std::iter::once(self.root)
+            .filter(|root| graph.nodes.contains_key(root))
+            .chain(sorted_ids(graph));
+        for root in candidates {
+            if visited.contains_key(&root) {
+                continue;
+            }
+            roots.push(root);
+            visited.insert(root, true);
+            match self.spanning {
+                SpanningTree::Bfs => {
+                    let mut queue = VecDeque::from([root]);
+                    while let Some(node) = queue.pop_front() {
+                        for next in neighbors[&node].iter() {
+                            if visited.insert(*next, true).is_none() {
+                                children.entry(node).or_default().push(*next);
+                                queue.push_back(*next);
+                            }
+                        }
+                    }
+                }
+                SpanningTree::Dfs => {
+                    let mut stack = vec![(root, 0)];
+                    while let Some((node, next)) = stack.pop() {
+                        let Some(end) = neighbors[&node].get(next) else {
+                            continue;
+                        };
+                        stack.push((node, next + 1));
+                        if visited.insert(*end, true).is_none() {
+                            children.entry(node).or_default().push(*end);
+                            stack.push((*end, 0));
+                        }
+                    }
+                }
+            }
+        }
+        (children, roots)
+    }
+}
+
+/// Left and right outline of a subtree, one entry per level with the deepest
+/// level first so a parent can be added with a `push`. Stored values are
+/// relative to `offset`, which lets a whole subtree shift in O(1).
+#[derive(Debug, Default)]
+struct Contour {
+    left: Vec<f64>,
+    right: Vec<f64>,
+    offset: f64,
+}
+
+impl Contour {
+    /// Outline value `level`s below the subtree root.
+    fn left_at(&self, level: usize) -> f64 {
+        self.left[self.left.len() - 1 - level] + self.offset
+    }
+    fn right_at(&self, level: usize) -> f64 {
+        self.right[self.right.len() - 1 - level] + self.offset
+    }
+
+    /// Places `next` as close to the right of `self` as `spacing` allows and
+    /// merges the outlines. Returns the shift applied to `next`.
+    fn attach(self, mut next: Contour, spacing: f64) -> (Contour, f64) {
+        let common = self.left.len().min(next.left.len());
+        let shift = (0..common)
+            .map(|level| self.right_at(level) - next.left_at(level) + spacing)
+            .fold(f64::NEG_INFINITY, f64::max);
+        next.offset += shift;
+        let merged = if self.left.len() >= next.left.len() {
+            let mut merged = self;
+            for level in 0..common {
+                let index = merged.right.len() - 1 - level;
+                merged.right[index] = next.right_at(level) - merged.offset;
+            }
+            merged
+        } else {
+            let mut merged = next;
+            for level in 0..common {
+                let index = merged.left.len() - 1 - level;
+                merged.left[index] = self.left_at(level) - merged.offset;
+            }
+            merged
+        };
+        (merged, shift)
+    }
+}
+
+impl Layout for TreeLayout {
+    fn apply(&mut self, graph: &mut Graph) {
+        let (children, roots) = self.spanning_forest(graph);
+        // Post-order without recursion, deep paths would overflow the stack.
+        let mut order = Vec::new();
+        let mut stack = roots.clone();
+        while let Some(node) = stack.pop() {
+            order.push(node);
+            stack.extend(children.get(&node).into_iter().flatten());
+        }
+
+        let mut contours: HashMap<u32, Contour> = HashMap::new();
+        // Horizontal position of every node relative to its parent.
+        let mut relative: HashMap<u32, f64> = HashMap::new();
+        let mut place = |kids: &[u32], contours: &mut HashMap<u32, Contour>| {
+            let mut merged: Option<Contour> = None;
+            let mut positions = Vec::new();
+            for kid in kids {
+                let contour = contours.remove(kid).unwrap_or_default();
+                let (next, shift) = match merged.take() {
+                    None => (contour, 0.0),
+                    Some(merged) => merged.attach(contour, self.node_spacing),
+                };
+                positions.push((*kid, shift));
+                merged = Some(next);
+            }
+            let middle = positions.last().map_or(0.0, |(_, x)| x / 2.0);
+            for (kid, x) in positions {
+                relative.insert(kid, x - middle);
+            }
+            let mut contour = merged.unwrap_or_default();
+            contour.offset -= middle;
+            contour
+        };
+        for node in order.iter().rev() {
+            let kids = children.get(node).map_or(&[][..], |list| list.as_slice());
+            let mut contour = place(kids, &mut contours);
+            contour.left.push(-contour.offset);
+            contour.right.push(-contour.offset);
+            contours.insert(*node, contour);
+        }
+        // The trees of the forest are laid side by side like the children of
+        // an invisible root.
+        place(&roots, &mut contours);
+
+        let mut stack: Vec<(u32, f64, f64)> = roots.iter().map(|root| (*root, 0.0, 0.0)).collect();
+        while let Some((node, parent_x, depth)) = stack.pop() {
+            let x = parent_x + relative.get(&node).copied().unwrap_or_default();
+            set_position(graph, node, (x, depth * self.level_spacing));
+            for kid in children.get(&node).into_iter().flatten() {
+                stack.push((*kid, x, depth + 1.0));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layouts::layout::position;
+
+    fn setup_tree() -> Graph {
+        let edges = vec![
+            (0, 1),
+            (0, 2),
+            (1, 3),
+            (1, 4),
+            (3, 7),
+            (3, 8),
+            (4, 9),
+            (4, 10),
+            (2, 5),
+            (2, 6),
+            (5, 11),
+            (6, 12),
+            (6, 13),
+            (6, 14),
+        ];
+        Graph::from_list_of_edges(edges, false)
+    }
+    fn point(graph: &Graph, id: u32) -> (f64, f64) {
+        position(graph, id).unwrap()
+    }
+
+    #[test]
+    fn test_tree_layout_places_children_below() -> Result<(), String> {
+        let mut my_graph = setup_tree();
+        let mut layout = TreeLayout::new(0);
+        layout.apply(&mut my_graph);
+
+        assert_eq!(point(&my_graph, 0), (0.0, 0.0));
+        assert_eq!(point(&my_graph, 12).1, 3.0 * layout.level_spacing);
+        let parent = point(&my_graph, 6).0;
+        assert_eq!(point(&my_graph, 13).0, parent, "Middle child is centered");
+        assert!(point(&my_graph, 12).0 < point(&my_graph, 14).0);
+
+        let mut level: Vec<f64> = (7..15).map(|id| point(&my_graph, id).0).collect();
+        level.sort_by(f64::total_cmp);
+        for pair in level.windows(2) {
+            assert!(pair[1] - pair[0] >= layout.node_spacing - 1e-9);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_layout_follows_root() -> Result<(), String> {
+        let mut my_graph = setup_tree();
+        let mut layout = TreeLayout::new(6);
+        layout.apply(&mut my_graph);
+
+        assert_eq!(point(&my_graph, 6).1, 0.0);
+        assert_eq!(point(&my_graph, 2).1, layout.level_spacing);
+        assert_eq!(point(&my_graph, 0).1, 2.0 * layout.level_spacing);
+        Ok(())
+    }
+
+    #[test]
+    fn test_spanning_forest_of_cyclic_graph() -> Result<(), String> {
+        let mut my_graph = Graph::from_list_of_edges(vec![(0, 1), (1, 2), (2, 0), (2, 3)], true);
+        my_graph.add_new_node_to_graph(4, 0.0, 0.0);
+        let mut layout = TreeLayout::new(0);
+
+        let (children, roots) = layout.spanning_forest(&my_graph);
+        assert_eq!(children[&0], vec![1, 2]);
+        assert_eq!(roots, vec![0, 4]);
+
+        layout.spanning = SpanningTree::Dfs;
+        let (children, _) = layout.spanning_forest(&my_graph);
+        assert_eq!(children[&0], vec![1]);
+        assert_eq!(children[&2], vec![3]);
+        Ok(())
+    }
+}
@@ -1,7 +1,9 @@
 pub mod algorithms;
 pub mod edge;
 pub mod graph;
+pub mod generators;
 pub mod gui_graphs;
 pub mod gui_rust;
+pub mod layouts;
 pub mod logger;
 pub mod node;
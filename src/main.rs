@@ -1,5 +1,7 @@
 use graph_visualizer::gui_rust::gui_model::GraphApp;
 
 fn main() -> iced::Result {
-    iced::run("Graph visualizer", GraphApp::update, GraphApp::view)
+    iced::application("Graph visualizer", GraphApp::update, GraphApp::view)
+        .subscription(GraphApp::subscription)
+        .run()
 }
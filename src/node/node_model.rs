@@ -9,7 +9,7 @@ pub struct NodeSnapshot {
     pub edges: Vec<EdgeSnapshot>,
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
 pub struct Runtime {
     pub edges: HashMap<u32, Edge>,
 }
@@ -20,22 +20,30 @@ impl Default for Runtime {
         }
     }
 }
-#[derive(PartialEq, Debug)]
+pub const DEFAULT_RADIUS: f64 = 15.0;
+
+#[derive(PartialEq, Debug, Clone)]
 pub struct GUIModel {
     pub position_x: OrderedFloat<f64>,
     pub position_y: OrderedFloat<f64>,
     pub radius: OrderedFloat<f64>,
+    /// Drawn instead of the node id when set.
+    pub label: Option<String>,
+    /// Placed by hand; automatic layouts leave it where it is.
+    pub pinned: bool,
 }
 impl Default for GUIModel {
     fn default() -> GUIModel {
         GUIModel {
             position_x: OrderedFloat(0.0),
             position_y: OrderedFloat(0.0),
-            radius: OrderedFloat(1.0),
+            radius: OrderedFloat(DEFAULT_RADIUS),
+            label: None,
+            pinned: false,
         }
     }
 }
-#[derive(PartialEq, Debug, Default)]
+#[derive(PartialEq, Debug, Default, Clone)]
 pub struct Node {
     pub id: u32,
     pub runtime: Runtime,
@@ -50,6 +58,8 @@ impl Node {
                 position_x: OrderedFloat(position_x),
                 position_y: OrderedFloat(position_y),
                 radius: OrderedFloat(radius),
+                label: None,
+                pinned: false,
             },
         }
     }
@@ -73,7 +83,7 @@ impl Node {
 }
 impl Node {
     pub fn create_new(id: u32, x: f64, y: f64) -> Self {
-        Self::new(id, x, y, 1.0)
+        Self::new(id, x, y, DEFAULT_RADIUS)
     }
     pub fn add_new_edge_to_node(&mut self, id: u32) {
         self.runtime
@@ -103,7 +113,7 @@ mod tests {
     #[test]
     fn test_node_default() -> Result<(), String> {
         let node = Node::new_node_from_id(10);
-        assert_eq!(node.gui_model.radius, OrderedFloat(1.0));
+        assert_eq!(node.gui_model.radius, OrderedFloat(DEFAULT_RADIUS));
         Ok(())
     }
 